@@ -1,8 +1,9 @@
 //! Attio API client implementation.
 
+use crate::attio::types::{AttioObject, AttioRecord, AttioRecordId};
+use crate::attio::webhooks::{AttioWebhook, AttioWebhookSubscription};
 use crate::config::AttioConfig;
 use crate::error::{Error, Result};
-use crate::attio::types::{AttioObject, AttioRecord, AttioRecordId};
 use serde_json::Value;
 
 /// Client for interacting with the Attio API
@@ -98,7 +99,7 @@ impl AttioClient {
         })
     }
 
-    /// Query records that changed since a given timestamp
+    /// Query records that changed at or after a given timestamp, oldest first
     pub async fn get_changes_since(
         &self,
         object: &str,
@@ -111,12 +112,36 @@ impl AttioClient {
                 }
             },
             "sorts": [
-                {"attribute": "updated_at", "direction": "asc"}
+                {"attribute": "updated_at", "direction": "asc"},
+                {"attribute": "record_id", "direction": "asc"}
             ]
         });
         self.list_records(object, Some(filter), None, None).await
     }
 
+    /// List a page of records ordered by ID, starting after a record ID (keyset pagination)
+    pub async fn list_records_after(
+        &self,
+        object: &str,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<AttioRecord>> {
+        let mut query = serde_json::json!({
+            "sorts": [
+                {"attribute": "record_id", "direction": "asc"}
+            ]
+        });
+        if let Some(after) = after {
+            query["filter"] = serde_json::json!({
+                "record_id": {
+                    "$gt": after
+                }
+            });
+        }
+        self.list_records(object, Some(query), Some(limit), None)
+            .await
+    }
+
     /// Assert a record (upsert by matching attributes)
     pub async fn assert_record(
         &self,
@@ -134,6 +159,68 @@ impl AttioClient {
             message: "Not implemented".to_string(),
         })
     }
+
+    /// Create a webhook subscription
+    pub async fn create_webhook(
+        &self,
+        target_url: &str,
+        subscriptions: Vec<AttioWebhookSubscription>,
+    ) -> Result<AttioWebhook> {
+        let _url = format!("{}/v2/webhooks", self.base_url());
+        let _body = serde_json::json!({
+            "data": {
+                "target_url": target_url,
+                "subscriptions": subscriptions
+            }
+        });
+        // TODO: Implement HTTP request
+        Err(Error::Internal {
+            message: "Not implemented".to_string(),
+        })
+    }
+
+    /// List all webhooks in the workspace
+    pub async fn list_webhooks(&self) -> Result<Vec<AttioWebhook>> {
+        let _url = format!("{}/v2/webhooks", self.base_url());
+        // TODO: Implement HTTP request
+        Err(Error::Internal {
+            message: "Not implemented".to_string(),
+        })
+    }
+
+    /// Update a webhook's target URL and/or subscriptions
+    pub async fn update_webhook(
+        &self,
+        webhook_id: &str,
+        target_url: Option<&str>,
+        subscriptions: Option<Vec<AttioWebhookSubscription>>,
+    ) -> Result<AttioWebhook> {
+        let _url = format!("{}/v2/webhooks/{}", self.base_url(), webhook_id);
+        let mut data = serde_json::Map::new();
+        if let Some(target_url) = target_url {
+            data.insert("target_url".to_string(), Value::from(target_url));
+        }
+        if let Some(subscriptions) = subscriptions {
+            data.insert(
+                "subscriptions".to_string(),
+                serde_json::to_value(subscriptions)?,
+            );
+        }
+        let _body = serde_json::json!({ "data": data });
+        // TODO: Implement HTTP request
+        Err(Error::Internal {
+            message: "Not implemented".to_string(),
+        })
+    }
+
+    /// Delete a webhook
+    pub async fn delete_webhook(&self, webhook_id: &str) -> Result<()> {
+        let _url = format!("{}/v2/webhooks/{}", self.base_url(), webhook_id);
+        // TODO: Implement HTTP request
+        Err(Error::Internal {
+            message: "Not implemented".to_string(),
+        })
+    }
 }
 
 impl AttioRecordId {
@@ -151,6 +238,7 @@ mod tests {
         AttioConfig {
             api_key: "test_key".to_string(),
             webhook_secret: None,
+            api_token_id: None,
             base_url: "https://api.attio.com".to_string(),
         }
     }
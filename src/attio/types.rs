@@ -239,6 +239,30 @@ impl AttioRecord {
             AttioValue::Multiple(items) => items.first().and_then(|i| i.as_number()),
         })
     }
+
+    /// Flatten record values to plain JSON for field mapping.
+    ///
+    /// Single values become the item itself, multi-value attributes become arrays
+    /// and empty attributes are omitted.
+    pub fn to_json(&self) -> Value {
+        let values = self
+            .values
+            .iter()
+            .filter_map(|(slug, value)| {
+                let flattened = match value {
+                    AttioValue::Single(item) => item.to_json(),
+                    AttioValue::Multiple(items) => match items.as_slice() {
+                        [] => return None,
+                        [item] => item.to_json(),
+                        items => Value::Array(items.iter().map(|i| i.to_json()).collect()),
+                    },
+                };
+                Some((slug.clone(), flattened))
+            })
+            .collect();
+
+        Value::Object(values)
+    }
 }
 
 impl AttioValueItem {
@@ -254,6 +278,28 @@ impl AttioValueItem {
         }
     }
 
+    /// Convert to plain JSON: scalar values unwrap, structured values keep their fields
+    pub fn to_json(&self) -> Value {
+        match &self.value {
+            AttioValueType::Text { value } | AttioValueType::Date { value } => {
+                Value::String(value.clone())
+            }
+            AttioValueType::Number { value } => serde_json::json!(value),
+            AttioValueType::Currency {
+                currency_value,
+                currency_code,
+            } => serde_json::json!({
+                "value": currency_value,
+                "currency_code": currency_code,
+            }),
+            AttioValueType::Timestamp { value } => Value::String(value.to_rfc3339()),
+            AttioValueType::Boolean { value } => Value::Bool(*value),
+            AttioValueType::Option { option } => Value::String(option.title.clone()),
+            AttioValueType::Raw(value) => value.clone(),
+            other => serde_json::to_value(other).unwrap_or(Value::Null),
+        }
+    }
+
     /// Convert to number if possible
     pub fn as_number(&self) -> Option<f64> {
         match &self.value {
@@ -280,6 +326,29 @@ impl std::fmt::Display for AttioObjectId {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_record_to_json() {
+        let record: AttioRecord = serde_json::from_value(serde_json::json!({
+            "id": "rec_1",
+            "object": "obj_1",
+            "values": {
+                "name": [{"value": "Acme"}],
+                "domains": [{"domain": "acme.com"}, {"domain": "acme.io"}],
+                "estimated_arr_usd": [{"currency_value": 1000.0, "currency_code": "USD"}],
+                "description": []
+            },
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+
+        let json = record.to_json();
+        assert_eq!(json["name"], "Acme");
+        assert_eq!(json["domains"][1]["domain"], "acme.io");
+        assert_eq!(json["estimated_arr_usd"]["value"], 1000.0);
+        assert!(json.get("description").is_none());
+    }
+
     #[test]
     fn test_attio_record_id_display() {
         let id = AttioRecordId("abc123".to_string());
@@ -1,12 +1,18 @@
 //! Attio webhook handling.
 
+use crate::config::Config;
 use crate::error::{Error, Result};
+use crate::sync::SyncDirectionHandler;
 use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
+use std::collections::HashMap;
 
 type HmacSha256 = Hmac<Sha256>;
 
+/// Header carrying the HMAC-SHA256 signature of the payload
+pub const SIGNATURE_HEADER: &str = "Attio-Signature";
+
 /// Attio webhook event
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttioWebhookEvent {
@@ -19,9 +25,13 @@ pub struct AttioWebhookEvent {
     /// Object type (e.g., "companies", "people")
     pub object: String,
 
-    /// Record ID that was affected
+    /// Record ID that was affected (the surviving record, for merges)
     pub record_id: String,
 
+    /// Record merged into `record_id` (for `record_merged`)
+    #[serde(default)]
+    pub duplicate_record_id: Option<String>,
+
     /// Event timestamp
     pub timestamp: String,
 
@@ -69,6 +79,228 @@ pub struct AttioActor {
     pub id: Option<String>,
 }
 
+/// Webhook registered in Attio via the `/v2/webhooks` API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttioWebhook {
+    /// Webhook identifier
+    pub id: AttioWebhookId,
+
+    /// URL Attio delivers events to
+    pub target_url: String,
+
+    /// Event subscriptions
+    #[serde(default)]
+    pub subscriptions: Vec<AttioWebhookSubscription>,
+
+    /// Delivery status
+    pub status: AttioWebhookStatus,
+
+    /// Signing secret (only returned when the webhook is created)
+    #[serde(default)]
+    pub secret: Option<String>,
+
+    /// Creation timestamp
+    pub created_at: Option<String>,
+}
+
+/// Attio webhook identifier
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AttioWebhookId {
+    /// Workspace ID
+    pub workspace_id: String,
+
+    /// Webhook ID
+    pub webhook_id: String,
+}
+
+/// A single event subscription on a webhook
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttioWebhookSubscription {
+    /// Event type (e.g., "record.created")
+    pub event_type: String,
+
+    /// Optional filter restricting which events are delivered
+    #[serde(default)]
+    pub filter: Option<serde_json::Value>,
+}
+
+/// Delivery status of a webhook
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AttioWebhookStatus {
+    /// Events are being delivered
+    Active,
+    /// Deliveries are failing
+    Degraded,
+    /// Deliveries have been paused
+    Inactive,
+    /// Unknown status
+    #[serde(other)]
+    Unknown,
+}
+
+/// Subscription the bridge needs for a configured object
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequiredSubscription {
+    /// Event type
+    pub event_type: AttioEventType,
+
+    /// Attio object slug
+    pub object: String,
+}
+
+/// Drift between the subscriptions the bridge needs and those live in Attio
+#[derive(Debug, Clone, Default)]
+pub struct WebhookDrift {
+    /// Required subscriptions with no matching live subscription
+    pub missing: Vec<RequiredSubscription>,
+
+    /// Live subscriptions that no enabled mapping needs
+    pub unexpected: Vec<AttioWebhookSubscription>,
+
+    /// Webhooks that are not actively delivering events
+    pub inactive: Vec<String>,
+}
+
+/// Record events the bridge subscribes to for every synced object
+const RECORD_EVENTS: &[AttioEventType] = &[
+    AttioEventType::RecordCreated,
+    AttioEventType::RecordUpdated,
+    AttioEventType::RecordDeleted,
+    AttioEventType::RecordMerged,
+];
+
+impl AttioEventType {
+    /// Event type name used by the webhooks API (e.g., "record.created")
+    pub fn api_name(&self) -> Option<&'static str> {
+        match self {
+            Self::RecordCreated => Some("record.created"),
+            Self::RecordUpdated => Some("record.updated"),
+            Self::RecordDeleted => Some("record.deleted"),
+            Self::RecordMerged => Some("record.merged"),
+            Self::ListEntryCreated => Some("list-entry.created"),
+            Self::ListEntryUpdated => Some("list-entry.updated"),
+            Self::ListEntryDeleted => Some("list-entry.deleted"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl RequiredSubscription {
+    /// Build the API subscription, filtered to the given Attio object ID
+    pub fn to_subscription(&self, object_id: &str) -> AttioWebhookSubscription {
+        AttioWebhookSubscription {
+            event_type: self.event_type.api_name().unwrap_or_default().to_string(),
+            filter: Some(serde_json::json!({
+                "$and": [
+                    {"field": "id.object_id", "operator": "equals", "value": object_id}
+                ]
+            })),
+        }
+    }
+}
+
+impl AttioWebhookSubscription {
+    /// Object ID this subscription is filtered to (None if unfiltered)
+    pub fn object_id(&self) -> Option<&str> {
+        self.filter
+            .as_ref()?
+            .get("$and")?
+            .as_array()?
+            .iter()
+            .find(|f| f.get("field").and_then(|v| v.as_str()) == Some("id.object_id"))?
+            .get("value")?
+            .as_str()
+    }
+
+    /// Whether this subscription delivers the given event for the given object
+    fn covers(&self, event_type: &str, object_id: Option<&str>) -> bool {
+        self.event_type == event_type
+            && match (self.object_id(), object_id) {
+                (None, _) => true,
+                (Some(filtered), Some(id)) => filtered == id,
+                (Some(_), None) => false,
+            }
+    }
+}
+
+impl WebhookDrift {
+    /// Whether live subscriptions match the configuration exactly
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty() && self.inactive.is_empty()
+    }
+}
+
+/// Subscriptions needed for the objects enabled in the configuration
+pub fn required_subscriptions(config: &Config) -> Vec<RequiredSubscription> {
+    // Attio events only matter when changes flow from Attio to Salesforce
+    if !SyncDirectionHandler::new(config.sync.direction).attio_to_sf_enabled() {
+        return vec![];
+    }
+
+    config
+        .enabled_mappings()
+        .iter()
+        .flat_map(|mapping| {
+            RECORD_EVENTS.iter().map(|event_type| RequiredSubscription {
+                event_type: event_type.clone(),
+                object: mapping.attio_object.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Compare required subscriptions against live webhooks
+///
+/// `object_ids` maps Attio object slugs to object IDs, since subscription
+/// filters reference objects by ID.
+pub fn detect_drift(
+    required: &[RequiredSubscription],
+    object_ids: &HashMap<String, String>,
+    live: &[AttioWebhook],
+) -> WebhookDrift {
+    let live_subscriptions: Vec<&AttioWebhookSubscription> = live
+        .iter()
+        .flat_map(|webhook| webhook.subscriptions.iter())
+        .collect();
+
+    let missing = required
+        .iter()
+        .filter(|req| {
+            let event_type = req.event_type.api_name().unwrap_or_default();
+            let object_id = object_ids.get(&req.object).map(|s| s.as_str());
+            object_id.is_none()
+                || !live_subscriptions
+                    .iter()
+                    .any(|sub| sub.covers(event_type, object_id))
+        })
+        .cloned()
+        .collect();
+
+    let unexpected = live_subscriptions
+        .into_iter()
+        .filter(|sub| {
+            !required.iter().any(|req| {
+                let event_type = req.event_type.api_name().unwrap_or_default();
+                sub.covers(event_type, object_ids.get(&req.object).map(|s| s.as_str()))
+            })
+        })
+        .cloned()
+        .collect();
+
+    let inactive = live
+        .iter()
+        .filter(|webhook| webhook.status != AttioWebhookStatus::Active)
+        .map(|webhook| webhook.id.webhook_id.clone())
+        .collect();
+
+    WebhookDrift {
+        missing,
+        unexpected,
+        inactive,
+    }
+}
+
 /// Verify webhook signature
 pub fn verify_signature(payload: &[u8], signature: &str, secret: &str) -> Result<()> {
     let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
@@ -133,6 +365,78 @@ mod tests {
         assert_eq!(event.object, "companies");
     }
 
+    fn test_webhook(subscriptions: Vec<AttioWebhookSubscription>) -> AttioWebhook {
+        AttioWebhook {
+            id: AttioWebhookId {
+                workspace_id: "ws_123".to_string(),
+                webhook_id: "wh_1".to_string(),
+            },
+            target_url: "https://sync.example.com/webhooks/attio".to_string(),
+            subscriptions,
+            status: AttioWebhookStatus::Active,
+            secret: None,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_webhook_subscription() {
+        let payload = r#"{
+            "id": {"workspace_id": "ws_123", "webhook_id": "wh_1"},
+            "target_url": "https://sync.example.com/webhooks/attio",
+            "subscriptions": [{
+                "event_type": "record.created",
+                "filter": {"$and": [{"field": "id.object_id", "operator": "equals", "value": "obj_1"}]}
+            }],
+            "status": "active",
+            "secret": "whsec_abc",
+            "created_at": "2024-01-01T00:00:00Z"
+        }"#;
+
+        let webhook: AttioWebhook = serde_json::from_str(payload).unwrap();
+        assert_eq!(webhook.status, AttioWebhookStatus::Active);
+        assert_eq!(webhook.secret.as_deref(), Some("whsec_abc"));
+        assert_eq!(webhook.subscriptions[0].object_id(), Some("obj_1"));
+    }
+
+    #[test]
+    fn test_detect_drift() {
+        let required = vec![
+            RequiredSubscription {
+                event_type: AttioEventType::RecordCreated,
+                object: "companies".to_string(),
+            },
+            RequiredSubscription {
+                event_type: AttioEventType::RecordUpdated,
+                object: "companies".to_string(),
+            },
+        ];
+        let object_ids: HashMap<String, String> =
+            [("companies".to_string(), "obj_companies".to_string())].into();
+
+        let live = vec![test_webhook(vec![
+            required[0].to_subscription("obj_companies"),
+            RequiredSubscription {
+                event_type: AttioEventType::RecordCreated,
+                object: "deals".to_string(),
+            }
+            .to_subscription("obj_deals"),
+        ])];
+
+        let drift = detect_drift(&required, &object_ids, &live);
+        assert!(!drift.is_clean());
+        assert_eq!(drift.missing, vec![required[1].clone()]);
+        assert_eq!(drift.unexpected.len(), 1);
+        assert_eq!(drift.unexpected[0].object_id(), Some("obj_deals"));
+        assert!(drift.inactive.is_empty());
+
+        let live = vec![test_webhook(vec![
+            required[0].to_subscription("obj_companies"),
+            required[1].to_subscription("obj_companies"),
+        ])];
+        assert!(detect_drift(&required, &object_ids, &live).is_clean());
+    }
+
     #[test]
     fn test_constant_time_compare() {
         assert!(constant_time_compare(b"hello", b"hello"));
@@ -1,13 +1,42 @@
 //! CLI tool for local development and testing.
 
 #[cfg(feature = "cli")]
-use clap::{Parser, Subcommand};
+use attio_sfdc::attio::webhooks::{detect_drift, required_subscriptions, RequiredSubscription};
+#[cfg(feature = "cli")]
+use attio_sfdc::attio::AttioClient;
+#[cfg(feature = "cli")]
+use attio_sfdc::storage::{MemoryStorage, Page, Storage, DEFAULT_PAGE_SIZE};
+#[cfg(feature = "cli")]
+use attio_sfdc::sync::matching::parse_link_csv;
+#[cfg(feature = "cli")]
+use attio_sfdc::sync::pending;
+#[cfg(feature = "cli")]
+use attio_sfdc::sync::scheduled::run_manual_sync;
+#[cfg(feature = "cli")]
+use attio_sfdc::sync::{
+    CandidateFilter, CandidateStatus, ConflictFilter, ConflictStatus, FieldChoice,
+    ManualResolution, RunStatus, SyncMode, SyncPlan,
+};
+#[cfg(feature = "cli")]
+use attio_sfdc::{Config, Error, Result, SyncEngine};
+#[cfg(feature = "cli")]
+use clap::{Args, Parser, Subcommand};
+#[cfg(feature = "cli")]
+use std::collections::HashMap;
+#[cfg(feature = "cli")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "cli")]
+use std::rc::Rc;
 
 #[cfg(feature = "cli")]
 #[derive(Parser)]
 #[command(name = "attio-sfdc")]
 #[command(about = "Attio-Salesforce sync bridge CLI", long_about = None)]
 struct Cli {
+    /// Local state file (ID mappings, cursors, webhook secrets)
+    #[arg(long, global = true, default_value = "attio-sfdc-state.json")]
+    state: PathBuf,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -16,15 +45,7 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Trigger a sync operation
-    Sync {
-        /// Sync direction: attio-to-sf, sf-to-attio, or bidirectional
-        #[arg(short, long, default_value = "bidirectional")]
-        direction: String,
-
-        /// Specific object to sync (optional)
-        #[arg(short, long)]
-        object: Option<String>,
-    },
+    Sync(SyncArgs),
     /// Check connection to APIs
     Check {
         /// Check only Attio connection
@@ -37,14 +58,203 @@ enum Commands {
     },
     /// Show current configuration
     Config,
+    /// Show bridge status, including references stuck waiting for their target
+    Status,
     /// Show sync history
     History {
-        /// Number of entries to show
-        #[arg(short, long, default_value = "10")]
+        /// Status: running, succeeded, partial, or failed
+        #[arg(long)]
+        status: Option<String>,
+
+        /// Page number (1-based)
+        #[arg(long, default_value = "1")]
+        page: usize,
+
+        /// Number of entries to show per page
+        #[arg(short, long = "per-page", alias = "limit", default_value = "10")]
         limit: usize,
     },
-    /// List unresolved conflicts
-    Conflicts,
+    /// Show the writes the bridge made to or from a record
+    Audit {
+        /// Attio object slug or Salesforce object name (e.g., companies or Account)
+        object: String,
+
+        /// Attio or Salesforce record ID
+        id: String,
+
+        /// Page number (1-based)
+        #[arg(long, default_value = "1")]
+        page: usize,
+
+        /// Entries per page
+        #[arg(long, default_value_t = DEFAULT_PAGE_SIZE)]
+        per_page: usize,
+    },
+    /// List conflicts (pending by default) or resolve one
+    #[command(args_conflicts_with_subcommands = true)]
+    Conflicts {
+        #[command(subcommand)]
+        command: Option<ConflictCommands>,
+
+        #[command(flatten)]
+        list: ConflictListArgs,
+    },
+    /// Manage Attio webhook subscriptions
+    Webhooks {
+        #[command(subcommand)]
+        command: WebhookCommands,
+    },
+    /// Review proposed links or import known record pairs
+    Links {
+        #[command(subcommand)]
+        command: LinkCommands,
+    },
+}
+
+#[cfg(feature = "cli")]
+#[derive(Args)]
+struct SyncArgs {
+    /// Sync direction: attio-to-sf, sf-to-attio, or bidirectional
+    #[arg(short, long, default_value = "bidirectional")]
+    direction: String,
+
+    /// Specific object to sync (optional)
+    #[arg(short, long, conflicts_with = "full")]
+    object: Option<String>,
+
+    /// Sync every record instead of recent changes, resuming from the last checkpoint
+    #[arg(long)]
+    full: bool,
+
+    /// Discard the full sync checkpoint and start over
+    #[arg(long, requires = "full")]
+    restart: bool,
+
+    /// Show what would be written without writing anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Output format for the dry-run plan: table or json
+    #[arg(long, default_value = "table")]
+    format: String,
+}
+
+#[cfg(feature = "cli")]
+#[derive(Args)]
+struct ConflictListArgs {
+    /// Only conflicts on this Attio or Salesforce object
+    #[arg(long)]
+    object: Option<String>,
+
+    /// Status: pending, auto_resolved, manually_resolved, or skipped
+    #[arg(long, default_value = "pending")]
+    status: String,
+
+    /// Only conflicts detected more than this many hours ago
+    #[arg(long)]
+    older_than_hours: Option<i64>,
+
+    /// Only conflicts detected within this many hours
+    #[arg(long)]
+    newer_than_hours: Option<i64>,
+
+    /// Page number (1-based)
+    #[arg(long, default_value = "1")]
+    page: usize,
+
+    /// Conflicts per page
+    #[arg(long, default_value_t = DEFAULT_PAGE_SIZE)]
+    per_page: usize,
+}
+
+#[cfg(feature = "cli")]
+#[derive(Subcommand)]
+enum ConflictCommands {
+    /// Resolve a pending conflict field by field
+    Resolve {
+        /// Conflict ID
+        id: String,
+
+        /// Winner for a field: <attio_field>=attio|salesforce|custom:<value> (repeatable)
+        #[arg(long = "field", value_name = "FIELD=CHOICE")]
+        fields: Vec<String>,
+
+        /// Note stored with the resolution
+        #[arg(long)]
+        note: Option<String>,
+
+        /// Who resolved the conflict (defaults to $USER)
+        #[arg(long)]
+        by: Option<String>,
+    },
+}
+
+#[cfg(feature = "cli")]
+#[derive(Subcommand)]
+enum LinkCommands {
+    /// List link candidates (pending by default), highest confidence first
+    Review {
+        /// Only candidates on this Attio or Salesforce object
+        #[arg(long)]
+        object: Option<String>,
+
+        /// Status: pending, approved, or rejected
+        #[arg(long, default_value = "pending")]
+        status: String,
+
+        /// Page number (1-based)
+        #[arg(long, default_value = "1")]
+        page: usize,
+
+        /// Candidates per page
+        #[arg(long, default_value_t = DEFAULT_PAGE_SIZE)]
+        per_page: usize,
+    },
+    /// Approve a pending link candidate, linking its records
+    Approve {
+        /// Candidate ID
+        id: String,
+
+        /// Who approved the link (defaults to $USER)
+        #[arg(long)]
+        by: Option<String>,
+    },
+    /// Reject a pending link candidate so it is not proposed again
+    Reject {
+        /// Candidate ID
+        id: String,
+
+        /// Who rejected the link (defaults to $USER)
+        #[arg(long)]
+        by: Option<String>,
+    },
+    /// Link record pairs from a CSV file with attio_object, attio_id,
+    /// salesforce_object and salesforce_id columns
+    Import {
+        /// CSV file
+        file: PathBuf,
+
+        /// Who imported the links (defaults to $USER)
+        #[arg(long)]
+        by: Option<String>,
+    },
+}
+
+#[cfg(feature = "cli")]
+#[derive(Subcommand)]
+enum WebhookCommands {
+    /// Subscribe to the record events needed by the enabled mappings
+    Install {
+        /// Base URL of the deployed worker
+        #[arg(long)]
+        url: String,
+    },
+    /// Compare live subscriptions against the enabled mappings
+    Status {
+        /// Only consider webhooks delivering to this worker URL
+        #[arg(long)]
+        url: Option<String>,
+    },
 }
 
 #[cfg(feature = "cli")]
@@ -53,13 +263,11 @@ async fn main() {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Sync { direction, object } => {
-            println!("Starting {} sync...", direction);
-            if let Some(obj) = object {
-                println!("Syncing object: {}", obj);
+        Commands::Sync(args) => {
+            if let Err(e) = run_sync(&args, &cli.state).await {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
             }
-            // TODO: Implement sync
-            println!("Sync complete!");
         }
         Commands::Check { attio_only, sf_only } => {
             if !sf_only {
@@ -79,19 +287,644 @@ async fn main() {
             println!("  Sync direction: bidirectional");
             println!("  Batch size: 100");
         }
-        Commands::History { limit } => {
-            println!("Recent sync history (last {}):", limit);
-            // TODO: Load and display history
-            println!("  No sync history found");
+        Commands::Status => {
+            if let Err(e) = run_status(&cli.state).await {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::History {
+            status,
+            page,
+            limit,
+        } => {
+            if let Err(e) = run_history(status.as_deref(), Page::new(page, limit), &cli.state).await
+            {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Audit {
+            object,
+            id,
+            page,
+            per_page,
+        } => {
+            if let Err(e) = run_audit(&object, &id, Page::new(page, per_page), &cli.state).await {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Conflicts { command, list } => {
+            let result = match command {
+                Some(ConflictCommands::Resolve {
+                    id,
+                    fields,
+                    note,
+                    by,
+                }) => run_resolve_conflict(&id, &fields, note, by, &cli.state).await,
+                None => run_conflicts(list, &cli.state).await,
+            };
+            if let Err(e) = result {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Webhooks { command } => {
+            if let Err(e) = run_webhooks(command, &cli.state).await {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
         }
-        Commands::Conflicts => {
-            println!("Unresolved conflicts:");
-            // TODO: Load and display conflicts
-            println!("  No conflicts found");
+        Commands::Links { command } => {
+            if let Err(e) = run_links(command, &cli.state).await {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
         }
     }
 }
 
+#[cfg(feature = "cli")]
+async fn run_webhooks(command: WebhookCommands, state: &Path) -> Result<()> {
+    let config = Config::from_env()?;
+    let attio = AttioClient::new(config.attio.clone());
+    let required = required_subscriptions(&config);
+    let object_ids = resolve_object_ids(&attio, &required).await?;
+
+    match command {
+        WebhookCommands::Install { url } => {
+            let target_url = webhook_target_url(&url);
+            let subscriptions: Vec<_> = required
+                .iter()
+                .filter_map(|req| {
+                    object_ids
+                        .get(&req.object)
+                        .map(|id| req.to_subscription(id))
+                })
+                .collect();
+
+            let existing = attio
+                .list_webhooks()
+                .await?
+                .into_iter()
+                .find(|webhook| webhook.target_url == target_url);
+
+            let webhook = match existing {
+                Some(webhook) => {
+                    println!("Updating webhook {}...", webhook.id.webhook_id);
+                    attio
+                        .update_webhook(&webhook.id.webhook_id, None, Some(subscriptions))
+                        .await?
+                }
+                None => {
+                    println!("Creating webhook for {}...", target_url);
+                    attio.create_webhook(&target_url, subscriptions).await?
+                }
+            };
+            println!(
+                "  {} subscriptions on webhook {}",
+                webhook.subscriptions.len(),
+                webhook.id.webhook_id
+            );
+
+            if let Some(secret) = webhook.secret {
+                let storage = open_state(state)?;
+                storage
+                    .save_webhook_secret(&webhook.id.webhook_id, &secret)
+                    .await?;
+                save_state(state, &storage)?;
+                println!("  Signing secret stored in {}", state.display());
+                println!("  Set it on the worker: wrangler secret put ATTIO_WEBHOOK_SECRET");
+            }
+        }
+        WebhookCommands::Status { url } => {
+            let target_url = url.map(|u| webhook_target_url(&u));
+            let live: Vec<_> = attio
+                .list_webhooks()
+                .await?
+                .into_iter()
+                .filter(|webhook| target_url.as_ref().is_none_or(|t| &webhook.target_url == t))
+                .collect();
+
+            let storage = open_state(state)?;
+            println!("Live webhooks:");
+            if live.is_empty() {
+                println!("  No webhooks found");
+            }
+            for webhook in &live {
+                let secret = storage.get_webhook_secret(&webhook.id.webhook_id).await?;
+                println!(
+                    "  {} → {} ({:?}, {} subscriptions, {})",
+                    webhook.id.webhook_id,
+                    webhook.target_url,
+                    webhook.status,
+                    webhook.subscriptions.len(),
+                    if secret.is_some() {
+                        "secret stored"
+                    } else {
+                        "no stored secret"
+                    }
+                );
+            }
+
+            let drift = detect_drift(&required, &object_ids, &live);
+            if drift.is_clean() {
+                println!("Subscriptions match the enabled mappings");
+                return Ok(());
+            }
+
+            println!("Drift detected:");
+            for missing in &drift.missing {
+                println!(
+                    "  missing:    {} on {}",
+                    missing.event_type.api_name().unwrap_or_default(),
+                    missing.object
+                );
+            }
+            for unexpected in &drift.unexpected {
+                println!(
+                    "  unexpected: {} on {}",
+                    unexpected.event_type,
+                    unexpected.object_id().unwrap_or("all objects")
+                );
+            }
+            for webhook_id in &drift.inactive {
+                println!("  inactive:   webhook {}", webhook_id);
+            }
+            std::process::exit(2);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "cli")]
+async fn run_sync(args: &SyncArgs, state: &Path) -> Result<()> {
+    let mut config = Config::from_env()?;
+    config.sync.direction = args.direction.replace('-', "_").parse()?;
+    let mode = if args.dry_run {
+        SyncMode::Plan
+    } else {
+        SyncMode::Apply
+    };
+    let storage = Rc::new(open_state(state)?);
+    let mut engine = SyncEngine::new(config)
+        .with_storage(storage.clone())
+        .with_mode(mode);
+
+    if mode == SyncMode::Plan {
+        let result = if args.full {
+            engine.full_sync(args.restart).await?
+        } else {
+            run_manual_sync(&mut engine, &*storage, args.object.as_deref()).await?
+        };
+        return print_plan(&result.plan, &args.format);
+    }
+
+    println!("Starting {} sync...", args.direction);
+    let result = if args.full {
+        engine.full_sync(args.restart).await
+    } else {
+        run_manual_sync(&mut engine, &*storage, args.object.as_deref()).await
+    };
+    // Save even when the sync fails, so an interrupted full sync resumes from its checkpoint
+    save_state(state, &storage)?;
+    let result = result?;
+
+    println!(
+        "Sync complete: {} processed, {} created, {} updated, {} deleted, {} unchanged, {} linked, {} to review, {} conflicts, {} errors",
+        result.records_processed,
+        result.records_created,
+        result.records_updated,
+        result.records_deleted,
+        result.records_unchanged,
+        result.records_linked,
+        result.link_candidates,
+        result.conflicts,
+        result.errors
+    );
+    for failure in &result.failures {
+        println!("  failed: {}", failure);
+    }
+    Ok(())
+}
+
+/// Print a dry-run plan as a table or as JSON
+#[cfg(feature = "cli")]
+fn print_plan(plan: &SyncPlan, format: &str) -> Result<()> {
+    match format {
+        "json" => {
+            println!("{}", serde_json::to_string_pretty(plan)?);
+            return Ok(());
+        }
+        "table" => {}
+        _ => {
+            return Err(Error::validation(format!(
+                "Invalid format: {} (expected table or json)",
+                format
+            )))
+        }
+    }
+
+    if plan.is_empty() {
+        println!("Nothing to do");
+        return Ok(());
+    }
+    println!(
+        "{:<8} {:<32} {:<32} FIELD CHANGES",
+        "ACTION", "SOURCE", "TARGET"
+    );
+    let changes = plan
+        .creates
+        .iter()
+        .chain(&plan.updates)
+        .chain(&plan.deletes);
+    for change in changes {
+        let target = format!(
+            "{}/{}",
+            change.target_object,
+            change.target_id.as_deref().unwrap_or("(new)")
+        );
+        let fields: Vec<_> = change
+            .changes
+            .iter()
+            .map(|c| {
+                let before = c.before.as_ref().map(|v| v.to_string());
+                format!(
+                    "{}: {} → {}",
+                    c.field,
+                    before.as_deref().unwrap_or("(unset)"),
+                    c.after
+                )
+            })
+            .collect();
+        println!(
+            "{:<8} {:<32} {:<32} {}",
+            format!("{:?}", change.action).to_lowercase(),
+            format!("{}/{}", change.source_object, change.source_id),
+            target,
+            fields.join("; ")
+        );
+    }
+    for conflict in &plan.conflicts {
+        let fields: Vec<_> = conflict
+            .conflicting_fields
+            .iter()
+            .map(|f| f.attio_field.as_str())
+            .collect();
+        println!(
+            "{:<8} {:<32} {:<32} {} ({:?})",
+            "conflict",
+            format!("{}/{}", conflict.attio_object, conflict.attio_record_id),
+            format!(
+                "{}/{}",
+                conflict.salesforce_object, conflict.salesforce_record_id
+            ),
+            fields.join(", "),
+            conflict.status
+        );
+    }
+    println!(
+        "\n{} creates, {} updates, {} deletes, {} conflicts",
+        plan.creates.len(),
+        plan.updates.len(),
+        plan.deletes.len(),
+        plan.conflicts.len()
+    );
+    Ok(())
+}
+
+#[cfg(feature = "cli")]
+async fn run_status(state: &Path) -> Result<()> {
+    let config = Config::from_env()?;
+    let storage = open_state(state)?;
+    let max_age = chrono::Duration::seconds(config.sync.stale_reference_secs);
+    let stale = pending::list_stale(&storage, max_age, DEFAULT_PAGE_SIZE).await?;
+
+    println!("attio-sfdc {}", env!("CARGO_PKG_VERSION"));
+    println!("Stale pending references: {}", stale.len());
+    for reference in &stale {
+        println!(
+            "  {} {}/{}  {} → {}/{}  (waiting since {})",
+            reference.target.as_str(),
+            reference.target_object(),
+            reference.target_id(),
+            reference.field,
+            reference.referenced_object,
+            reference.referenced_id,
+            reference.created_at.format("%Y-%m-%d %H:%M")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(feature = "cli")]
+async fn run_history(status: Option<&str>, page: Page, state: &Path) -> Result<()> {
+    let status = status.map(str::parse::<RunStatus>).transpose()?;
+    let storage = open_state(state)?;
+    let runs = storage.list_runs(status, page).await?;
+
+    println!("Sync history:");
+    if runs.is_empty() {
+        println!("  No sync history found");
+    }
+    for run in &runs {
+        let duration = run
+            .duration_ms
+            .map(|ms| format!("{:.1}s", ms as f64 / 1000.0))
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "  {}  {}  {:?} {:?} [{}]  {:?}  {} processed, {} created, {} updated, {} deleted, {} unchanged, {} conflicts, {} errors  ({})",
+            run.id,
+            run.started_at.format("%Y-%m-%d %H:%M"),
+            run.trigger,
+            run.direction,
+            run.objects.join(", "),
+            run.status,
+            run.records_processed,
+            run.records_created,
+            run.records_updated,
+            run.records_deleted,
+            run.records_unchanged,
+            run.conflicts,
+            run.errors,
+            duration
+        );
+        if let Some(error) = &run.error {
+            println!("      error: {}", error);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "cli")]
+async fn run_audit(object: &str, id: &str, page: Page, state: &Path) -> Result<()> {
+    let storage = open_state(state)?;
+    let entries = storage.list_audit_entries(object, id, page).await?;
+
+    println!("Audit trail for {}/{}:", object, id);
+    if entries.is_empty() {
+        println!("  No writes recorded");
+    }
+    for entry in &entries {
+        println!(
+            "  {}  {:?} {}/{} → {}/{}  ({:?}{}{})",
+            entry.created_at.format("%Y-%m-%d %H:%M:%S"),
+            entry.action,
+            entry.source_object,
+            entry.source_id,
+            entry.target_object,
+            entry.target_id,
+            entry.trigger,
+            entry
+                .event
+                .as_ref()
+                .map(|e| format!(", {}", e))
+                .unwrap_or_default(),
+            entry
+                .run_id
+                .as_ref()
+                .map(|id| format!(", run {}", id))
+                .unwrap_or_default()
+        );
+        for change in &entry.changes {
+            let before = change
+                .before
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| "(unset)".to_string());
+            println!("      {}: {} → {}", change.field, before, change.after);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "cli")]
+async fn run_conflicts(args: ConflictListArgs, state: &Path) -> Result<()> {
+    let hours_ago = |hours: i64| chrono::Utc::now() - chrono::Duration::hours(hours);
+    let filter = ConflictFilter {
+        object: args.object,
+        record_id: None,
+        status: Some(args.status.parse::<ConflictStatus>()?),
+        detected_before: args.older_than_hours.map(hours_ago),
+        detected_after: args.newer_than_hours.map(hours_ago),
+    };
+    let storage = open_state(state)?;
+    let conflicts = storage
+        .list_conflicts(&filter, Page::new(args.page, args.per_page))
+        .await?;
+
+    println!("Conflicts ({}):", args.status);
+    if conflicts.is_empty() {
+        println!("  No conflicts found");
+    }
+    for conflict in &conflicts {
+        let fields: Vec<_> = conflict
+            .conflicting_fields
+            .iter()
+            .map(|f| f.attio_field.as_str())
+            .collect();
+        println!(
+            "  {}  {}/{} ↔ {}/{}  [{}]  detected {}",
+            conflict.id,
+            conflict.attio_object,
+            conflict.attio_record_id,
+            conflict.salesforce_object,
+            conflict.salesforce_record_id,
+            fields.join(", "),
+            conflict.detected_at.format("%Y-%m-%d %H:%M")
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "cli")]
+async fn run_resolve_conflict(
+    id: &str,
+    fields: &[String],
+    note: Option<String>,
+    by: Option<String>,
+    state: &Path,
+) -> Result<()> {
+    let fields = fields
+        .iter()
+        .map(|arg| {
+            let (field, choice) = arg
+                .split_once('=')
+                .ok_or_else(|| Error::validation(format!("Expected FIELD=CHOICE, got {}", arg)))?;
+            Ok((field.to_string(), choice.parse::<FieldChoice>()?))
+        })
+        .collect::<Result<HashMap<_, _>>>()?;
+    let resolved_by = by
+        .or_else(|| std::env::var("USER").ok())
+        .unwrap_or_else(|| "cli".to_string());
+
+    let config = Config::from_env()?;
+    let storage = Rc::new(open_state(state)?);
+    let mut engine = SyncEngine::new(config).with_storage(storage.clone());
+    let conflict = engine
+        .resolve_conflict(id, &ManualResolution { fields, note }, &resolved_by)
+        .await?;
+    save_state(state, &storage)?;
+
+    println!(
+        "Resolved conflict {} on {}/{} ↔ {}/{} ({})",
+        conflict.id,
+        conflict.attio_object,
+        conflict.attio_record_id,
+        conflict.salesforce_object,
+        conflict.salesforce_record_id,
+        resolved_by
+    );
+    Ok(())
+}
+
+#[cfg(feature = "cli")]
+async fn run_links(command: LinkCommands, state: &Path) -> Result<()> {
+    let reviewer = |by: Option<String>| {
+        by.or_else(|| std::env::var("USER").ok())
+            .unwrap_or_else(|| "cli".to_string())
+    };
+
+    let approve = matches!(command, LinkCommands::Approve { .. });
+    match command {
+        LinkCommands::Review {
+            object,
+            status,
+            page,
+            per_page,
+        } => {
+            let filter = CandidateFilter {
+                object,
+                record_id: None,
+                status: Some(status.parse::<CandidateStatus>()?),
+            };
+            let storage = open_state(state)?;
+            let candidates = storage
+                .list_link_candidates(&filter, Page::new(page, per_page))
+                .await?;
+
+            println!("Link candidates ({}):", status);
+            if candidates.is_empty() {
+                println!("  No candidates found");
+            }
+            for candidate in &candidates {
+                println!(
+                    "  {}  {}/{} ↔ {}/{}  {:.2} (rule {})",
+                    candidate.id,
+                    candidate.attio_object,
+                    candidate.attio_id,
+                    candidate.salesforce_object,
+                    candidate.salesforce_id,
+                    candidate.confidence,
+                    candidate.rule + 1
+                );
+            }
+        }
+        LinkCommands::Approve { id, by } | LinkCommands::Reject { id, by } => {
+            let reviewed_by = reviewer(by);
+            let storage = Rc::new(open_state(state)?);
+            let mut engine = SyncEngine::new(Config::from_env()?).with_storage(storage.clone());
+            let candidate = engine
+                .review_link_candidate(&id, approve, &reviewed_by)
+                .await?;
+            save_state(state, &storage)?;
+
+            println!(
+                "{} link {}/{} ↔ {}/{} ({})",
+                if approve { "Approved" } else { "Rejected" },
+                candidate.attio_object,
+                candidate.attio_id,
+                candidate.salesforce_object,
+                candidate.salesforce_id,
+                reviewed_by
+            );
+        }
+        LinkCommands::Import { file, by } => {
+            let csv = std::fs::read_to_string(&file).map_err(|e| {
+                Error::validation(format!("Failed to read {}: {}", file.display(), e))
+            })?;
+            let links = parse_link_csv(&csv)?;
+            let imported_by = reviewer(by);
+            let storage = Rc::new(open_state(state)?);
+            let mut engine = SyncEngine::new(Config::from_env()?).with_storage(storage.clone());
+
+            let (mut linked, mut skipped, mut failed) = (0, 0, 0);
+            for (line, link) in &links {
+                match engine.import_link(link, &imported_by).await {
+                    Ok(true) => linked += 1,
+                    Ok(false) => skipped += 1,
+                    // Keep going so one bad row does not block the rest of the file
+                    Err(e) => {
+                        failed += 1;
+                        eprintln!("  line {}: {}", line, e);
+                    }
+                }
+            }
+            save_state(state, &storage)?;
+
+            println!(
+                "Imported {}: {} linked, {} already linked, {} failed",
+                file.display(),
+                linked,
+                skipped,
+                failed
+            );
+            if failed > 0 {
+                std::process::exit(1);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve Attio object IDs for the objects in the required subscriptions
+#[cfg(feature = "cli")]
+async fn resolve_object_ids(
+    attio: &AttioClient,
+    required: &[RequiredSubscription],
+) -> Result<HashMap<String, String>> {
+    let mut object_ids = HashMap::new();
+    for req in required {
+        if !object_ids.contains_key(&req.object) {
+            let object = attio.get_object(&req.object).await?;
+            object_ids.insert(req.object.clone(), object.id.0);
+        }
+    }
+    Ok(object_ids)
+}
+
+/// Attio webhook endpoint on the given worker URL
+#[cfg(feature = "cli")]
+fn webhook_target_url(worker_url: &str) -> String {
+    format!("{}/webhooks/attio", worker_url.trim_end_matches('/'))
+}
+
+/// Load the local state file, starting empty if it does not exist
+#[cfg(feature = "cli")]
+fn open_state(path: &Path) -> Result<MemoryStorage> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => Ok(MemoryStorage::from_json(&json)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(MemoryStorage::new()),
+        Err(e) => Err(Error::Storage {
+            message: format!("Failed to read {}: {}", path.display(), e),
+        }),
+    }
+}
+
+/// Write the local state file
+#[cfg(feature = "cli")]
+fn save_state(path: &Path, storage: &MemoryStorage) -> Result<()> {
+    std::fs::write(path, storage.to_json()?).map_err(|e| Error::Storage {
+        message: format!("Failed to write {}: {}", path.display(), e),
+    })
+}
+
 #[cfg(not(feature = "cli"))]
 fn main() {
     eprintln!("CLI not enabled. Build with --features cli");
@@ -0,0 +1,118 @@
+//! Sync order of object mappings.
+//!
+//! A mapping with a reference field (e.g., people → `AccountId`) depends on
+//! the mapping of the object it points at: the referenced records have to be
+//! linked first, or the reference has nothing to resolve to. Mappings are
+//! synced in topological order of these dependencies.
+
+use crate::config::ObjectMapping;
+use crate::error::{Error, Result};
+
+/// Order mappings so that every mapping comes after the ones it references.
+///
+/// Ties keep the input order. References to objects without a mapping in
+/// `mappings`, and references of a mapping to itself, are ignored.
+pub fn sync_order(mappings: &[ObjectMapping]) -> Result<Vec<ObjectMapping>> {
+    let dependencies: Vec<Vec<usize>> = mappings
+        .iter()
+        .map(|m| dependencies_of(m, mappings))
+        .collect();
+
+    let mut ordered = Vec::with_capacity(mappings.len());
+    let mut placed = vec![false; mappings.len()];
+    while ordered.len() < mappings.len() {
+        let next =
+            (0..mappings.len()).find(|&i| !placed[i] && dependencies[i].iter().all(|&d| placed[d]));
+        match next {
+            Some(i) => {
+                placed[i] = true;
+                ordered.push(mappings[i].clone());
+            }
+            None => {
+                let cycle = find_cycle(&dependencies, &placed);
+                let names: Vec<&str> = cycle
+                    .iter()
+                    .map(|&i| mappings[i].attio_object.as_str())
+                    .collect();
+                return Err(Error::config(format!(
+                    "Mapping references form a cycle: {}",
+                    names.join(" → ")
+                )));
+            }
+        }
+    }
+    Ok(ordered)
+}
+
+/// Indexes of the mappings a mapping references
+fn dependencies_of(mapping: &ObjectMapping, mappings: &[ObjectMapping]) -> Vec<usize> {
+    mapping
+        .references()
+        .into_iter()
+        .filter_map(|(attio_object, salesforce_object)| {
+            mappings.iter().position(|m| {
+                m.attio_object == attio_object && m.salesforce_object == salesforce_object
+            })
+        })
+        .filter(|&i| mappings[i].key() != mapping.key())
+        .collect()
+}
+
+/// A cycle among the unplaced mappings, closed by repeating its first entry.
+///
+/// Every unplaced mapping has an unplaced dependency, so following them
+/// from any unplaced mapping must eventually revisit one.
+fn find_cycle(dependencies: &[Vec<usize>], placed: &[bool]) -> Vec<usize> {
+    let start = placed.iter().position(|p| !p).unwrap_or_default();
+    let mut path = vec![start];
+    loop {
+        let current = path[path.len() - 1];
+        let Some(&next) = dependencies[current].iter().find(|&&d| !placed[d]) else {
+            return path;
+        };
+        if let Some(pos) = path.iter().position(|&i| i == next) {
+            let mut cycle = path.split_off(pos);
+            cycle.push(next);
+            return cycle;
+        }
+        path.push(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TransformType;
+
+    fn defaults() -> Vec<ObjectMapping> {
+        ["companies", "deals", "people"]
+            .iter()
+            .zip(["Account", "Opportunity", "Contact"])
+            .map(|(attio, sf)| ObjectMapping::from_defaults(attio, sf).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_default_order() {
+        let ordered = sync_order(&defaults()).unwrap();
+        let objects: Vec<&str> = ordered.iter().map(|m| m.attio_object.as_str()).collect();
+        assert_eq!(objects, vec!["companies", "people", "deals"]);
+    }
+
+    #[test]
+    fn test_cycle_names_mappings() {
+        let mut mappings = defaults();
+        // companies → people → companies
+        let mut field = mappings[2].fields[0].clone();
+        field.salesforce_field = "Primary_Contact__c".to_string();
+        field.transform = TransformType::Reference {
+            attio_object: "people".to_string(),
+            salesforce_object: "Contact".to_string(),
+        };
+        mappings[0].fields.push(field);
+
+        let err = sync_order(&mappings).unwrap_err();
+        assert!(matches!(err, Error::Config { .. }));
+        assert!(err.to_string().contains("companies → people → companies"));
+    }
+}
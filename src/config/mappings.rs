@@ -1,5 +1,7 @@
 //! Default field mappings between Attio and Salesforce objects.
 
+use crate::config::ConflictResolution;
+use crate::salesforce::types::SalesforceFieldType;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -22,6 +24,106 @@ pub struct ObjectMapping {
     /// Status/stage mappings (for Deals → Opportunity)
     #[serde(default)]
     pub status_mappings: HashMap<String, String>,
+
+    /// Conflict resolution for this object (overrides the global setting)
+    #[serde(default)]
+    pub conflict_resolution: Option<ConflictResolution>,
+
+    /// Custom merge strategy for this object (overrides the global setting)
+    #[serde(default)]
+    pub merge_strategy: Option<String>,
+
+    /// What to do with the linked record when a record is deleted on one side
+    #[serde(default)]
+    pub on_delete: DeletePolicy,
+
+    /// Merge the linked Salesforce records when their Attio records are merged
+    /// (Attio has no merge API, so Salesforce merges are never mirrored)
+    #[serde(default)]
+    pub mirror_merges: bool,
+
+    /// Rules pairing unlinked records before the first sync (the object's defaults if empty)
+    #[serde(default)]
+    pub match_rules: Vec<MatchRule>,
+
+    /// Confidence at or above which a matched pair is linked without review
+    #[serde(default = "default_auto_link_threshold")]
+    pub auto_link_threshold: f64,
+}
+
+/// Rule pairing an unlinked Attio record with an unlinked Salesforce record
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MatchRule {
+    /// Fields compared; a pair matches when all of them are equal after normalization
+    pub fields: Vec<MatchField>,
+
+    /// Confidence of a pair matched by this rule (0.0 to 1.0)
+    pub confidence: f64,
+
+    /// Minimum Jaro-Winkler similarity of the normalized values, instead of equality
+    #[serde(default)]
+    pub similarity: Option<f64>,
+}
+
+/// Pair of fields compared by a match rule
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MatchField {
+    /// Attio attribute slug or path
+    pub attio_field: String,
+
+    /// Salesforce field API name
+    pub salesforce_field: String,
+
+    /// How both values are normalized before comparing
+    #[serde(default)]
+    pub normalize: MatchNormalization,
+}
+
+/// Normalization applied to values compared by a match rule
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchNormalization {
+    /// Trimmed and case-insensitive
+    #[default]
+    Text,
+
+    /// Registrable domain of a website, host or email address
+    Domain,
+
+    /// Email address
+    Email,
+
+    /// Name, ignoring case, diacritics, punctuation and spacing
+    Name,
+
+    /// Company name, also ignoring legal suffixes such as Inc or GmbH
+    Company,
+}
+
+/// What to do with the linked record when a record is deleted on one side
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeletePolicy {
+    /// Delete the linked record too
+    Propagate,
+
+    /// Set a field on the linked record (e.g., `Attio_Deleted__c`) and unlink it
+    Flag {
+        /// Salesforce checkbox set when the Attio record is deleted
+        #[serde(default)]
+        salesforce_field: Option<String>,
+
+        /// Attio checkbox set when the Salesforce record is deleted
+        #[serde(default)]
+        attio_field: Option<String>,
+    },
+
+    /// Only remove the ID mapping
+    Unlink,
+
+    /// Do nothing
+    #[default]
+    Ignore,
 }
 
 /// Mapping between an Attio field and a Salesforce field
@@ -44,6 +146,14 @@ pub struct FieldMapping {
     /// Sync direction for this specific field
     #[serde(default)]
     pub direction: FieldSyncDirection,
+
+    /// Salesforce field type used to compare values (inferred from the field name if unset)
+    #[serde(default)]
+    pub field_type: Option<SalesforceFieldType>,
+
+    /// Conflict resolution for this field (overrides the object and global settings)
+    #[serde(default)]
+    pub conflict_resolution: Option<ConflictResolution>,
 }
 
 /// Type of transformation to apply during mapping
@@ -72,6 +182,14 @@ pub enum TransformType {
     /// Parse employee range to number (e.g., "11-50" → 30)
     EmployeeRangeToNumber,
 
+    /// Lookup to a record of another mapped object, translated through the ID mappings
+    Reference {
+        /// Attio object of the referenced record
+        attio_object: String,
+        /// Salesforce object of the referenced record
+        salesforce_object: String,
+    },
+
     /// Custom JavaScript/WASM function (future)
     Custom { function_name: String },
 }
@@ -98,6 +216,10 @@ fn default_true() -> bool {
     true
 }
 
+fn default_auto_link_threshold() -> f64 {
+    0.9
+}
+
 /// Default mappings for standard objects
 pub static DEFAULT_MAPPINGS: &[(&str, &str, &[(&str, &str, &str)])] = &[
     // Companies → Account
@@ -158,7 +280,7 @@ pub static DEFAULT_MAPPINGS: &[(&str, &str, &[(&str, &str, &str)])] = &[
                 "MailingCountry",
                 "country_code_to_name",
             ),
-            // Note: AccountId requires reference resolution via ID mapping
+            ("company", "AccountId", "reference"),
         ],
     ),
     // Deals → Opportunity
@@ -171,12 +293,120 @@ pub static DEFAULT_MAPPINGS: &[(&str, &str, &[(&str, &str, &str)])] = &[
             ("expected_close_date", "CloseDate", "direct"),
             ("status", "StageName", "map_value"),
             ("probability", "Probability", "direct"),
-            // Note: AccountId and ContactId require reference resolution
+            ("associated_company", "AccountId", "reference"),
+            ("associated_people", "ContactId", "reference"),
         ],
     ),
 ];
 
 impl ObjectMapping {
+    /// Key identifying this mapping (e.g., "companies_Account")
+    pub fn key(&self) -> String {
+        format!("{}_{}", self.attio_object, self.salesforce_object)
+    }
+
+    /// Objects this mapping's reference fields point at, as (Attio, Salesforce) pairs
+    pub fn references(&self) -> Vec<(&str, &str)> {
+        self.fields
+            .iter()
+            .filter_map(|f| match &f.transform {
+                TransformType::Reference {
+                    attio_object,
+                    salesforce_object,
+                } => Some((attio_object.as_str(), salesforce_object.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Match rules of this mapping, falling back to the defaults for its objects
+    pub fn effective_match_rules(&self) -> Vec<MatchRule> {
+        if !self.match_rules.is_empty() {
+            return self.match_rules.clone();
+        }
+        let rule = |fields: &[(&str, &str, MatchNormalization)], confidence| MatchRule {
+            fields: fields
+                .iter()
+                .map(|(attio, sf, normalize)| MatchField {
+                    attio_field: attio.to_string(),
+                    salesforce_field: sf.to_string(),
+                    normalize: *normalize,
+                })
+                .collect(),
+            confidence,
+            similarity: None,
+        };
+        match (self.attio_object.as_str(), self.salesforce_object.as_str()) {
+            ("companies", "Account") => vec![
+                rule(
+                    &[("domains[0].domain", "Website", MatchNormalization::Domain)],
+                    1.0,
+                ),
+                rule(
+                    &[
+                        ("name", "Name", MatchNormalization::Company),
+                        (
+                            "primary_location.locality",
+                            "BillingCity",
+                            MatchNormalization::Name,
+                        ),
+                    ],
+                    0.8,
+                ),
+            ],
+            ("people", "Contact") => vec![
+                rule(
+                    &[(
+                        "email_addresses[0].email_address",
+                        "Email",
+                        MatchNormalization::Email,
+                    )],
+                    1.0,
+                ),
+                rule(
+                    &[
+                        ("name.full_name", "Name", MatchNormalization::Name),
+                        (
+                            "primary_location.locality",
+                            "MailingCity",
+                            MatchNormalization::Name,
+                        ),
+                    ],
+                    0.8,
+                ),
+            ],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Field mappings that sync from Attio to Salesforce
+    pub fn attio_to_salesforce_fields(&self) -> Vec<FieldMapping> {
+        self.fields
+            .iter()
+            .filter(|f| {
+                matches!(
+                    f.direction,
+                    FieldSyncDirection::Bidirectional | FieldSyncDirection::AttioToSalesforce
+                )
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Field mappings that sync from Salesforce to Attio
+    pub fn salesforce_to_attio_fields(&self) -> Vec<FieldMapping> {
+        self.fields
+            .iter()
+            .filter(|f| {
+                matches!(
+                    f.direction,
+                    FieldSyncDirection::Bidirectional | FieldSyncDirection::SalesforceToAttio
+                )
+            })
+            .cloned()
+            .collect()
+    }
+
     /// Create a new object mapping with default fields
     pub fn from_defaults(attio_object: &str, salesforce_object: &str) -> Option<Self> {
         DEFAULT_MAPPINGS
@@ -191,9 +421,11 @@ impl ObjectMapping {
                     .map(|(a, s, t)| FieldMapping {
                         attio_field: a.to_string(),
                         salesforce_field: s.to_string(),
-                        transform: TransformType::from_str(t),
+                        transform: TransformType::from_str(t, s),
                         required: false,
                         direction: FieldSyncDirection::default(),
+                        field_type: None,
+                        conflict_resolution: None,
                     })
                     .collect(),
                 status_mappings: if attio_object == "deals" {
@@ -201,12 +433,46 @@ impl ObjectMapping {
                 } else {
                     HashMap::new()
                 },
+                conflict_resolution: None,
+                merge_strategy: None,
+                on_delete: DeletePolicy::default(),
+                mirror_merges: false,
+                match_rules: Vec::new(),
+                auto_link_threshold: default_auto_link_threshold(),
             })
     }
 }
 
+impl FieldMapping {
+    /// Check whether this field holds a reference to another record
+    pub fn is_reference(&self) -> bool {
+        matches!(self.transform, TransformType::Reference { .. })
+    }
+
+    /// Type used to compare this field's values
+    pub fn value_type(&self) -> SalesforceFieldType {
+        if let Some(field_type) = &self.field_type {
+            return field_type.clone();
+        }
+
+        match self.salesforce_field.as_str() {
+            "Email" | "PersonEmail" => SalesforceFieldType::Email,
+            "Phone" | "MobilePhone" | "HomePhone" | "OtherPhone" | "Fax" => {
+                SalesforceFieldType::Phone
+            }
+            "Website" => SalesforceFieldType::Url,
+            "Amount" | "AnnualRevenue" | "ExpectedRevenue" => SalesforceFieldType::Currency,
+            "NumberOfEmployees" => SalesforceFieldType::Int,
+            "Probability" => SalesforceFieldType::Double,
+            "CloseDate" | "Birthdate" => SalesforceFieldType::Date,
+            "CreatedDate" | "LastModifiedDate" | "SystemModstamp" => SalesforceFieldType::DateTime,
+            _ => SalesforceFieldType::String,
+        }
+    }
+}
+
 impl TransformType {
-    fn from_str(s: &str) -> Self {
+    fn from_str(s: &str, salesforce_field: &str) -> Self {
         match s {
             "direct" => Self::Direct,
             "extract_first" => Self::ExtractFirst,
@@ -219,9 +485,22 @@ impl TransformType {
             "map_value" => Self::MapValue {
                 mappings: HashMap::new(),
             },
+            "reference" => Self::lookup(salesforce_field),
             _ => Self::Direct,
         }
     }
+
+    /// Reference transform for a standard Salesforce lookup field
+    fn lookup(salesforce_field: &str) -> Self {
+        let (attio_object, salesforce_object) = match salesforce_field {
+            "ContactId" => ("people", "Contact"),
+            _ => ("companies", "Account"),
+        };
+        Self::Reference {
+            attio_object: attio_object.to_string(),
+            salesforce_object: salesforce_object.to_string(),
+        }
+    }
 }
 
 /// Default status mappings for Deals → Opportunity stages
@@ -1,9 +1,13 @@
 //! Configuration management for attio-sfdc.
 
+pub mod dependencies;
 pub mod mappings;
 mod validation;
 
-pub use mappings::{FieldMapping, ObjectMapping, TransformType, DEFAULT_MAPPINGS};
+pub use mappings::{
+    DeletePolicy, FieldMapping, MatchField, MatchNormalization, MatchRule, ObjectMapping,
+    TransformType, DEFAULT_MAPPINGS,
+};
 pub use validation::validate_config;
 
 use crate::error::{Error, Result};
@@ -38,6 +42,18 @@ pub enum ConflictResolution {
     Manual,
 }
 
+impl ConflictResolution {
+    /// Config name of the strategy
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::LastWrite => "last_write",
+            Self::AttioWins => "attio_wins",
+            Self::SalesforceWins => "salesforce_wins",
+            Self::Manual => "manual",
+        }
+    }
+}
+
 /// Main configuration struct
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -70,6 +86,23 @@ pub struct SyncConfig {
     #[serde(default)]
     pub conflict_resolution: ConflictResolution,
 
+    /// Name of a registered custom merge strategy used instead of the resolver
+    #[serde(default)]
+    pub merge_strategy: Option<String>,
+
+    /// Seconds before each object cursor to re-query, catching late or clock-skewed changes
+    #[serde(default = "default_cursor_overlap_secs")]
+    pub cursor_overlap_secs: i64,
+
+    /// Incremental sync attempts after which a record that keeps failing is
+    /// dead-lettered, so the cursor can move past it
+    #[serde(default = "default_max_record_attempts")]
+    pub max_record_attempts: u32,
+
+    /// Seconds after which a reference still waiting for its target to be linked is reported as stale
+    #[serde(default = "default_stale_reference_secs")]
+    pub stale_reference_secs: i64,
+
     /// Enable real-time webhook sync
     #[serde(default = "default_true")]
     pub webhook_enabled: bool,
@@ -88,6 +121,10 @@ pub struct AttioConfig {
     /// Webhook secret for signature verification
     pub webhook_secret: Option<String>,
 
+    /// ID of the bridge's API token, used to ignore our own changes
+    #[serde(default)]
+    pub api_token_id: Option<String>,
+
     /// Base URL (defaults to production)
     #[serde(default = "default_attio_base_url")]
     pub base_url: String,
@@ -111,12 +148,32 @@ pub struct SalesforceConfig {
     /// API version
     #[serde(default = "default_sf_api_version")]
     pub api_version: String,
+
+    /// Shared secret for verifying Salesforce webhook signatures
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
+
+    /// ID of the integration user the bridge writes as, used to ignore our own changes
+    #[serde(default)]
+    pub integration_user_id: Option<String>,
 }
 
 fn default_batch_size() -> usize {
     100
 }
 
+fn default_cursor_overlap_secs() -> i64 {
+    300
+}
+
+fn default_max_record_attempts() -> u32 {
+    5
+}
+
+fn default_stale_reference_secs() -> i64 {
+    86400
+}
+
 fn default_true() -> bool {
     true
 }
@@ -135,6 +192,10 @@ impl Default for SyncConfig {
             direction: SyncDirection::default(),
             batch_size: default_batch_size(),
             conflict_resolution: ConflictResolution::default(),
+            merge_strategy: None,
+            cursor_overlap_secs: default_cursor_overlap_secs(),
+            max_record_attempts: default_max_record_attempts(),
+            stale_reference_secs: default_stale_reference_secs(),
             webhook_enabled: true,
             scheduled_enabled: true,
         }
@@ -144,27 +205,55 @@ impl Default for SyncConfig {
 impl Config {
     /// Load configuration from environment variables
     pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Load configuration using a custom variable lookup (e.g., Worker vars and secrets)
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let lookup = &lookup;
         let config = Self {
             sync: SyncConfig {
-                direction: Self::parse_env_or_default("SYNC_DIRECTION")?,
-                batch_size: Self::parse_env_or_default("BATCH_SIZE")?,
-                conflict_resolution: Self::parse_env_or_default("CONFLICT_RESOLUTION")?,
-                webhook_enabled: Self::parse_env_or_default("WEBHOOK_ENABLED")?,
-                scheduled_enabled: Self::parse_env_or_default("SCHEDULED_ENABLED")?,
+                direction: Self::parse_env_or(lookup, "SYNC_DIRECTION", SyncDirection::default())?,
+                batch_size: Self::parse_env_or(lookup, "BATCH_SIZE", default_batch_size())?,
+                conflict_resolution: Self::parse_env_or(
+                    lookup,
+                    "CONFLICT_RESOLUTION",
+                    ConflictResolution::default(),
+                )?,
+                merge_strategy: lookup("MERGE_STRATEGY"),
+                cursor_overlap_secs: Self::parse_env_or(
+                    lookup,
+                    "CURSOR_OVERLAP_SECS",
+                    default_cursor_overlap_secs(),
+                )?,
+                max_record_attempts: Self::parse_env_or(
+                    lookup,
+                    "MAX_RECORD_ATTEMPTS",
+                    default_max_record_attempts(),
+                )?,
+                stale_reference_secs: Self::parse_env_or(
+                    lookup,
+                    "STALE_REFERENCE_SECS",
+                    default_stale_reference_secs(),
+                )?,
+                webhook_enabled: Self::parse_env_or(lookup, "WEBHOOK_ENABLED", true)?,
+                scheduled_enabled: Self::parse_env_or(lookup, "SCHEDULED_ENABLED", true)?,
             },
             attio: AttioConfig {
-                api_key: Self::require_env("ATTIO_API_KEY")?,
-                webhook_secret: Self::optional_env("ATTIO_WEBHOOK_SECRET"),
-                base_url: Self::optional_env("ATTIO_BASE_URL")
-                    .unwrap_or_else(default_attio_base_url),
+                api_key: Self::require_env(lookup, "ATTIO_API_KEY")?,
+                webhook_secret: lookup("ATTIO_WEBHOOK_SECRET"),
+                api_token_id: lookup("ATTIO_API_TOKEN_ID"),
+                base_url: lookup("ATTIO_BASE_URL").unwrap_or_else(default_attio_base_url),
             },
             salesforce: SalesforceConfig {
-                client_id: Self::require_env("SALESFORCE_CLIENT_ID")?,
-                client_secret: Self::require_env("SALESFORCE_CLIENT_SECRET")?,
-                instance_url: Self::require_env("SALESFORCE_INSTANCE_URL")?,
-                refresh_token: Self::optional_env("SALESFORCE_REFRESH_TOKEN"),
-                api_version: Self::optional_env("SALESFORCE_API_VERSION")
+                client_id: Self::require_env(lookup, "SALESFORCE_CLIENT_ID")?,
+                client_secret: Self::require_env(lookup, "SALESFORCE_CLIENT_SECRET")?,
+                instance_url: Self::require_env(lookup, "SALESFORCE_INSTANCE_URL")?,
+                refresh_token: lookup("SALESFORCE_REFRESH_TOKEN"),
+                api_version: lookup("SALESFORCE_API_VERSION")
                     .unwrap_or_else(default_sf_api_version),
+                webhook_secret: lookup("SALESFORCE_WEBHOOK_SECRET"),
+                integration_user_id: lookup("SALESFORCE_INTEGRATION_USER_ID"),
             },
             mappings: HashMap::new(),
         };
@@ -179,20 +268,59 @@ impl Config {
         self.mappings.get(&key)
     }
 
-    fn require_env(name: &str) -> Result<String> {
-        std::env::var(name).map_err(|_| Error::config(format!("Missing required env var: {}", name)))
+    /// Get all enabled object mappings, falling back to the defaults when
+    /// no mappings are configured
+    pub fn enabled_mappings(&self) -> Vec<ObjectMapping> {
+        let mut mappings: Vec<ObjectMapping> = if self.mappings.is_empty() {
+            DEFAULT_MAPPINGS
+                .iter()
+                .filter_map(|(attio, sf, _)| ObjectMapping::from_defaults(attio, sf))
+                .collect()
+        } else {
+            self.mappings
+                .values()
+                .filter(|m| m.enabled)
+                .cloned()
+                .collect()
+        };
+
+        mappings.sort_by(|a, b| a.attio_object.cmp(&b.attio_object));
+        mappings
     }
 
-    fn optional_env(name: &str) -> Option<String> {
-        std::env::var(name).ok()
+    /// Get all enabled object mappings in sync order, referenced objects first
+    pub fn ordered_mappings(&self) -> Result<Vec<ObjectMapping>> {
+        dependencies::sync_order(&self.enabled_mappings())
     }
 
-    fn parse_env_or_default<T: Default + std::str::FromStr>(name: &str) -> Result<T> {
-        match std::env::var(name) {
-            Ok(val) => val
+    /// Get the enabled mapping for an Attio object
+    pub fn mapping_for_attio_object(&self, attio_object: &str) -> Option<ObjectMapping> {
+        self.enabled_mappings()
+            .into_iter()
+            .find(|m| m.attio_object == attio_object)
+    }
+
+    /// Get the enabled mapping for a Salesforce object
+    pub fn mapping_for_salesforce_object(&self, sf_object: &str) -> Option<ObjectMapping> {
+        self.enabled_mappings()
+            .into_iter()
+            .find(|m| m.salesforce_object == sf_object)
+    }
+
+    fn require_env(lookup: &dyn Fn(&str) -> Option<String>, name: &str) -> Result<String> {
+        lookup(name).ok_or_else(|| Error::config(format!("Missing required env var: {}", name)))
+    }
+
+    fn parse_env_or<T: std::str::FromStr>(
+        lookup: &dyn Fn(&str) -> Option<String>,
+        name: &str,
+        default: T,
+    ) -> Result<T> {
+        match lookup(name) {
+            Some(val) => val
                 .parse()
                 .map_err(|_| Error::config(format!("Invalid value for {}", name))),
-            Err(_) => Ok(T::default()),
+            None => Ok(default),
         }
     }
 }
@@ -243,6 +371,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_lookup_defaults() {
+        let vars: HashMap<&str, &str> = [
+            ("ATTIO_API_KEY", "attio_key"),
+            ("SALESFORCE_CLIENT_ID", "client_id"),
+            ("SALESFORCE_CLIENT_SECRET", "client_secret"),
+            ("SALESFORCE_INSTANCE_URL", "https://test.salesforce.com"),
+        ]
+        .into();
+
+        let config = Config::from_lookup(|name| vars.get(name).map(|v| v.to_string())).unwrap();
+        assert_eq!(config.sync.batch_size, 100);
+        assert!(config.sync.webhook_enabled);
+        assert!(config.sync.scheduled_enabled);
+        assert!(config.salesforce.webhook_secret.is_none());
+    }
+
     #[test]
     fn test_conflict_resolution_from_str() {
         assert_eq!(
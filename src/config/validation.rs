@@ -39,13 +39,43 @@ pub fn validate_config(config: &Config) -> Result<()> {
         return Err(Error::config("Batch size cannot exceed 10000"));
     }
 
+    if config.sync.cursor_overlap_secs < 0 {
+        return Err(Error::config("Cursor overlap cannot be negative"));
+    }
+
+    if config.sync.max_record_attempts == 0 {
+        return Err(Error::config("Max record attempts must be greater than 0"));
+    }
+
+    if config.sync.stale_reference_secs < 0 {
+        return Err(Error::config("Stale reference age cannot be negative"));
+    }
+
+    // Match confidences and thresholds are fractions
+    for mapping in config.enabled_mappings() {
+        let confidences = mapping
+            .match_rules
+            .iter()
+            .flat_map(|rule| std::iter::once(rule.confidence).chain(rule.similarity));
+        let mut fractions = std::iter::once(mapping.auto_link_threshold).chain(confidences);
+        if fractions.any(|f| !(0.0..=1.0).contains(&f)) {
+            return Err(Error::config(format!(
+                "Match confidence, similarity and auto-link threshold of {} must be between 0 and 1",
+                mapping.attio_object
+            )));
+        }
+    }
+
+    // Reference fields must not depend on each other in a cycle
+    config.ordered_mappings()?;
+
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{AttioConfig, SalesforceConfig, SyncConfig};
+    use crate::config::{AttioConfig, ObjectMapping, SalesforceConfig, SyncConfig};
     use std::collections::HashMap;
 
     fn valid_config() -> Config {
@@ -54,6 +84,7 @@ mod tests {
             attio: AttioConfig {
                 api_key: "test_key".to_string(),
                 webhook_secret: None,
+                api_token_id: None,
                 base_url: "https://api.attio.com".to_string(),
             },
             salesforce: SalesforceConfig {
@@ -62,6 +93,8 @@ mod tests {
                 instance_url: "https://test.salesforce.com".to_string(),
                 refresh_token: None,
                 api_version: "v59.0".to_string(),
+                webhook_secret: None,
+                integration_user_id: None,
             },
             mappings: HashMap::new(),
         }
@@ -86,4 +119,13 @@ mod tests {
         config.salesforce.instance_url = "http://test.salesforce.com".to_string();
         assert!(validate_config(&config).is_err());
     }
+
+    #[test]
+    fn test_auto_link_threshold_range() {
+        let mut config = valid_config();
+        let mut mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+        mapping.auto_link_threshold = 1.5;
+        config.mappings.insert(mapping.key(), mapping);
+        assert!(validate_config(&config).is_err());
+    }
 }
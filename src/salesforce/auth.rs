@@ -151,6 +151,8 @@ mod tests {
             instance_url: "https://test.salesforce.com".to_string(),
             refresh_token: None,
             api_version: "v59.0".to_string(),
+            webhook_secret: None,
+            integration_user_id: None,
         }
     }
 
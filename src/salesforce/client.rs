@@ -3,7 +3,9 @@
 use crate::config::SalesforceConfig;
 use crate::error::{Error, Result};
 use crate::salesforce::auth::SalesforceAuth;
-use crate::salesforce::types::{QueryResult, SObject, SalesforceId, SalesforceRecord};
+use crate::salesforce::types::{
+    DeletedResult, QueryResult, SObject, SalesforceId, SalesforceRecord,
+};
 use serde_json::Value;
 
 /// Client for interacting with the Salesforce REST API
@@ -59,6 +61,17 @@ impl SalesforceClient {
         })
     }
 
+    /// Query records including deleted and merged ones (`queryAll`)
+    pub async fn query_all(&mut self, soql: &str) -> Result<QueryResult> {
+        let _url = format!("{}?q={}", self.api_url("queryAll"), soql);
+        let _token = self.auth.get_access_token().await?;
+
+        // TODO: Implement HTTP request with query parameter
+        Err(Error::Internal {
+            message: "Not implemented".to_string(),
+        })
+    }
+
     /// Query more records (pagination)
     pub async fn query_more(&mut self, next_records_url: &str) -> Result<QueryResult> {
         let _url = format!("{}{}", self.auth.instance_url(), next_records_url);
@@ -132,6 +145,67 @@ impl SalesforceClient {
         })
     }
 
+    /// Merge duplicate records into a master record (up to two duplicates per call)
+    pub async fn merge_records(
+        &mut self,
+        sobject_type: &str,
+        master_id: &str,
+        duplicate_ids: &[&str],
+    ) -> Result<()> {
+        let _url = format!(
+            "{}/services/Soap/u/{}",
+            self.auth.instance_url(),
+            self.api_version.trim_start_matches('v')
+        );
+        let _token = self.auth.get_access_token().await?;
+        let _request = (sobject_type, master_id, duplicate_ids);
+
+        // TODO: Implement SOAP merge() call
+        Err(Error::Internal {
+            message: "Not implemented".to_string(),
+        })
+    }
+
+    /// Record a deleted record was merged into (`MasterRecordId`), if any
+    pub async fn get_merge_master(
+        &mut self,
+        sobject_type: &str,
+        id: &str,
+    ) -> Result<Option<String>> {
+        let soql = format!(
+            "SELECT Id, MasterRecordId FROM {} WHERE Id = '{}' AND IsDeleted = true",
+            sobject_type,
+            id.replace('\'', "\\'")
+        );
+
+        let result = self.query_all(&soql).await?;
+        Ok(result
+            .records
+            .first()
+            .and_then(|record| record.get_string("MasterRecordId")))
+    }
+
+    /// List records deleted between two timestamps (`getDeleted`, last 30 days only)
+    pub async fn get_deleted(
+        &mut self,
+        sobject_type: &str,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+    ) -> Result<DeletedResult> {
+        let _url = self.api_url(&format!(
+            "sobjects/{}/deleted/?start={}&end={}",
+            sobject_type,
+            start.format("%Y-%m-%dT%H:%M:%SZ"),
+            end.format("%Y-%m-%dT%H:%M:%SZ")
+        ));
+        let _token = self.auth.get_access_token().await?;
+
+        // TODO: Implement HTTP request
+        Err(Error::Internal {
+            message: "Not implemented".to_string(),
+        })
+    }
+
     /// Get object metadata (describe)
     pub async fn describe_object(&mut self, sobject_type: &str) -> Result<SObject> {
         let _url = self.api_url(&format!("sobjects/{}/describe", sobject_type));
@@ -154,14 +228,14 @@ impl SalesforceClient {
         })
     }
 
-    /// Query records that changed since a given timestamp
+    /// Query records that changed at or after a given timestamp, oldest first
     pub async fn get_changes_since(
         &mut self,
         sobject_type: &str,
         since: chrono::DateTime<chrono::Utc>,
     ) -> Result<Vec<SalesforceRecord>> {
         let soql = format!(
-            "SELECT Id, LastModifiedDate FROM {} WHERE LastModifiedDate > {} ORDER BY LastModifiedDate ASC",
+            "SELECT Id, SystemModstamp FROM {} WHERE SystemModstamp >= {} ORDER BY SystemModstamp ASC, Id ASC",
             sobject_type,
             since.format("%Y-%m-%dT%H:%M:%S%.3fZ")
         );
@@ -169,6 +243,25 @@ impl SalesforceClient {
         let result = self.query(&soql).await?;
         Ok(result.records)
     }
+
+    /// Query a page of records ordered by ID, starting after a record ID (keyset pagination)
+    pub async fn get_records_after(
+        &mut self,
+        sobject_type: &str,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SalesforceRecord>> {
+        let filter = after
+            .map(|id| format!(" WHERE Id > '{}'", id.replace('\'', "\\'")))
+            .unwrap_or_default();
+        let soql = format!(
+            "SELECT Id FROM {}{} ORDER BY Id ASC LIMIT {}",
+            sobject_type, filter, limit
+        );
+
+        let result = self.query(&soql).await?;
+        Ok(result.records)
+    }
 }
 
 #[cfg(test)]
@@ -182,6 +275,8 @@ mod tests {
             instance_url: "https://test.salesforce.com".to_string(),
             refresh_token: None,
             api_version: "v59.0".to_string(),
+            webhook_secret: None,
+            integration_user_id: None,
         }
     }
 
@@ -5,6 +5,7 @@ pub mod bulk;
 pub mod client;
 pub mod objects;
 pub mod types;
+pub mod webhooks;
 
 pub use auth::SalesforceAuth;
 pub use client::SalesforceClient;
@@ -168,6 +168,33 @@ pub struct QueryResult {
     pub records: Vec<SalesforceRecord>,
 }
 
+/// Result of the `getDeleted` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletedResult {
+    /// Records deleted in the requested window
+    #[serde(rename = "deletedRecords")]
+    pub deleted_records: Vec<DeletedRecord>,
+
+    /// Earliest deletion still available (older deletes have been purged)
+    #[serde(rename = "earliestDateAvailable")]
+    pub earliest_date_available: Option<DateTime<Utc>>,
+
+    /// Last time covered by the response
+    #[serde(rename = "latestDateCovered")]
+    pub latest_date_covered: Option<DateTime<Utc>>,
+}
+
+/// A deleted record reported by `getDeleted`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletedRecord {
+    /// Record ID
+    pub id: SalesforceId,
+
+    /// When the record was deleted
+    #[serde(rename = "deletedDate")]
+    pub deleted_date: DateTime<Utc>,
+}
+
 /// Composite request for batch operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompositeRequest {
@@ -229,6 +256,26 @@ impl SalesforceRecord {
     pub fn object_type(&self) -> Option<&str> {
         self.attributes.as_ref().map(|a| a.sobject_type.as_str())
     }
+
+    /// Get the `LastModifiedDate` timestamp
+    pub fn last_modified(&self) -> Option<DateTime<Utc>> {
+        self.get_datetime("LastModifiedDate")
+    }
+
+    /// Get the `SystemModstamp` timestamp, falling back to `LastModifiedDate`
+    pub fn system_modstamp(&self) -> Option<DateTime<Utc>> {
+        self.get_datetime("SystemModstamp")
+            .or_else(|| self.last_modified())
+    }
+
+    /// Get a datetime field
+    pub fn get_datetime(&self, field: &str) -> Option<DateTime<Utc>> {
+        let raw = self.get_string(field)?;
+        DateTime::parse_from_str(&raw, "%Y-%m-%dT%H:%M:%S%.f%z")
+            .or_else(|_| DateTime::parse_from_rfc3339(&raw))
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
 }
 
 impl std::fmt::Display for SalesforceId {
@@ -266,6 +313,15 @@ mod tests {
         assert_eq!(record.get_string("Name"), Some("Test Account".to_string()));
     }
 
+    #[test]
+    fn test_last_modified() {
+        let mut record = SalesforceRecord::new("Account");
+        record.set("LastModifiedDate", "2024-01-15T10:30:00.000+0000");
+
+        let expected = "2024-01-15T10:30:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(record.last_modified(), Some(expected));
+    }
+
     #[test]
     fn test_salesforce_id_to_15_char() {
         let id = SalesforceId::new("001000000000001AAA");
@@ -0,0 +1,88 @@
+//! Salesforce change notification handling.
+//!
+//! Salesforce does not deliver signed webhooks natively, so change events are
+//! expected from a relay (e.g., a Change Data Capture subscriber or Apex trigger)
+//! that posts the `ChangeEventHeader` as JSON, signed with a shared secret.
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// Header carrying the HMAC-SHA256 signature of the payload
+pub const SIGNATURE_HEADER: &str = "X-Signature";
+
+/// Salesforce change event (mirrors the CDC `ChangeEventHeader`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SalesforceWebhookEvent {
+    /// Object API name (e.g., "Account")
+    pub entity_name: String,
+
+    /// IDs of the records that changed
+    pub record_ids: Vec<String>,
+
+    /// Type of change
+    pub change_type: SalesforceChangeType,
+
+    /// ID of the user who made the change
+    #[serde(default)]
+    pub commit_user: Option<String>,
+
+    /// Commit timestamp (milliseconds since epoch)
+    #[serde(default)]
+    pub commit_timestamp: Option<i64>,
+}
+
+/// Salesforce change types
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SalesforceChangeType {
+    /// Record created
+    Create,
+    /// Record updated
+    Update,
+    /// Record deleted
+    Delete,
+    /// Record restored from the recycle bin
+    Undelete,
+    /// Unknown change type (including gap events)
+    #[serde(other)]
+    Unknown,
+}
+
+/// Verify webhook signature (same `sha256=<hex>` HMAC scheme as Attio)
+pub fn verify_signature(payload: &[u8], signature: &str, secret: &str) -> Result<()> {
+    crate::attio::webhooks::verify_signature(payload, signature, secret)
+}
+
+/// Parse webhook payload
+pub fn parse_webhook(payload: &[u8]) -> Result<SalesforceWebhookEvent> {
+    serde_json::from_slice(payload).map_err(Error::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_webhook() {
+        let payload = r#"{
+            "entityName": "Account",
+            "recordIds": ["001000000000001AAA"],
+            "changeType": "UPDATE",
+            "commitUser": "005000000000001AAA",
+            "commitTimestamp": 1704067200000
+        }"#;
+
+        let event = parse_webhook(payload.as_bytes()).unwrap();
+        assert_eq!(event.entity_name, "Account");
+        assert_eq!(event.change_type, SalesforceChangeType::Update);
+        assert_eq!(event.commit_user.as_deref(), Some("005000000000001AAA"));
+    }
+
+    #[test]
+    fn test_unknown_change_type() {
+        let payload = r#"{"entityName": "Account", "recordIds": [], "changeType": "GAP_UPDATE"}"#;
+        let event = parse_webhook(payload.as_bytes()).unwrap();
+        assert_eq!(event.change_type, SalesforceChangeType::Unknown);
+    }
+}
@@ -1,8 +1,17 @@
 //! Cloudflare D1 (SQLite) storage implementation.
 
 use crate::error::{Error, Result};
-use crate::storage::Storage;
+use crate::storage::{Page, Storage};
+use crate::sync::audit::AuditEntry;
+use crate::sync::checkpoint::FullSyncCheckpoint;
 use crate::sync::cursor::SyncCursor;
+use crate::sync::echo::{System, WriteFingerprint};
+use crate::sync::history::{RunStatus, SyncRun};
+use crate::sync::matching::{CandidateFilter, LinkCandidate};
+use crate::sync::merge::BaseSnapshot;
+use crate::sync::pending::PendingReference;
+use crate::sync::queue::FailedMessage;
+use crate::sync::{ConflictFilter, ConflictRecord};
 use crate::transform::reference::IdMapping;
 use async_trait::async_trait;
 
@@ -20,14 +29,64 @@ impl D1Storage {
         }
     }
 
+    /// Version of the schema `SCHEMA` creates
+    pub const SCHEMA_VERSION: u32 = 2;
+
+    /// Changes to tables created by earlier versions of `SCHEMA`, keyed by
+    /// the version each brings the database to.
+    ///
+    /// Tables new in a version are left to `SCHEMA`'s `CREATE TABLE IF NOT
+    /// EXISTS`; only columns added to existing tables need a migration.
+    pub const MIGRATIONS: &'static [(u32, &'static str)] = &[(
+        2,
+        r#"
+        ALTER TABLE id_mappings ADD COLUMN attio_hash TEXT;
+        ALTER TABLE id_mappings ADD COLUMN salesforce_hash TEXT;
+
+        ALTER TABLE sync_history ADD COLUMN trigger TEXT NOT NULL DEFAULT 'manual';
+        ALTER TABLE sync_history ADD COLUMN objects TEXT NOT NULL DEFAULT '[]';
+        ALTER TABLE sync_history ADD COLUMN records_deleted INTEGER DEFAULT 0;
+        ALTER TABLE sync_history ADD COLUMN records_unchanged INTEGER DEFAULT 0;
+        ALTER TABLE sync_history ADD COLUMN conflicts INTEGER DEFAULT 0;
+        ALTER TABLE sync_history ADD COLUMN error TEXT;
+        ALTER TABLE sync_history ADD COLUMN duration_ms INTEGER;
+
+        ALTER TABLE conflicts ADD COLUMN conflicting_fields TEXT NOT NULL DEFAULT '[]';
+    "#,
+    )];
+
+    /// Statements that bring a database up to `SCHEMA_VERSION`.
+    ///
+    /// `version` is the one recorded in `schema_version`, or `None` for a new
+    /// database. Databases created before versions were recorded are at 1.
+    pub fn migration_statements(version: Option<u32>) -> Vec<String> {
+        let mut statements: Vec<String> = Self::MIGRATIONS
+            .iter()
+            .filter(|(to, _)| version.is_some_and(|version| *to > version))
+            .map(|(_, sql)| sql.to_string())
+            .collect();
+        statements.push(Self::SCHEMA.to_string());
+        statements.push(format!(
+            "DELETE FROM schema_version; INSERT INTO schema_version (version) VALUES ({});",
+            Self::SCHEMA_VERSION
+        ));
+        statements
+    }
+
     /// SQL schema for initializing the database
     pub const SCHEMA: &'static str = r#"
+        CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER NOT NULL
+        );
+
         CREATE TABLE IF NOT EXISTS id_mappings (
             id TEXT PRIMARY KEY,
             attio_object TEXT NOT NULL,
             attio_id TEXT NOT NULL,
             salesforce_object TEXT NOT NULL,
             salesforce_id TEXT NOT NULL,
+            attio_hash TEXT,
+            salesforce_hash TEXT,
             created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
             updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
             UNIQUE(attio_object, attio_id),
@@ -43,18 +102,55 @@ impl D1Storage {
             updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
         );
 
+        CREATE TABLE IF NOT EXISTS sync_checkpoints (
+            key TEXT PRIMARY KEY,
+            checkpoint_data TEXT NOT NULL,
+            updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );
+
+        CREATE TABLE IF NOT EXISTS pending_references (
+            id TEXT PRIMARY KEY,
+            referenced_object TEXT NOT NULL,
+            referenced_id TEXT NOT NULL,
+            pending_data TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_pending_referenced ON pending_references(referenced_object, referenced_id);
+
+        CREATE TABLE IF NOT EXISTS link_candidates (
+            id TEXT PRIMARY KEY,
+            attio_object TEXT NOT NULL,
+            attio_id TEXT NOT NULL,
+            salesforce_object TEXT NOT NULL,
+            salesforce_id TEXT NOT NULL,
+            confidence REAL NOT NULL,
+            status TEXT NOT NULL,
+            candidate_data TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+
         CREATE TABLE IF NOT EXISTS sync_history (
             id TEXT PRIMARY KEY,
+            trigger TEXT NOT NULL,
             direction TEXT NOT NULL,
+            objects TEXT NOT NULL,
             started_at TEXT NOT NULL,
             completed_at TEXT,
             records_processed INTEGER DEFAULT 0,
             records_created INTEGER DEFAULT 0,
             records_updated INTEGER DEFAULT 0,
+            records_deleted INTEGER DEFAULT 0,
+            records_unchanged INTEGER DEFAULT 0,
+            conflicts INTEGER DEFAULT 0,
             errors INTEGER DEFAULT 0,
-            status TEXT NOT NULL DEFAULT 'running'
+            status TEXT NOT NULL DEFAULT 'running',
+            error TEXT,
+            duration_ms INTEGER
         );
 
+        CREATE INDEX IF NOT EXISTS idx_sync_history_status ON sync_history(status, started_at);
+
         CREATE TABLE IF NOT EXISTS conflicts (
             id TEXT PRIMARY KEY,
             attio_object TEXT NOT NULL,
@@ -64,10 +160,69 @@ impl D1Storage {
             attio_data TEXT NOT NULL,
             salesforce_data TEXT NOT NULL,
             detected_at TEXT NOT NULL,
+            conflicting_fields TEXT NOT NULL DEFAULT '[]',
             status TEXT NOT NULL DEFAULT 'pending',
             resolved_at TEXT,
             resolution TEXT
         );
+
+        CREATE INDEX IF NOT EXISTS idx_conflicts_status ON conflicts(status, detected_at);
+
+        CREATE TABLE IF NOT EXISTS webhook_secrets (
+            webhook_id TEXT PRIMARY KEY,
+            secret TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );
+
+        CREATE TABLE IF NOT EXISTS webhook_failures (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            message_id TEXT NOT NULL,
+            queue TEXT NOT NULL,
+            body TEXT NOT NULL,
+            error TEXT NOT NULL,
+            failed_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_failures_time ON webhook_failures(failed_at);
+
+        CREATE TABLE IF NOT EXISTS write_fingerprints (
+            system TEXT NOT NULL,
+            object TEXT NOT NULL,
+            record_id TEXT NOT NULL,
+            fields TEXT NOT NULL,
+            hash TEXT NOT NULL,
+            expires_at TEXT NOT NULL,
+            PRIMARY KEY (system, object, record_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS base_snapshots (
+            attio_object TEXT NOT NULL,
+            attio_id TEXT NOT NULL,
+            salesforce_object TEXT NOT NULL,
+            salesforce_id TEXT NOT NULL,
+            attio_values TEXT NOT NULL,
+            salesforce_values TEXT NOT NULL,
+            synced_at TEXT NOT NULL,
+            PRIMARY KEY (attio_object, attio_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS audit_log (
+            id TEXT PRIMARY KEY,
+            run_id TEXT,
+            trigger TEXT NOT NULL,
+            event TEXT,
+            action TEXT NOT NULL,
+            direction TEXT NOT NULL,
+            source_object TEXT NOT NULL,
+            source_id TEXT NOT NULL,
+            target_object TEXT NOT NULL,
+            target_id TEXT NOT NULL,
+            changes TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_audit_source ON audit_log(source_object, source_id, created_at);
+        CREATE INDEX IF NOT EXISTS idx_audit_target ON audit_log(target_object, target_id, created_at);
     "#;
 }
 
@@ -122,4 +277,295 @@ impl Storage for D1Storage {
             message: "D1 storage not implemented".to_string(),
         })
     }
+
+    async fn save_webhook_secret(&self, _webhook_id: &str, _secret: &str) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_webhook_secret(&self, _webhook_id: &str) -> Result<Option<String>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn record_failure(&self, _failure: &FailedMessage) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn list_failures(&self, _limit: usize) -> Result<Vec<FailedMessage>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn record_run(&self, _run: &SyncRun) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn list_runs(&self, _status: Option<RunStatus>, _page: Page) -> Result<Vec<SyncRun>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_run(&self, _id: &str) -> Result<Option<SyncRun>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn save_fingerprint(&self, _fingerprint: &WriteFingerprint) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_fingerprint(
+        &self,
+        _system: System,
+        _object: &str,
+        _record_id: &str,
+    ) -> Result<Option<WriteFingerprint>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn save_conflict(&self, _conflict: &ConflictRecord) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn list_conflicts(
+        &self,
+        _filter: &ConflictFilter,
+        _page: Page,
+    ) -> Result<Vec<ConflictRecord>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_conflict(&self, _id: &str) -> Result<Option<ConflictRecord>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn update_conflict(&self, _conflict: &ConflictRecord) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn save_base_snapshot(&self, _snapshot: &BaseSnapshot) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_base_snapshot(
+        &self,
+        _attio_object: &str,
+        _attio_id: &str,
+    ) -> Result<Option<BaseSnapshot>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn delete_base_snapshot(&self, _attio_object: &str, _attio_id: &str) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn record_audit_entry(&self, _entry: &AuditEntry) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn list_audit_entries(
+        &self,
+        _object: &str,
+        _record_id: &str,
+        _page: Page,
+    ) -> Result<Vec<AuditEntry>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn save_checkpoint(&self, _key: &str, _checkpoint: &FullSyncCheckpoint) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_checkpoint(&self, _key: &str) -> Result<Option<FullSyncCheckpoint>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn delete_checkpoint(&self, _key: &str) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn find_snapshots_referencing(
+        &self,
+        _system: System,
+        _record_id: &str,
+    ) -> Result<Vec<BaseSnapshot>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_mappings_by_attio_ids(
+        &self,
+        _object: &str,
+        _attio_ids: &[String],
+    ) -> Result<Vec<IdMapping>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_mappings_by_sf_ids(
+        &self,
+        _object: &str,
+        _sf_ids: &[String],
+    ) -> Result<Vec<IdMapping>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn save_pending_reference(&self, _pending: &PendingReference) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn find_pending_references(
+        &self,
+        _object: &str,
+        _record_id: &str,
+    ) -> Result<Vec<PendingReference>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn find_pending_references_of(
+        &self,
+        _attio_object: &str,
+        _attio_id: &str,
+    ) -> Result<Vec<PendingReference>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn list_pending_references(&self, _limit: usize) -> Result<Vec<PendingReference>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn delete_pending_reference(&self, _id: &str) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn save_link_candidate(&self, _candidate: &LinkCandidate) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_link_candidate(&self, _id: &str) -> Result<Option<LinkCandidate>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+
+    async fn list_link_candidates(
+        &self,
+        _filter: &CandidateFilter,
+        _page: Page,
+    ) -> Result<Vec<LinkCandidate>> {
+        // TODO: Implement with actual Cloudflare Workers D1 bindings
+        Err(Error::Storage {
+            message: "D1 storage not implemented".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migration_statements() {
+        // New databases get the current schema directly
+        let fresh = D1Storage::migration_statements(None);
+        assert_eq!(fresh.len(), 2);
+        assert_eq!(fresh[0], D1Storage::SCHEMA);
+        assert!(fresh[1].contains("VALUES (2)"));
+
+        // Older databases get the missing columns before the schema runs
+        let legacy = D1Storage::migration_statements(Some(1));
+        assert_eq!(legacy.len(), 3);
+        assert!(legacy[0].contains("ADD COLUMN records_unchanged"));
+        assert_eq!(legacy[1], D1Storage::SCHEMA);
+
+        // Up-to-date databases only re-run the idempotent schema
+        let current = D1Storage::migration_statements(Some(D1Storage::SCHEMA_VERSION));
+        assert_eq!(current.len(), 2);
+        assert_eq!(current[0], D1Storage::SCHEMA);
+    }
 }
@@ -1,8 +1,17 @@
 //! Cloudflare KV storage implementation.
 
 use crate::error::{Error, Result};
-use crate::storage::Storage;
+use crate::storage::{Page, Storage};
+use crate::sync::audit::AuditEntry;
+use crate::sync::checkpoint::FullSyncCheckpoint;
 use crate::sync::cursor::SyncCursor;
+use crate::sync::echo::{System, WriteFingerprint};
+use crate::sync::history::{RunStatus, SyncRun};
+use crate::sync::matching::{CandidateFilter, LinkCandidate};
+use crate::sync::merge::BaseSnapshot;
+use crate::sync::pending::PendingReference;
+use crate::sync::queue::FailedMessage;
+use crate::sync::{ConflictFilter, ConflictRecord};
 use crate::transform::reference::IdMapping;
 use async_trait::async_trait;
 
@@ -82,4 +91,270 @@ impl Storage for KVStorage {
             message: "KV storage not implemented".to_string(),
         })
     }
+
+    async fn save_webhook_secret(&self, _webhook_id: &str, _secret: &str) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_webhook_secret(&self, _webhook_id: &str) -> Result<Option<String>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn record_failure(&self, _failure: &FailedMessage) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn list_failures(&self, _limit: usize) -> Result<Vec<FailedMessage>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn record_run(&self, _run: &SyncRun) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn list_runs(&self, _status: Option<RunStatus>, _page: Page) -> Result<Vec<SyncRun>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_run(&self, _id: &str) -> Result<Option<SyncRun>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn save_fingerprint(&self, _fingerprint: &WriteFingerprint) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_fingerprint(
+        &self,
+        _system: System,
+        _object: &str,
+        _record_id: &str,
+    ) -> Result<Option<WriteFingerprint>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn save_conflict(&self, _conflict: &ConflictRecord) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn list_conflicts(
+        &self,
+        _filter: &ConflictFilter,
+        _page: Page,
+    ) -> Result<Vec<ConflictRecord>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_conflict(&self, _id: &str) -> Result<Option<ConflictRecord>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn update_conflict(&self, _conflict: &ConflictRecord) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn save_base_snapshot(&self, _snapshot: &BaseSnapshot) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_base_snapshot(
+        &self,
+        _attio_object: &str,
+        _attio_id: &str,
+    ) -> Result<Option<BaseSnapshot>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn delete_base_snapshot(&self, _attio_object: &str, _attio_id: &str) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn record_audit_entry(&self, _entry: &AuditEntry) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn list_audit_entries(
+        &self,
+        _object: &str,
+        _record_id: &str,
+        _page: Page,
+    ) -> Result<Vec<AuditEntry>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn save_checkpoint(&self, _key: &str, _checkpoint: &FullSyncCheckpoint) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_checkpoint(&self, _key: &str) -> Result<Option<FullSyncCheckpoint>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn delete_checkpoint(&self, _key: &str) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn find_snapshots_referencing(
+        &self,
+        _system: System,
+        _record_id: &str,
+    ) -> Result<Vec<BaseSnapshot>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_mappings_by_attio_ids(
+        &self,
+        _object: &str,
+        _attio_ids: &[String],
+    ) -> Result<Vec<IdMapping>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_mappings_by_sf_ids(
+        &self,
+        _object: &str,
+        _sf_ids: &[String],
+    ) -> Result<Vec<IdMapping>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn save_pending_reference(&self, _pending: &PendingReference) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn find_pending_references(
+        &self,
+        _object: &str,
+        _record_id: &str,
+    ) -> Result<Vec<PendingReference>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn find_pending_references_of(
+        &self,
+        _attio_object: &str,
+        _attio_id: &str,
+    ) -> Result<Vec<PendingReference>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn list_pending_references(&self, _limit: usize) -> Result<Vec<PendingReference>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn delete_pending_reference(&self, _id: &str) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn save_link_candidate(&self, _candidate: &LinkCandidate) -> Result<()> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn get_link_candidate(&self, _id: &str) -> Result<Option<LinkCandidate>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
+
+    async fn list_link_candidates(
+        &self,
+        _filter: &CandidateFilter,
+        _page: Page,
+    ) -> Result<Vec<LinkCandidate>> {
+        // TODO: Implement with actual Cloudflare Workers KV bindings
+        Err(Error::Storage {
+            message: "KV storage not implemented".to_string(),
+        })
+    }
 }
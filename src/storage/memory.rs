@@ -1,10 +1,21 @@
 //! In-memory storage implementation for testing.
 
-use crate::error::Result;
-use crate::storage::Storage;
+use crate::error::{Error, Result};
+use crate::storage::{Page, Storage};
+use crate::sync::audit::AuditEntry;
+use crate::sync::checkpoint::FullSyncCheckpoint;
 use crate::sync::cursor::SyncCursor;
+use crate::sync::echo::{System, WriteFingerprint};
+use crate::sync::history::{RunStatus, SyncRun};
+use crate::sync::matching::{CandidateFilter, LinkCandidate};
+use crate::sync::merge::BaseSnapshot;
+use crate::sync::pending::PendingReference;
+use crate::sync::queue::FailedMessage;
+use crate::sync::{ConflictFilter, ConflictRecord};
 use crate::transform::reference::IdMapping;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::RwLock;
 
@@ -12,20 +23,101 @@ use std::sync::RwLock;
 pub struct MemoryStorage {
     mappings: RwLock<HashMap<String, IdMapping>>,
     cursors: RwLock<HashMap<String, SyncCursor>>,
+    webhook_secrets: RwLock<HashMap<String, String>>,
+    failures: RwLock<Vec<FailedMessage>>,
+    runs: RwLock<Vec<SyncRun>>,
+    fingerprints: RwLock<HashMap<String, WriteFingerprint>>,
+    base_snapshots: RwLock<HashMap<String, BaseSnapshot>>,
+    conflicts: RwLock<Vec<ConflictRecord>>,
+    audit_log: RwLock<Vec<AuditEntry>>,
+    checkpoints: RwLock<HashMap<String, FullSyncCheckpoint>>,
+    pending_references: RwLock<HashMap<String, PendingReference>>,
+    link_candidates: RwLock<HashMap<String, LinkCandidate>>,
+}
+
+/// Serializable snapshot of in-memory state (used by the CLI state file)
+#[derive(Default, Serialize, Deserialize)]
+struct MemorySnapshot {
+    #[serde(default)]
+    mappings: HashMap<String, IdMapping>,
+    #[serde(default)]
+    cursors: HashMap<String, SyncCursor>,
+    #[serde(default)]
+    webhook_secrets: HashMap<String, String>,
+    #[serde(default)]
+    failures: Vec<FailedMessage>,
+    #[serde(default)]
+    runs: Vec<SyncRun>,
+    #[serde(default)]
+    fingerprints: HashMap<String, WriteFingerprint>,
+    #[serde(default)]
+    base_snapshots: HashMap<String, BaseSnapshot>,
+    #[serde(default)]
+    conflicts: Vec<ConflictRecord>,
+    #[serde(default)]
+    audit_log: Vec<AuditEntry>,
+    #[serde(default)]
+    checkpoints: HashMap<String, FullSyncCheckpoint>,
+    #[serde(default)]
+    pending_references: HashMap<String, PendingReference>,
+    #[serde(default)]
+    link_candidates: HashMap<String, LinkCandidate>,
 }
 
 impl MemoryStorage {
     /// Create a new in-memory storage
     pub fn new() -> Self {
+        Self::from_snapshot(MemorySnapshot::default())
+    }
+
+    fn from_snapshot(snapshot: MemorySnapshot) -> Self {
         Self {
-            mappings: RwLock::new(HashMap::new()),
-            cursors: RwLock::new(HashMap::new()),
+            mappings: RwLock::new(snapshot.mappings),
+            cursors: RwLock::new(snapshot.cursors),
+            webhook_secrets: RwLock::new(snapshot.webhook_secrets),
+            failures: RwLock::new(snapshot.failures),
+            runs: RwLock::new(snapshot.runs),
+            fingerprints: RwLock::new(snapshot.fingerprints),
+            base_snapshots: RwLock::new(snapshot.base_snapshots),
+            conflicts: RwLock::new(snapshot.conflicts),
+            audit_log: RwLock::new(snapshot.audit_log),
+            checkpoints: RwLock::new(snapshot.checkpoints),
+            pending_references: RwLock::new(snapshot.pending_references),
+            link_candidates: RwLock::new(snapshot.link_candidates),
         }
     }
 
+    /// Serialize all stored state to a JSON string
+    pub fn to_json(&self) -> std::result::Result<String, serde_json::Error> {
+        let snapshot = MemorySnapshot {
+            mappings: self.mappings.read().unwrap().clone(),
+            cursors: self.cursors.read().unwrap().clone(),
+            webhook_secrets: self.webhook_secrets.read().unwrap().clone(),
+            failures: self.failures.read().unwrap().clone(),
+            runs: self.runs.read().unwrap().clone(),
+            fingerprints: self.fingerprints.read().unwrap().clone(),
+            base_snapshots: self.base_snapshots.read().unwrap().clone(),
+            conflicts: self.conflicts.read().unwrap().clone(),
+            audit_log: self.audit_log.read().unwrap().clone(),
+            checkpoints: self.checkpoints.read().unwrap().clone(),
+            pending_references: self.pending_references.read().unwrap().clone(),
+            link_candidates: self.link_candidates.read().unwrap().clone(),
+        };
+        serde_json::to_string_pretty(&snapshot)
+    }
+
+    /// Restore storage from a JSON string produced by `to_json`
+    pub fn from_json(json: &str) -> std::result::Result<Self, serde_json::Error> {
+        serde_json::from_str(json).map(Self::from_snapshot)
+    }
+
     fn mapping_key(object: &str, id: &str) -> String {
         format!("{}:{}", object, id)
     }
+
+    fn fingerprint_key(system: System, object: &str, id: &str) -> String {
+        format!("{}:{}:{}", system.as_str(), object, id)
+    }
 }
 
 impl Default for MemoryStorage {
@@ -80,9 +172,296 @@ impl Storage for MemoryStorage {
     async fn delete_mapping(&self, attio_object: &str, attio_id: &str) -> Result<()> {
         let mut mappings = self.mappings.write().unwrap();
         let key = Self::mapping_key(attio_object, attio_id);
-        mappings.remove(&key);
+        if let Some(mapping) = mappings.remove(&key) {
+            let sf_key = Self::mapping_key(&mapping.salesforce_object, &mapping.salesforce_id);
+            mappings.remove(&sf_key);
+        }
+        Ok(())
+    }
+
+    async fn save_webhook_secret(&self, webhook_id: &str, secret: &str) -> Result<()> {
+        let mut secrets = self.webhook_secrets.write().unwrap();
+        secrets.insert(webhook_id.to_string(), secret.to_string());
+        Ok(())
+    }
+
+    async fn get_webhook_secret(&self, webhook_id: &str) -> Result<Option<String>> {
+        let secrets = self.webhook_secrets.read().unwrap();
+        Ok(secrets.get(webhook_id).cloned())
+    }
+
+    async fn record_failure(&self, failure: &FailedMessage) -> Result<()> {
+        let mut failures = self.failures.write().unwrap();
+        failures.push(failure.clone());
+        Ok(())
+    }
+
+    async fn list_failures(&self, limit: usize) -> Result<Vec<FailedMessage>> {
+        let failures = self.failures.read().unwrap();
+        Ok(failures.iter().rev().take(limit).cloned().collect())
+    }
+
+    async fn record_run(&self, run: &SyncRun) -> Result<()> {
+        let mut runs = self.runs.write().unwrap();
+        match runs.iter_mut().find(|r| r.id == run.id) {
+            Some(existing) => *existing = run.clone(),
+            None => runs.push(run.clone()),
+        }
+        Ok(())
+    }
+
+    async fn list_runs(&self, status: Option<RunStatus>, page: Page) -> Result<Vec<SyncRun>> {
+        let runs = self.runs.read().unwrap();
+        let mut matching: Vec<_> = runs
+            .iter()
+            .filter(|r| status.is_none_or(|s| r.status == s))
+            .collect();
+        matching.sort_by_key(|r| std::cmp::Reverse(r.started_at));
+        Ok(page.apply(matching.into_iter().cloned()))
+    }
+
+    async fn get_run(&self, id: &str) -> Result<Option<SyncRun>> {
+        let runs = self.runs.read().unwrap();
+        Ok(runs.iter().find(|r| r.id == id).cloned())
+    }
+
+    async fn save_fingerprint(&self, fingerprint: &WriteFingerprint) -> Result<()> {
+        let mut fingerprints = self.fingerprints.write().unwrap();
+        let key = Self::fingerprint_key(
+            fingerprint.system,
+            &fingerprint.object,
+            &fingerprint.record_id,
+        );
+        fingerprints.insert(key, fingerprint.clone());
+        Ok(())
+    }
+
+    async fn get_fingerprint(
+        &self,
+        system: System,
+        object: &str,
+        record_id: &str,
+    ) -> Result<Option<WriteFingerprint>> {
+        let fingerprints = self.fingerprints.read().unwrap();
+        let key = Self::fingerprint_key(system, object, record_id);
+        Ok(fingerprints.get(&key).cloned())
+    }
+
+    async fn save_conflict(&self, conflict: &ConflictRecord) -> Result<()> {
+        let mut conflicts = self.conflicts.write().unwrap();
+        conflicts.push(conflict.clone());
+        Ok(())
+    }
+
+    async fn list_conflicts(
+        &self,
+        filter: &ConflictFilter,
+        page: Page,
+    ) -> Result<Vec<ConflictRecord>> {
+        let conflicts = self.conflicts.read().unwrap();
+        let mut matching: Vec<_> = conflicts.iter().filter(|c| filter.matches(c)).collect();
+        matching.sort_by_key(|c| std::cmp::Reverse(c.detected_at));
+        Ok(page.apply(matching.into_iter().cloned()))
+    }
+
+    async fn get_conflict(&self, id: &str) -> Result<Option<ConflictRecord>> {
+        let conflicts = self.conflicts.read().unwrap();
+        Ok(conflicts.iter().find(|c| c.id == id).cloned())
+    }
+
+    async fn update_conflict(&self, conflict: &ConflictRecord) -> Result<()> {
+        let mut conflicts = self.conflicts.write().unwrap();
+        let existing = conflicts
+            .iter_mut()
+            .find(|c| c.id == conflict.id)
+            .ok_or_else(|| Error::not_found("Conflict", &conflict.id))?;
+        *existing = conflict.clone();
+        Ok(())
+    }
+
+    async fn save_base_snapshot(&self, snapshot: &BaseSnapshot) -> Result<()> {
+        let mut snapshots = self.base_snapshots.write().unwrap();
+        let key = Self::mapping_key(&snapshot.attio_object, &snapshot.attio_id);
+        snapshots.insert(key, snapshot.clone());
+        Ok(())
+    }
+
+    async fn get_base_snapshot(
+        &self,
+        attio_object: &str,
+        attio_id: &str,
+    ) -> Result<Option<BaseSnapshot>> {
+        let snapshots = self.base_snapshots.read().unwrap();
+        let key = Self::mapping_key(attio_object, attio_id);
+        Ok(snapshots.get(&key).cloned())
+    }
+
+    async fn delete_base_snapshot(&self, attio_object: &str, attio_id: &str) -> Result<()> {
+        let mut snapshots = self.base_snapshots.write().unwrap();
+        snapshots.remove(&Self::mapping_key(attio_object, attio_id));
         Ok(())
     }
+
+    async fn record_audit_entry(&self, entry: &AuditEntry) -> Result<()> {
+        let mut audit_log = self.audit_log.write().unwrap();
+        audit_log.push(entry.clone());
+        Ok(())
+    }
+
+    async fn list_audit_entries(
+        &self,
+        object: &str,
+        record_id: &str,
+        page: Page,
+    ) -> Result<Vec<AuditEntry>> {
+        let audit_log = self.audit_log.read().unwrap();
+        let matching = audit_log
+            .iter()
+            .rev()
+            .filter(|e| e.involves(object, record_id))
+            .cloned();
+        Ok(page.apply(matching))
+    }
+
+    async fn save_checkpoint(&self, key: &str, checkpoint: &FullSyncCheckpoint) -> Result<()> {
+        let mut checkpoints = self.checkpoints.write().unwrap();
+        checkpoints.insert(key.to_string(), checkpoint.clone());
+        Ok(())
+    }
+
+    async fn get_checkpoint(&self, key: &str) -> Result<Option<FullSyncCheckpoint>> {
+        let checkpoints = self.checkpoints.read().unwrap();
+        Ok(checkpoints.get(key).cloned())
+    }
+
+    async fn delete_checkpoint(&self, key: &str) -> Result<()> {
+        self.checkpoints.write().unwrap().remove(key);
+        Ok(())
+    }
+
+    async fn find_snapshots_referencing(
+        &self,
+        system: System,
+        record_id: &str,
+    ) -> Result<Vec<BaseSnapshot>> {
+        let snapshots = self.base_snapshots.read().unwrap();
+        Ok(snapshots
+            .values()
+            .filter(|snapshot| {
+                let values = match system {
+                    System::Attio => &snapshot.attio,
+                    System::Salesforce => &snapshot.salesforce,
+                };
+                values.values().any(|value| references(value, record_id))
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn get_mappings_by_attio_ids(
+        &self,
+        object: &str,
+        attio_ids: &[String],
+    ) -> Result<Vec<IdMapping>> {
+        let mappings = self.mappings.read().unwrap();
+        Ok(attio_ids
+            .iter()
+            .filter_map(|id| mappings.get(&Self::mapping_key(object, id)).cloned())
+            .collect())
+    }
+
+    async fn get_mappings_by_sf_ids(
+        &self,
+        object: &str,
+        sf_ids: &[String],
+    ) -> Result<Vec<IdMapping>> {
+        let mappings = self.mappings.read().unwrap();
+        Ok(sf_ids
+            .iter()
+            .filter_map(|id| mappings.get(&Self::mapping_key(object, id)).cloned())
+            .collect())
+    }
+
+    async fn save_pending_reference(&self, pending: &PendingReference) -> Result<()> {
+        let mut references = self.pending_references.write().unwrap();
+        references.insert(pending.id.clone(), pending.clone());
+        Ok(())
+    }
+
+    async fn find_pending_references(
+        &self,
+        object: &str,
+        record_id: &str,
+    ) -> Result<Vec<PendingReference>> {
+        let references = self.pending_references.read().unwrap();
+        Ok(references
+            .values()
+            .filter(|p| p.referenced_object == object && p.referenced_id == record_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_pending_references_of(
+        &self,
+        attio_object: &str,
+        attio_id: &str,
+    ) -> Result<Vec<PendingReference>> {
+        let references = self.pending_references.read().unwrap();
+        Ok(references
+            .values()
+            .filter(|p| p.link.attio_object == attio_object && p.link.attio_id == attio_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn list_pending_references(&self, limit: usize) -> Result<Vec<PendingReference>> {
+        let references = self.pending_references.read().unwrap();
+        let mut pending: Vec<PendingReference> = references.values().cloned().collect();
+        pending.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        pending.truncate(limit);
+        Ok(pending)
+    }
+
+    async fn delete_pending_reference(&self, id: &str) -> Result<()> {
+        self.pending_references.write().unwrap().remove(id);
+        Ok(())
+    }
+
+    async fn save_link_candidate(&self, candidate: &LinkCandidate) -> Result<()> {
+        let mut candidates = self.link_candidates.write().unwrap();
+        candidates.insert(candidate.id.clone(), candidate.clone());
+        Ok(())
+    }
+
+    async fn get_link_candidate(&self, id: &str) -> Result<Option<LinkCandidate>> {
+        let candidates = self.link_candidates.read().unwrap();
+        Ok(candidates.get(id).cloned())
+    }
+
+    async fn list_link_candidates(
+        &self,
+        filter: &CandidateFilter,
+        page: Page,
+    ) -> Result<Vec<LinkCandidate>> {
+        let candidates = self.link_candidates.read().unwrap();
+        let mut matching: Vec<_> = candidates.values().filter(|c| filter.matches(c)).collect();
+        matching.sort_by(|a, b| {
+            b.confidence
+                .total_cmp(&a.confidence)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        Ok(page.apply(matching.into_iter().cloned()))
+    }
+}
+
+/// Check whether a stored field value is, or contains, a record ID
+fn references(value: &Value, record_id: &str) -> bool {
+    match value {
+        Value::String(s) => s == record_id,
+        Value::Array(values) => values.iter().any(|v| references(v, record_id)),
+        Value::Object(fields) => fields.values().any(|v| references(v, record_id)),
+        _ => false,
+    }
 }
 
 #[cfg(test)]
@@ -103,5 +482,126 @@ mod tests {
             .unwrap();
         assert!(found.is_some());
         assert_eq!(found.unwrap().salesforce_id, "001xxx");
+
+        let found = storage
+            .get_mappings_by_sf_ids("Account", &["001xxx".to_string(), "001yyy".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+
+        storage
+            .delete_mapping("companies", "rec_123")
+            .await
+            .unwrap();
+        assert!(storage
+            .get_mapping_by_sf_id("Account", "001xxx")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_failures() {
+        let storage = MemoryStorage::new();
+        for id in ["msg_1", "msg_2", "msg_3"] {
+            let failure =
+                FailedMessage::new(id, "attio-sfdc-webhooks", serde_json::json!({}), "boom");
+            storage.record_failure(&failure).await.unwrap();
+        }
+
+        let failures = storage.list_failures(2).await.unwrap();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].message_id, "msg_3");
+    }
+
+    #[tokio::test]
+    async fn test_json_roundtrip() {
+        let storage = MemoryStorage::new();
+        storage
+            .save_id_mapping(&IdMapping::new("companies", "rec_123", "Account", "001xxx"))
+            .await
+            .unwrap();
+        storage
+            .save_webhook_secret("wh_1", "whsec_abc")
+            .await
+            .unwrap();
+
+        let restored = MemoryStorage::from_json(&storage.to_json().unwrap()).unwrap();
+        assert!(restored
+            .get_mapping_by_sf_id("Account", "001xxx")
+            .await
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            restored.get_webhook_secret("wh_1").await.unwrap(),
+            Some("whsec_abc".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_conflict_queue() {
+        let storage = MemoryStorage::new();
+        for (object, sf_object) in [("companies", "Account"), ("people", "Contact")] {
+            let conflict = ConflictRecord::new(
+                object,
+                "rec_1",
+                sf_object,
+                "001xxx",
+                serde_json::json!({}),
+                serde_json::json!({}),
+            );
+            storage.save_conflict(&conflict).await.unwrap();
+        }
+
+        let filter = ConflictFilter {
+            object: Some("companies".to_string()),
+            ..ConflictFilter::default()
+        };
+        let mut found = storage
+            .list_conflicts(&filter, Page::default())
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+
+        let mut conflict = found.remove(0);
+        conflict.status = crate::sync::ConflictStatus::Skipped;
+        storage.update_conflict(&conflict).await.unwrap();
+        let stored = storage.get_conflict(&conflict.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, crate::sync::ConflictStatus::Skipped);
+
+        assert_eq!(
+            storage
+                .list_conflicts(&ConflictFilter::default(), Page::new(2, 1))
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_history() {
+        use crate::config::SyncDirection;
+        use crate::sync::history::SyncTrigger;
+
+        let storage = MemoryStorage::new();
+        let mut run = SyncRun::start(
+            SyncTrigger::Manual,
+            SyncDirection::Bidirectional,
+            vec!["companies".to_string()],
+        );
+        storage.record_run(&run).await.unwrap();
+        run.finish(&Ok(crate::sync::SyncResult::default()));
+        storage.record_run(&run).await.unwrap();
+
+        let all = storage.list_runs(None, Page::default()).await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].status, RunStatus::Succeeded);
+        let failed = storage
+            .list_runs(Some(RunStatus::Failed), Page::default())
+            .await
+            .unwrap();
+        assert!(failed.is_empty());
+        assert!(storage.get_run(&run.id).await.unwrap().is_some());
     }
 }
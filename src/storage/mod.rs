@@ -9,10 +9,53 @@ pub use kv::KVStorage;
 pub use memory::MemoryStorage;
 
 use crate::error::Result;
+use crate::sync::audit::AuditEntry;
+use crate::sync::checkpoint::FullSyncCheckpoint;
 use crate::sync::cursor::SyncCursor;
+use crate::sync::echo::{System, WriteFingerprint};
+use crate::sync::history::{RunStatus, SyncRun};
+use crate::sync::matching::{CandidateFilter, LinkCandidate};
+use crate::sync::merge::BaseSnapshot;
+use crate::sync::pending::PendingReference;
+use crate::sync::queue::FailedMessage;
+use crate::sync::{ConflictFilter, ConflictRecord};
 use crate::transform::reference::IdMapping;
 use async_trait::async_trait;
 
+/// Default number of items per page for list queries
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// Page of results for list queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    /// Number of items to skip
+    pub offset: usize,
+
+    /// Maximum number of items to return
+    pub limit: usize,
+}
+
+impl Page {
+    /// Page `number` (1-based) of `size` items
+    pub fn new(number: usize, size: usize) -> Self {
+        Self {
+            offset: number.saturating_sub(1) * size,
+            limit: size,
+        }
+    }
+
+    /// Take this page from an iterator
+    pub fn apply<T>(&self, items: impl Iterator<Item = T>) -> Vec<T> {
+        items.skip(self.offset).take(self.limit).collect()
+    }
+}
+
+impl Default for Page {
+    fn default() -> Self {
+        Self::new(1, DEFAULT_PAGE_SIZE)
+    }
+}
+
 /// Storage trait for persistence operations
 #[async_trait(?Send)]
 pub trait Storage {
@@ -41,4 +84,142 @@ pub trait Storage {
 
     /// Delete ID mapping
     async fn delete_mapping(&self, attio_object: &str, attio_id: &str) -> Result<()>;
+
+    /// Save the signing secret returned when an Attio webhook is created
+    async fn save_webhook_secret(&self, webhook_id: &str, secret: &str) -> Result<()>;
+
+    /// Get the signing secret for an Attio webhook
+    async fn get_webhook_secret(&self, webhook_id: &str) -> Result<Option<String>>;
+
+    /// Record a queue message that could not be processed
+    async fn record_failure(&self, failure: &FailedMessage) -> Result<()>;
+
+    /// List recorded failures, most recent first
+    async fn list_failures(&self, limit: usize) -> Result<Vec<FailedMessage>>;
+
+    /// Insert or update a sync run in the history
+    async fn record_run(&self, run: &SyncRun) -> Result<()>;
+
+    /// List sync runs, optionally with one status, most recent first
+    async fn list_runs(&self, status: Option<RunStatus>, page: Page) -> Result<Vec<SyncRun>>;
+
+    /// Get a sync run by ID
+    async fn get_run(&self, id: &str) -> Result<Option<SyncRun>>;
+
+    /// Save the fingerprint of a record the bridge just wrote
+    async fn save_fingerprint(&self, fingerprint: &WriteFingerprint) -> Result<()>;
+
+    /// Get the latest write fingerprint for a record
+    async fn get_fingerprint(
+        &self,
+        system: System,
+        object: &str,
+        record_id: &str,
+    ) -> Result<Option<WriteFingerprint>>;
+
+    /// Save a detected conflict
+    async fn save_conflict(&self, conflict: &ConflictRecord) -> Result<()>;
+
+    /// List conflicts matching a filter, most recent first
+    async fn list_conflicts(
+        &self,
+        filter: &ConflictFilter,
+        page: Page,
+    ) -> Result<Vec<ConflictRecord>>;
+
+    /// Get a conflict by ID
+    async fn get_conflict(&self, id: &str) -> Result<Option<ConflictRecord>>;
+
+    /// Update a stored conflict (fails if it does not exist)
+    async fn update_conflict(&self, conflict: &ConflictRecord) -> Result<()>;
+
+    /// Save the last-synced field values of a link
+    async fn save_base_snapshot(&self, snapshot: &BaseSnapshot) -> Result<()>;
+
+    /// Get the last-synced field values of a link
+    async fn get_base_snapshot(
+        &self,
+        attio_object: &str,
+        attio_id: &str,
+    ) -> Result<Option<BaseSnapshot>>;
+
+    /// Delete the last-synced field values of a link (no-op if missing)
+    async fn delete_base_snapshot(&self, attio_object: &str, attio_id: &str) -> Result<()>;
+
+    /// Append an entry to the audit log
+    async fn record_audit_entry(&self, entry: &AuditEntry) -> Result<()>;
+
+    /// List audit entries where a record is the source or target, most recent first
+    async fn list_audit_entries(
+        &self,
+        object: &str,
+        record_id: &str,
+        page: Page,
+    ) -> Result<Vec<AuditEntry>>;
+
+    /// Save full sync checkpoint
+    async fn save_checkpoint(&self, key: &str, checkpoint: &FullSyncCheckpoint) -> Result<()>;
+
+    /// Get full sync checkpoint
+    async fn get_checkpoint(&self, key: &str) -> Result<Option<FullSyncCheckpoint>>;
+
+    /// Delete full sync checkpoint (no-op if there is none)
+    async fn delete_checkpoint(&self, key: &str) -> Result<()>;
+
+    /// Base snapshots whose values on one side reference a record ID (e.g., children of a parent)
+    async fn find_snapshots_referencing(
+        &self,
+        system: System,
+        record_id: &str,
+    ) -> Result<Vec<BaseSnapshot>>;
+
+    /// Get the ID mappings of several Attio records in one lookup
+    async fn get_mappings_by_attio_ids(
+        &self,
+        object: &str,
+        attio_ids: &[String],
+    ) -> Result<Vec<IdMapping>>;
+
+    /// Get the ID mappings of several Salesforce records in one lookup
+    async fn get_mappings_by_sf_ids(
+        &self,
+        object: &str,
+        sf_ids: &[String],
+    ) -> Result<Vec<IdMapping>>;
+
+    /// Save a pending reference, replacing the entry with the same ID
+    async fn save_pending_reference(&self, pending: &PendingReference) -> Result<()>;
+
+    /// Pending references waiting for a record to be linked (by its object and ID)
+    async fn find_pending_references(
+        &self,
+        object: &str,
+        record_id: &str,
+    ) -> Result<Vec<PendingReference>>;
+
+    /// Pending references to patch on a linked record (by its Attio object and ID)
+    async fn find_pending_references_of(
+        &self,
+        attio_object: &str,
+        attio_id: &str,
+    ) -> Result<Vec<PendingReference>>;
+
+    /// List pending references, oldest first
+    async fn list_pending_references(&self, limit: usize) -> Result<Vec<PendingReference>>;
+
+    /// Delete a pending reference (no-op if missing)
+    async fn delete_pending_reference(&self, id: &str) -> Result<()>;
+
+    /// Save a link candidate, replacing the candidate with the same ID
+    async fn save_link_candidate(&self, candidate: &LinkCandidate) -> Result<()>;
+
+    /// Get a link candidate by ID
+    async fn get_link_candidate(&self, id: &str) -> Result<Option<LinkCandidate>>;
+
+    /// List link candidates matching a filter, highest confidence first
+    async fn list_link_candidates(
+        &self,
+        filter: &CandidateFilter,
+        page: Page,
+    ) -> Result<Vec<LinkCandidate>>;
 }
@@ -0,0 +1,214 @@
+//! Audit trail of the writes the bridge makes.
+//!
+//! Every create, update and delete records which run made it, from which
+//! source record, and the field values before and after.
+
+use crate::config::SyncDirection;
+use crate::sync::echo::System;
+use crate::sync::history::SyncTrigger;
+use crate::sync::merge::FieldValues;
+use crate::transform::reference::IdMapping;
+use crate::transform::TransformPipeline;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Kind of write
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// Record created
+    Create,
+    /// Record updated
+    Update,
+    /// Record deleted
+    Delete,
+}
+
+/// Before/after values of one written field
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FieldChange {
+    /// Field name on the target side
+    pub field: String,
+
+    /// Value before the write (`None` if unknown or unset)
+    pub before: Option<Value>,
+
+    /// Value written
+    pub after: Value,
+}
+
+/// One write made by the bridge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Entry ID
+    pub id: String,
+
+    /// Sync run that made the write (if any)
+    pub run_id: Option<String>,
+
+    /// What started the write
+    pub trigger: SyncTrigger,
+
+    /// Event that caused the write (e.g., "attio record.updated")
+    pub event: Option<String>,
+
+    /// Kind of write
+    pub action: AuditAction,
+
+    /// Direction of the write
+    pub direction: SyncDirection,
+
+    /// Object type the values came from
+    pub source_object: String,
+
+    /// Record ID the values came from
+    pub source_id: String,
+
+    /// Object type written to
+    pub target_object: String,
+
+    /// Record ID written to
+    pub target_id: String,
+
+    /// Field-level diff
+    pub changes: Vec<FieldChange>,
+
+    /// When the write happened
+    pub created_at: DateTime<Utc>,
+}
+
+/// Run and event the engine is currently working on
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext {
+    /// Current run ID
+    pub run_id: Option<String>,
+
+    /// Current triggering event
+    pub event: Option<String>,
+}
+
+impl AuditEntry {
+    /// Entry for a write to the `target` side of a link
+    pub fn new(
+        context: &AuditContext,
+        trigger: SyncTrigger,
+        action: AuditAction,
+        target: System,
+        link: &IdMapping,
+        changes: Vec<FieldChange>,
+    ) -> Self {
+        let attio = (link.attio_object.clone(), link.attio_id.clone());
+        let salesforce = (link.salesforce_object.clone(), link.salesforce_id.clone());
+        let (direction, (source_object, source_id), (target_object, target_id)) = match target {
+            System::Salesforce => (SyncDirection::AttioToSalesforce, attio, salesforce),
+            System::Attio => (SyncDirection::SalesforceToAttio, salesforce, attio),
+        };
+
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            run_id: context.run_id.clone(),
+            trigger,
+            event: context.event.clone(),
+            action,
+            direction,
+            source_object,
+            source_id,
+            target_object,
+            target_id,
+            changes,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Check whether the entry concerns a record, as source or target
+    pub fn involves(&self, object: &str, record_id: &str) -> bool {
+        (self.source_object == object && self.source_id == record_id)
+            || (self.target_object == object && self.target_id == record_id)
+    }
+}
+
+/// Diff written fields against their previous values, leaving out unchanged ones
+pub fn diff_fields(
+    pipeline: &TransformPipeline,
+    fields: &[String],
+    before: &FieldValues,
+    written: &Value,
+) -> Vec<FieldChange> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            let after = pipeline.get_nested_value(written, field)?;
+            let before = before.get(field).cloned();
+            (before.as_ref() != Some(&after)).then(|| FieldChange {
+                field: field.clone(),
+                before,
+                after,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_sides() {
+        let link = IdMapping::new("deals", "rec_1", "Opportunity", "006xxx");
+        let entry = AuditEntry::new(
+            &AuditContext::default(),
+            SyncTrigger::Webhook,
+            AuditAction::Update,
+            System::Attio,
+            &link,
+            vec![],
+        );
+        assert_eq!(entry.direction, SyncDirection::SalesforceToAttio);
+        assert_eq!(entry.source_id, "006xxx");
+        assert_eq!(entry.target_id, "rec_1");
+        assert!(entry.involves("Opportunity", "006xxx"));
+        assert!(entry.involves("deals", "rec_1"));
+        assert!(!entry.involves("deals", "006xxx"));
+    }
+
+    #[test]
+    fn test_diff_fields() {
+        let pipeline = TransformPipeline::new();
+        let fields = vec![
+            "Name".to_string(),
+            "Industry".to_string(),
+            "Website".to_string(),
+            "Phone".to_string(),
+        ];
+        let before: FieldValues = [
+            ("Name".to_string(), serde_json::json!("Acme")),
+            ("Industry".to_string(), serde_json::json!("Tech")),
+        ]
+        .into_iter()
+        .collect();
+        let written = serde_json::json!({
+            "Name": "Acme",
+            "Industry": "Software",
+            "Website": "acme.com"
+        });
+
+        // Unchanged and unwritten fields are left out; unknown previous values are `None`
+        let changes = diff_fields(&pipeline, &fields, &before, &written);
+        assert_eq!(
+            changes,
+            vec![
+                FieldChange {
+                    field: "Industry".to_string(),
+                    before: Some(serde_json::json!("Tech")),
+                    after: serde_json::json!("Software"),
+                },
+                FieldChange {
+                    field: "Website".to_string(),
+                    before: None,
+                    after: serde_json::json!("acme.com"),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Checkpoints for resumable full syncs.
+//!
+//! A full sync pages through every record of every enabled mapping. After
+//! each page the engine saves where it got to, so a run cut short by a
+//! crash, a CPU limit or a deploy picks up from the last page instead of
+//! starting over. The matching phase before it is checkpointed the same way,
+//! a page of applied pairs at a time.
+
+use crate::config::SyncDirection;
+use crate::sync::cursor::ObjectCursor;
+use crate::sync::engine::SyncResult;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Storage key of the full sync checkpoint
+pub const FULL_SYNC_CHECKPOINT_KEY: &str = "full_sync";
+
+/// Progress of a full sync, saved after every page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FullSyncCheckpoint {
+    /// Direction the full sync was started with
+    pub direction: SyncDirection,
+
+    /// Source objects that have been paged through completely
+    pub completed: Vec<String>,
+
+    /// Position in the source object currently being paged through
+    pub cursor: Option<ObjectCursor>,
+
+    /// Records processed so far
+    pub records_processed: u64,
+
+    /// Records created so far
+    pub records_created: u64,
+
+    /// Records updated so far
+    pub records_updated: u64,
+
+    /// Records skipped as unchanged so far
+    pub records_unchanged: u64,
+
+    /// Conflicts detected so far
+    pub conflicts: u64,
+
+    /// Records that failed so far
+    pub errors: u64,
+
+    /// Mappings whose records have been matched
+    #[serde(default)]
+    pub matched: Vec<String>,
+
+    /// Record pairs linked by matching so far
+    #[serde(default)]
+    pub records_linked: u64,
+
+    /// Record pairs left for review by matching so far
+    #[serde(default)]
+    pub link_candidates: u64,
+
+    /// Position in the mapping currently being matched (by mapping key and Attio record ID)
+    #[serde(default)]
+    pub match_cursor: Option<ObjectCursor>,
+
+    /// When the full sync was first started
+    pub started_at: DateTime<Utc>,
+
+    /// When the last page was checkpointed
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FullSyncCheckpoint {
+    /// Checkpoint for a full sync starting from scratch
+    pub fn new(direction: SyncDirection) -> Self {
+        let now = Utc::now();
+        Self {
+            direction,
+            completed: Vec::new(),
+            cursor: None,
+            records_processed: 0,
+            records_created: 0,
+            records_updated: 0,
+            records_unchanged: 0,
+            conflicts: 0,
+            errors: 0,
+            matched: Vec::new(),
+            records_linked: 0,
+            link_candidates: 0,
+            match_cursor: None,
+            started_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Check whether a source object has been paged through completely
+    pub fn is_completed(&self, object: &str) -> bool {
+        self.completed.iter().any(|o| o == object)
+    }
+
+    /// Last record ID synced in a source object, if it is the one in progress
+    pub fn last_record_id(&self, object: &str) -> Option<&str> {
+        self.cursor
+            .as_ref()
+            .filter(|c| c.object == object)
+            .and_then(|c| c.last_record_id.as_deref())
+    }
+
+    /// Record a synced page of a source object
+    pub fn page_done(&mut self, object: &str, last_record_id: Option<String>, page: &SyncResult) {
+        let cursor = self.cursor.get_or_insert_with(|| ObjectCursor::new(object));
+        if cursor.object != object {
+            *cursor = ObjectCursor::new(object);
+        }
+        cursor.update(last_record_id, page.records_processed);
+
+        self.records_processed += page.records_processed;
+        self.records_created += page.records_created;
+        self.records_updated += page.records_updated;
+        self.records_unchanged += page.records_unchanged;
+        self.conflicts += page.conflicts;
+        self.errors += page.errors;
+        self.updated_at = Utc::now();
+    }
+
+    /// Check whether the records of a mapping have been matched
+    pub fn is_matched(&self, mapping_key: &str) -> bool {
+        self.matched.iter().any(|m| m == mapping_key)
+    }
+
+    /// Last Attio record ID whose pairs were applied, if the mapping is being matched
+    pub fn last_matched_id(&self, mapping_key: &str) -> Option<&str> {
+        self.match_cursor
+            .as_ref()
+            .filter(|c| c.object == mapping_key)
+            .and_then(|c| c.last_record_id.as_deref())
+    }
+
+    /// Record a page of pairs applied while matching a mapping
+    pub fn match_page_done(
+        &mut self,
+        mapping_key: &str,
+        last_attio_id: Option<String>,
+        page: &SyncResult,
+    ) {
+        let cursor = self
+            .match_cursor
+            .get_or_insert_with(|| ObjectCursor::new(mapping_key));
+        if cursor.object != mapping_key {
+            *cursor = ObjectCursor::new(mapping_key);
+        }
+        cursor.update(last_attio_id, page.records_linked + page.link_candidates);
+
+        self.records_linked += page.records_linked;
+        self.link_candidates += page.link_candidates;
+        self.errors += page.errors;
+        self.updated_at = Utc::now();
+    }
+
+    /// Mark the matching phase of a mapping as done
+    pub fn matching_done(&mut self, mapping_key: &str) {
+        self.match_cursor = None;
+        if !self.is_matched(mapping_key) {
+            self.matched.push(mapping_key.to_string());
+        }
+        self.updated_at = Utc::now();
+    }
+
+    /// Mark a source object as paged through completely
+    pub fn object_done(&mut self, object: &str) {
+        self.cursor = None;
+        if !self.is_completed(object) {
+            self.completed.push(object.to_string());
+        }
+        self.updated_at = Utc::now();
+    }
+
+    /// Counters accumulated across all runs of this full sync
+    pub fn result(&self) -> SyncResult {
+        SyncResult {
+            records_processed: self.records_processed,
+            records_created: self.records_created,
+            records_updated: self.records_updated,
+            records_unchanged: self.records_unchanged,
+            conflicts: self.conflicts,
+            errors: self.errors,
+            records_linked: self.records_linked,
+            link_candidates: self.link_candidates,
+            direction: self.direction,
+            ..SyncResult::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_progress() {
+        let mut checkpoint = FullSyncCheckpoint::new(SyncDirection::Bidirectional);
+        let page = SyncResult {
+            records_processed: 2,
+            records_created: 1,
+            ..SyncResult::default()
+        };
+
+        checkpoint.page_done("companies", Some("rec_2".to_string()), &page);
+        assert_eq!(checkpoint.last_record_id("companies"), Some("rec_2"));
+        assert_eq!(checkpoint.last_record_id("Account"), None);
+
+        checkpoint.object_done("companies");
+        assert!(checkpoint.is_completed("companies"));
+        assert_eq!(checkpoint.last_record_id("companies"), None);
+
+        checkpoint.page_done("Account", Some("001b".to_string()), &page);
+        let result = checkpoint.result();
+        assert_eq!(result.records_processed, 4);
+        assert_eq!(result.records_created, 2);
+        assert_eq!(result.direction, SyncDirection::Bidirectional);
+    }
+
+    #[test]
+    fn test_checkpoint_matching_progress() {
+        let mut checkpoint = FullSyncCheckpoint::new(SyncDirection::Bidirectional);
+        let page = SyncResult {
+            records_linked: 2,
+            link_candidates: 1,
+            ..SyncResult::default()
+        };
+
+        checkpoint.match_page_done("companies_Account", Some("rec_3".to_string()), &page);
+        assert_eq!(
+            checkpoint.last_matched_id("companies_Account"),
+            Some("rec_3")
+        );
+        assert_eq!(checkpoint.last_matched_id("people_Contact"), None);
+        assert!(!checkpoint.is_matched("companies_Account"));
+
+        checkpoint.matching_done("companies_Account");
+        assert!(checkpoint.is_matched("companies_Account"));
+        assert_eq!(checkpoint.last_matched_id("companies_Account"), None);
+        let result = checkpoint.result();
+        assert_eq!((result.records_linked, result.link_candidates), (2, 1));
+    }
+}
@@ -0,0 +1,187 @@
+//! Type-aware value equivalence for change and conflict detection.
+//!
+//! Attio and Salesforce format the same value differently (number precision,
+//! timestamp precision, phone punctuation, email case), so raw JSON equality
+//! reports changes that are not real.
+
+use crate::salesforce::types::SalesforceFieldType;
+use crate::transform::field::utils::{
+    is_empty, normalize_email, normalize_phone, to_boolean, to_number,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::Value;
+
+/// Relative tolerance for numeric fields
+pub const NUMBER_TOLERANCE: f64 = 1e-9;
+
+/// Absolute tolerance for currency fields (half a cent)
+pub const CURRENCY_TOLERANCE: f64 = 0.005;
+
+/// Check whether two values of a field are the same (missing, null and empty are the same)
+pub fn equivalent(field_type: &SalesforceFieldType, a: Option<&Value>, b: Option<&Value>) -> bool {
+    match (a.filter(|v| !is_empty(v)), b.filter(|v| !is_empty(v))) {
+        (None, None) => true,
+        (Some(a), Some(b)) => a == b || values_equivalent(field_type, a, b),
+        _ => false,
+    }
+}
+
+fn values_equivalent(field_type: &SalesforceFieldType, a: &Value, b: &Value) -> bool {
+    match field_type {
+        SalesforceFieldType::Email => same(normalize_email(a), normalize_email(b)),
+        SalesforceFieldType::Phone => same(normalize_phone(a), normalize_phone(b)),
+        SalesforceFieldType::Url => same(normalize_url(a), normalize_url(b)),
+        SalesforceFieldType::Boolean => same(to_boolean(a), to_boolean(b)),
+        SalesforceFieldType::Int => {
+            same(to_number(a).map(f64::round), to_number(b).map(f64::round))
+        }
+        SalesforceFieldType::Double => numbers_equal(a, b, |x, y| {
+            (x - y).abs() <= NUMBER_TOLERANCE * x.abs().max(y.abs()).max(1.0)
+        }),
+        SalesforceFieldType::Currency => {
+            numbers_equal(a, b, |x, y| (x - y).abs() <= CURRENCY_TOLERANCE)
+        }
+        SalesforceFieldType::Date => same(
+            parse_timestamp(a).map(|t| t.date_naive()),
+            parse_timestamp(b).map(|t| t.date_naive()),
+        ),
+        // Salesforce stores milliseconds, Attio nanoseconds; compare to the second
+        SalesforceFieldType::DateTime => same(
+            parse_timestamp(a).map(|t| t.timestamp()),
+            parse_timestamp(b).map(|t| t.timestamp()),
+        ),
+        _ => match (a, b) {
+            (Value::String(a), Value::String(b)) => a.trim() == b.trim(),
+            (Value::Number(_), Value::Number(_)) => {
+                values_equivalent(&SalesforceFieldType::Double, a, b)
+            }
+            _ => false,
+        },
+    }
+}
+
+fn same<T: PartialEq>(a: Option<T>, b: Option<T>) -> bool {
+    a.is_some() && a == b
+}
+
+fn numbers_equal(a: &Value, b: &Value, close: impl Fn(f64, f64) -> bool) -> bool {
+    match (to_number(a), to_number(b)) {
+        (Some(a), Some(b)) => close(a, b),
+        _ => false,
+    }
+}
+
+/// Parse an RFC 3339 / Salesforce timestamp or a plain date (as midnight UTC)
+fn parse_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    let s = value.as_str()?.trim();
+    DateTime::parse_from_rfc3339(s)
+        .or_else(|_| DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .map(|t| t.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|t| t.and_utc())
+        })
+}
+
+/// Lowercase a URL and drop the scheme, `www.` and trailing slash
+fn normalize_url(value: &Value) -> Option<String> {
+    let url = value.as_str()?.trim().to_lowercase();
+    let url = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(&url);
+    let url = url.strip_prefix("www.").unwrap_or(url);
+    Some(url.trim_end_matches('/').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn eq(field_type: SalesforceFieldType, a: Value, b: Value) -> bool {
+        equivalent(&field_type, Some(&a), Some(&b))
+    }
+
+    #[test]
+    fn test_equivalent_formats() {
+        assert!(eq(
+            SalesforceFieldType::Email,
+            json!("ACME@x.com"),
+            json!("acme@x.com")
+        ));
+        assert!(eq(
+            SalesforceFieldType::Phone,
+            json!("+1 (555) 123"),
+            json!("+1555123")
+        ));
+        assert!(eq(
+            SalesforceFieldType::Currency,
+            json!(1000),
+            json!(1000.0)
+        ));
+        assert!(eq(
+            SalesforceFieldType::Currency,
+            json!("1000.001"),
+            json!(1000)
+        ));
+        assert!(eq(
+            SalesforceFieldType::Date,
+            json!("2024-01-01"),
+            json!("2024-01-01T00:00:00.000+0000")
+        ));
+        assert!(eq(
+            SalesforceFieldType::DateTime,
+            json!("2024-01-01T10:00:00.123456789Z"),
+            json!("2024-01-01T10:00:00.000+0000")
+        ));
+        assert!(eq(
+            SalesforceFieldType::Url,
+            json!("https://www.acme.com/"),
+            json!("acme.com")
+        ));
+        assert!(eq(
+            SalesforceFieldType::String,
+            json!("Acme "),
+            json!("Acme")
+        ));
+        assert!(eq(SalesforceFieldType::String, json!(1000), json!(1000.0)));
+        assert!(equivalent(
+            &SalesforceFieldType::String,
+            Some(&json!("")),
+            None
+        ));
+    }
+
+    #[test]
+    fn test_real_changes_are_detected() {
+        assert!(!eq(
+            SalesforceFieldType::Email,
+            json!("a@x.com"),
+            json!("b@x.com")
+        ));
+        assert!(!eq(
+            SalesforceFieldType::Currency,
+            json!(1000),
+            json!(1000.5)
+        ));
+        assert!(!eq(
+            SalesforceFieldType::Date,
+            json!("2024-01-01"),
+            json!("2024-01-02")
+        ));
+        assert!(!eq(
+            SalesforceFieldType::String,
+            json!("Acme"),
+            json!("acme")
+        ));
+        assert!(!equivalent(
+            &SalesforceFieldType::String,
+            Some(&json!("Acme")),
+            None
+        ));
+    }
+}
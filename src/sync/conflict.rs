@@ -1,10 +1,13 @@
 //! Conflict detection and resolution.
 
+use crate::config::mappings::{FieldMapping, ObjectMapping};
 use crate::config::ConflictResolution;
 use crate::error::{Error, Result};
+use crate::sync::merge::{converged, diverged};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 
 /// A record with conflict between Attio and Salesforce
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,6 +82,48 @@ pub enum ConflictStatus {
     Skipped,
 }
 
+/// Manual choice for one conflicting field
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldChoice {
+    /// Keep the Attio value
+    Attio,
+    /// Keep the Salesforce value
+    Salesforce,
+    /// Write this value (in Attio's format) to Attio, and its transformed form to Salesforce
+    Custom(Value),
+}
+
+/// Manual resolution of a conflict submitted by a user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManualResolution {
+    /// Choice per conflicting field (keyed by Attio field)
+    pub fields: HashMap<String, FieldChoice>,
+
+    /// Optional note stored with the resolution
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Criteria for listing stored conflicts
+#[derive(Debug, Clone, Default)]
+pub struct ConflictFilter {
+    /// Attio or Salesforce object type
+    pub object: Option<String>,
+
+    /// Attio or Salesforce record ID
+    pub record_id: Option<String>,
+
+    /// Resolution status
+    pub status: Option<ConflictStatus>,
+
+    /// Only conflicts detected before this time
+    pub detected_before: Option<DateTime<Utc>>,
+
+    /// Only conflicts detected after this time
+    pub detected_after: Option<DateTime<Utc>>,
+}
+
 /// Result of conflict resolution
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConflictResolutionResult {
@@ -105,21 +150,95 @@ pub enum ConflictWinner {
     Neither,
 }
 
+/// Per-field outcome of merging a conflict
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDecision {
+    /// Field name (Attio side)
+    pub attio_field: String,
+
+    /// Strategy that decided the field
+    pub strategy: ConflictResolution,
+
+    /// Winning side, or `None` if the field needs manual resolution
+    pub winner: Option<ConflictWinner>,
+}
+
+/// Result of merging a conflict field by field
+#[derive(Debug, Clone)]
+pub struct MergedConflict {
+    /// Attio data with the Salesforce-won fields applied
+    pub values: Value,
+
+    /// Decision for each conflicting field
+    pub decisions: Vec<FieldDecision>,
+
+    /// Overall resolution, or `None` if any field needs manual resolution
+    pub resolution: Option<ConflictResolutionResult>,
+}
+
 /// Conflict resolver
 pub struct ConflictResolver {
     strategy: ConflictResolution,
+    field_strategies: HashMap<String, ConflictResolution>,
 }
 
 impl ConflictResolver {
     /// Create a new conflict resolver
     pub fn new(strategy: ConflictResolution) -> Self {
-        Self { strategy }
+        Self {
+            strategy,
+            field_strategies: HashMap::new(),
+        }
     }
 
-    /// Check if two values conflict
-    pub fn detect_conflict(&self, attio_value: &Value, sf_value: &Value) -> bool {
-        // Simple equality check - could be enhanced with fuzzy matching
-        attio_value != sf_value
+    /// Resolver applying an object mapping's overrides on top of this one's strategy
+    pub fn for_mapping(&self, mapping: &ObjectMapping) -> Self {
+        Self {
+            strategy: mapping.conflict_resolution.unwrap_or(self.strategy),
+            field_strategies: mapping
+                .fields
+                .iter()
+                .filter_map(|f| Some((f.attio_field.clone(), f.conflict_resolution?)))
+                .collect(),
+        }
+    }
+
+    /// Effective strategy for a field
+    pub fn field_strategy(&self, attio_field: &str) -> ConflictResolution {
+        self.field_strategies
+            .get(attio_field)
+            .copied()
+            .unwrap_or(self.strategy)
+    }
+
+    /// Pick the winner of a single field (`None` if it needs manual resolution)
+    pub fn resolve_field(&self, field: &FieldConflict) -> Option<ConflictWinner> {
+        match self.field_strategy(&field.attio_field) {
+            ConflictResolution::LastWrite => {
+                match (field.attio_modified_at, field.salesforce_modified_at) {
+                    (Some(a), Some(s)) if s > a => Some(ConflictWinner::Salesforce),
+                    (None, Some(_)) => Some(ConflictWinner::Salesforce),
+                    _ => Some(ConflictWinner::Attio),
+                }
+            }
+            ConflictResolution::AttioWins => Some(ConflictWinner::Attio),
+            ConflictResolution::SalesforceWins => Some(ConflictWinner::Salesforce),
+            ConflictResolution::Manual => None,
+        }
+    }
+
+    /// Check if a field conflicts, i.e. both sides diverged from the base to different values
+    pub fn detect_conflict(
+        &self,
+        field: &FieldMapping,
+        attio_base: Option<&Value>,
+        attio_value: Option<&Value>,
+        sf_base: Option<&Value>,
+        sf_value: Option<&Value>,
+    ) -> bool {
+        diverged(field, attio_base, attio_value)
+            && diverged(field, sf_base, sf_value)
+            && !converged(field, attio_value, sf_value)
     }
 
     /// Resolve a conflict based on strategy
@@ -177,36 +296,169 @@ impl ConflictResolver {
         })
     }
 
-    /// Get merged values (field-level last-write-wins)
-    pub fn merge_values(&self, conflict: &ConflictRecord) -> Value {
+    /// Merge a conflict field by field, each with its own effective strategy
+    pub fn merge_values(&self, conflict: &ConflictRecord) -> MergedConflict {
         let mut merged = conflict.attio_data.clone();
-
-        if let (Some(attio_obj), Some(sf_obj)) =
-            (merged.as_object_mut(), conflict.salesforce_data.as_object())
-        {
-            for field_conflict in &conflict.conflicting_fields {
-                // Determine winner for this specific field
-                let use_sf = match (
-                    field_conflict.attio_modified_at,
-                    field_conflict.salesforce_modified_at,
-                ) {
-                    (Some(a), Some(s)) => s > a,
-                    (None, Some(_)) => true,
-                    _ => false,
-                };
-
-                if use_sf {
-                    if let Some(sf_value) = sf_obj.get(&field_conflict.salesforce_field) {
-                        attio_obj.insert(
-                            field_conflict.attio_field.clone(),
-                            sf_value.clone(),
-                        );
-                    }
+        let mut decisions = Vec::new();
+
+        for field_conflict in &conflict.conflicting_fields {
+            let winner = self.resolve_field(field_conflict);
+            if winner == Some(ConflictWinner::Salesforce) {
+                if let Some(attio_obj) = merged.as_object_mut() {
+                    attio_obj.insert(
+                        field_conflict.attio_field.clone(),
+                        field_conflict.salesforce_value.clone(),
+                    );
                 }
             }
+            decisions.push(FieldDecision {
+                attio_field: field_conflict.attio_field.clone(),
+                strategy: self.field_strategy(&field_conflict.attio_field),
+                winner,
+            });
+        }
+
+        let resolution = decisions
+            .iter()
+            .map(|d| d.winner.clone())
+            .collect::<Option<Vec<_>>>()
+            .map(|winners| ConflictResolutionResult {
+                winner: if winners.iter().all(|w| *w == ConflictWinner::Attio) {
+                    ConflictWinner::Attio
+                } else if winners.iter().all(|w| *w == ConflictWinner::Salesforce) {
+                    ConflictWinner::Salesforce
+                } else {
+                    ConflictWinner::Merged
+                },
+                resolved_at: Utc::now(),
+                resolved_by: "system".to_string(),
+                notes: Some(decision_notes(&decisions)),
+            });
+
+        MergedConflict {
+            values: merged,
+            decisions,
+            resolution,
         }
+    }
+}
+
+/// Describe which strategy decided each field, e.g. "Amount: salesforce_wins → salesforce"
+fn decision_notes(decisions: &[FieldDecision]) -> String {
+    decisions
+        .iter()
+        .map(|d| {
+            let outcome = match &d.winner {
+                Some(ConflictWinner::Attio) => "attio",
+                Some(ConflictWinner::Salesforce) => "salesforce",
+                Some(ConflictWinner::Merged) => "merged",
+                Some(ConflictWinner::Neither) | None => "unresolved",
+            };
+            format!("{}: {} → {}", d.attio_field, d.strategy.as_str(), outcome)
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl std::str::FromStr for ConflictStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "pending" => Ok(Self::Pending),
+            "auto_resolved" => Ok(Self::AutoResolved),
+            "manually_resolved" => Ok(Self::ManuallyResolved),
+            "skipped" => Ok(Self::Skipped),
+            _ => Err(Error::validation(format!("Invalid conflict status: {}", s))),
+        }
+    }
+}
+
+impl std::str::FromStr for FieldChoice {
+    type Err = Error;
+
+    /// Parse `attio`, `salesforce`, or `custom:<value>` (JSON, or plain text)
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once(':') {
+            Some(("custom", value)) => Ok(Self::Custom(
+                serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string())),
+            )),
+            _ => match s.to_lowercase().as_str() {
+                "attio" => Ok(Self::Attio),
+                "salesforce" | "sf" => Ok(Self::Salesforce),
+                _ => Err(Error::validation(format!("Invalid field choice: {}", s))),
+            },
+        }
+    }
+}
+
+impl ManualResolution {
+    /// Overall winner: one side if every field chose it, otherwise merged
+    pub fn winner(&self) -> ConflictWinner {
+        let all = |choice: FieldChoice| self.fields.values().all(|c| *c == choice);
+        if all(FieldChoice::Attio) {
+            ConflictWinner::Attio
+        } else if all(FieldChoice::Salesforce) {
+            ConflictWinner::Salesforce
+        } else {
+            ConflictWinner::Merged
+        }
+    }
+
+    /// Check that every conflicting field, and nothing else, has a choice
+    pub fn validate(&self, conflict: &ConflictRecord) -> Result<()> {
+        let missing: Vec<_> = conflict
+            .conflicting_fields
+            .iter()
+            .map(|f| f.attio_field.as_str())
+            .filter(|field| !self.fields.contains_key(*field))
+            .collect();
+        if !missing.is_empty() {
+            return Err(Error::validation(format!(
+                "No choice for conflicting fields: {}",
+                missing.join(", ")
+            )));
+        }
+
+        let unknown: Vec<_> = self
+            .fields
+            .keys()
+            .filter(|field| {
+                !conflict
+                    .conflicting_fields
+                    .iter()
+                    .any(|f| f.attio_field == **field)
+            })
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            return Err(Error::validation(format!(
+                "Fields are not in conflict: {}",
+                unknown.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+}
 
-        merged
+impl ConflictFilter {
+    /// Check whether a conflict matches every set criterion
+    pub fn matches(&self, conflict: &ConflictRecord) -> bool {
+        self.object.as_ref().is_none_or(|object| {
+            conflict.attio_object == *object || conflict.salesforce_object == *object
+        }) && self.record_id.as_ref().is_none_or(|id| {
+            conflict.attio_record_id == *id || conflict.salesforce_record_id == *id
+        }) && self
+            .status
+            .as_ref()
+            .is_none_or(|status| conflict.status == *status)
+            && self
+                .detected_before
+                .is_none_or(|before| conflict.detected_at < before)
+            && self
+                .detected_after
+                .is_none_or(|after| conflict.detected_at >= after)
     }
 }
 
@@ -245,6 +497,12 @@ impl ConflictRecord {
         self.status = ConflictStatus::AutoResolved;
         self.resolution = Some(resolution);
     }
+
+    /// Mark as resolved by a user
+    pub fn resolve_manually(&mut self, resolution: ConflictResolutionResult) {
+        self.status = ConflictStatus::ManuallyResolved;
+        self.resolution = Some(resolution);
+    }
 }
 
 #[cfg(test)]
@@ -254,15 +512,40 @@ mod tests {
     #[test]
     fn test_detect_conflict() {
         let resolver = ConflictResolver::new(ConflictResolution::LastWrite);
+        let field = FieldMapping {
+            attio_field: "email_addresses[0].email_address".to_string(),
+            salesforce_field: "Email".to_string(),
+            transform: Default::default(),
+            required: false,
+            direction: Default::default(),
+            field_type: None,
+            conflict_resolution: None,
+        };
+        let base = serde_json::json!("base@x.com");
 
         assert!(resolver.detect_conflict(
-            &serde_json::json!("value1"),
-            &serde_json::json!("value2")
+            &field,
+            Some(&base),
+            Some(&serde_json::json!("one@x.com")),
+            Some(&base),
+            Some(&serde_json::json!("two@x.com"))
+        ));
+
+        assert!(!resolver.detect_conflict(
+            &field,
+            Some(&base),
+            Some(&serde_json::json!("one@x.com")),
+            Some(&base),
+            Some(&serde_json::json!("BASE@x.com "))
         ));
 
+        // Both sides changed to the same address
         assert!(!resolver.detect_conflict(
-            &serde_json::json!("same"),
-            &serde_json::json!("same")
+            &field,
+            Some(&base),
+            Some(&serde_json::json!("new@x.com")),
+            Some(&base),
+            Some(&serde_json::json!("New@X.com"))
         ));
     }
 
@@ -281,4 +564,185 @@ mod tests {
         let result = resolver.resolve(&conflict).unwrap();
         assert_eq!(result.winner, ConflictWinner::Attio);
     }
+
+    #[test]
+    fn test_merge_values_per_field_strategies() {
+        let mut mapping = ObjectMapping::from_defaults("deals", "Opportunity").unwrap();
+        for field in &mut mapping.fields {
+            if field.salesforce_field == "Amount" {
+                field.conflict_resolution = Some(ConflictResolution::SalesforceWins);
+            }
+        }
+        mapping.conflict_resolution = Some(ConflictResolution::AttioWins);
+        let resolver = ConflictResolver::new(ConflictResolution::Manual).for_mapping(&mapping);
+
+        let mut conflict = ConflictRecord::new(
+            "deals",
+            "rec_1",
+            "Opportunity",
+            "006xxx",
+            serde_json::json!({"name": "Big deal", "value.value": 100}),
+            serde_json::json!({"Name": "Bigger deal", "Amount": 200}),
+        );
+        for (attio, sf, attio_value, sf_value) in [
+            ("name", "Name", "Big deal", serde_json::json!("Bigger deal")),
+            ("value.value", "Amount", "100", serde_json::json!(200)),
+        ] {
+            conflict.add_field_conflict(FieldConflict {
+                attio_field: attio.to_string(),
+                salesforce_field: sf.to_string(),
+                attio_value: serde_json::json!(attio_value),
+                salesforce_value: sf_value,
+                attio_modified_at: None,
+                salesforce_modified_at: None,
+            });
+        }
+
+        let merged = resolver.merge_values(&conflict);
+        assert_eq!(merged.values["name"], "Big deal");
+        assert_eq!(merged.values["value.value"], 200);
+        assert_eq!(merged.decisions[1].winner, Some(ConflictWinner::Salesforce));
+
+        let resolution = merged.resolution.unwrap();
+        assert_eq!(resolution.winner, ConflictWinner::Merged);
+        assert_eq!(
+            resolution.notes.as_deref(),
+            Some("name: attio_wins → attio; value.value: salesforce_wins → salesforce")
+        );
+    }
+
+    #[test]
+    fn test_manual_field_override_leaves_conflict_pending() {
+        let mut mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+        for field in &mut mapping.fields {
+            if field.salesforce_field == "Description" {
+                field.conflict_resolution = Some(ConflictResolution::Manual);
+            }
+        }
+        let resolver = ConflictResolver::new(ConflictResolution::LastWrite).for_mapping(&mapping);
+        assert_eq!(
+            resolver.field_strategy("name"),
+            ConflictResolution::LastWrite
+        );
+        assert_eq!(
+            resolver.field_strategy("description"),
+            ConflictResolution::Manual
+        );
+
+        let now = Utc::now();
+        let mut conflict = ConflictRecord::new(
+            "companies",
+            "rec_1",
+            "Account",
+            "001xxx",
+            serde_json::json!({}),
+            serde_json::json!({}),
+        );
+        for (attio, sf) in [("name", "Name"), ("description", "Description")] {
+            conflict.add_field_conflict(FieldConflict {
+                attio_field: attio.to_string(),
+                salesforce_field: sf.to_string(),
+                attio_value: serde_json::json!("a"),
+                salesforce_value: serde_json::json!("b"),
+                attio_modified_at: Some(now - chrono::Duration::minutes(5)),
+                salesforce_modified_at: Some(now),
+            });
+        }
+
+        // The newer Salesforce value wins the name; the description waits for a person
+        let merged = resolver.merge_values(&conflict);
+        assert_eq!(merged.decisions[0].winner, Some(ConflictWinner::Salesforce));
+        assert_eq!(merged.decisions[1].winner, None);
+        assert!(merged.resolution.is_none());
+    }
+
+    #[test]
+    fn test_last_write_without_timestamps() {
+        let resolver = ConflictResolver::new(ConflictResolution::LastWrite);
+        let mut field = FieldConflict {
+            attio_field: "name".to_string(),
+            salesforce_field: "Name".to_string(),
+            attio_value: serde_json::json!("a"),
+            salesforce_value: serde_json::json!("b"),
+            attio_modified_at: None,
+            salesforce_modified_at: None,
+        };
+        assert_eq!(resolver.resolve_field(&field), Some(ConflictWinner::Attio));
+
+        field.salesforce_modified_at = Some(Utc::now());
+        assert_eq!(
+            resolver.resolve_field(&field),
+            Some(ConflictWinner::Salesforce)
+        );
+
+        // A mapping without overrides keeps the global strategy
+        let mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+        let resolver = ConflictResolver::new(ConflictResolution::AttioWins).for_mapping(&mapping);
+        assert_eq!(resolver.resolve_field(&field), Some(ConflictWinner::Attio));
+    }
+
+    #[test]
+    fn test_conflict_filter() {
+        let conflict = ConflictRecord::new(
+            "companies",
+            "rec_1",
+            "Account",
+            "001xxx",
+            serde_json::json!({}),
+            serde_json::json!({}),
+        );
+
+        let filter = ConflictFilter {
+            object: Some("Account".to_string()),
+            status: Some("pending".parse().unwrap()),
+            ..ConflictFilter::default()
+        };
+        assert!(filter.matches(&conflict));
+
+        let stale = ConflictFilter {
+            detected_before: Some(conflict.detected_at - chrono::Duration::days(7)),
+            ..ConflictFilter::default()
+        };
+        assert!(!stale.matches(&conflict));
+    }
+
+    #[test]
+    fn test_manual_resolution() {
+        let mut conflict = ConflictRecord::new(
+            "companies",
+            "rec_1",
+            "Account",
+            "001xxx",
+            serde_json::json!({}),
+            serde_json::json!({}),
+        );
+        for field in ["name", "description"] {
+            conflict.add_field_conflict(FieldConflict {
+                attio_field: field.to_string(),
+                salesforce_field: field.to_string(),
+                attio_value: serde_json::json!("a"),
+                salesforce_value: serde_json::json!("b"),
+                attio_modified_at: None,
+                salesforce_modified_at: None,
+            });
+        }
+
+        let resolution: ManualResolution = serde_json::from_value(serde_json::json!({
+            "fields": {"name": "attio", "description": {"custom": "Agreed text"}},
+            "note": "Checked with account owner"
+        }))
+        .unwrap();
+        assert!(resolution.validate(&conflict).is_ok());
+        assert_eq!(resolution.winner(), ConflictWinner::Merged);
+        assert_eq!(
+            "custom:Agreed text".parse::<FieldChoice>().unwrap(),
+            resolution.fields["description"]
+        );
+
+        let partial = ManualResolution {
+            fields: HashMap::from([("name".to_string(), FieldChoice::Salesforce)]),
+            note: None,
+        };
+        assert!(partial.validate(&conflict).is_err());
+    }
 }
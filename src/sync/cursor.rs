@@ -1,6 +1,6 @@
 //! Sync cursor management for incremental sync.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Sync cursor tracking the last successful sync point
@@ -25,11 +25,19 @@ pub struct ObjectCursor {
     /// Last synced timestamp for this object
     pub last_sync: DateTime<Utc>,
 
-    /// Last synced record ID (for pagination)
+    /// Last synced record ID (for pagination, and to break ties between equal timestamps)
     pub last_record_id: Option<String>,
 
+    /// Latest modification timestamp (`updated_at`/`SystemModstamp`) synced so far
+    #[serde(default)]
+    pub last_modified: Option<DateTime<Utc>>,
+
     /// Number of records synced in last batch
     pub last_batch_count: u64,
+
+    /// Failed sync attempts of records holding the cursor back, by record ID
+    #[serde(default)]
+    pub failed_attempts: std::collections::HashMap<String, u32>,
 }
 
 impl SyncCursor {
@@ -85,8 +93,50 @@ impl ObjectCursor {
             object: object.into(),
             last_sync: Utc::now(),
             last_record_id: None,
+            last_modified: None,
             last_batch_count: 0,
+            failed_attempts: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Create an object cursor positioned at a modification timestamp
+    pub fn at(object: impl Into<String>, last_modified: DateTime<Utc>) -> Self {
+        Self {
+            last_modified: Some(last_modified),
+            ..Self::new(object)
+        }
+    }
+
+    /// Timestamp to query changes from, re-checking `overlap` before the position
+    pub fn since(&self, overlap: Duration) -> Option<DateTime<Utc>> {
+        self.last_modified.map(|t| t - overlap)
+    }
+
+    /// Move the position forward to a committed record, ordered by timestamp then record ID
+    pub fn advance(&mut self, modified: DateTime<Utc>, record_id: &str) {
+        let current = self
+            .last_modified
+            .map(|t| (t, self.last_record_id.as_deref().unwrap_or_default()));
+        if current.is_some_and(|position| position >= (modified, record_id)) {
+            return;
         }
+        self.last_modified = Some(modified);
+        self.last_record_id = Some(record_id.to_string());
+    }
+
+    /// Count a failed attempt to sync a record, returning the attempts so far
+    pub fn record_failure(&mut self, record_id: &str) -> u32 {
+        let attempts = self
+            .failed_attempts
+            .entry(record_id.to_string())
+            .or_default();
+        *attempts += 1;
+        *attempts
+    }
+
+    /// Forget the failed attempts of a record that was synced or given up on
+    pub fn clear_failures(&mut self, record_id: &str) {
+        self.failed_attempts.remove(record_id);
     }
 
     /// Update with sync results
@@ -118,4 +168,42 @@ mod tests {
         assert!(cursor.get_object_cursor("companies").is_some());
         assert!(cursor.get_object_cursor("people").is_none());
     }
+
+    #[test]
+    fn test_object_cursor_advance() {
+        let t0 = Utc::now();
+        let mut cursor = ObjectCursor::at("Account", t0);
+        assert_eq!(
+            cursor.since(Duration::seconds(60)),
+            Some(t0 - Duration::seconds(60))
+        );
+
+        cursor.advance(t0, "001b");
+        cursor.advance(t0, "001a");
+        assert_eq!(cursor.last_record_id.as_deref(), Some("001b"));
+
+        cursor.advance(t0 - Duration::seconds(1), "001z");
+        assert_eq!(cursor.last_modified, Some(t0));
+
+        cursor.advance(t0 + Duration::seconds(1), "001a");
+        assert_eq!(cursor.last_modified, Some(t0 + Duration::seconds(1)));
+        assert_eq!(cursor.last_record_id.as_deref(), Some("001a"));
+    }
+
+    #[test]
+    fn test_failed_attempts() {
+        let mut cursor = ObjectCursor::new("Account");
+        assert_eq!(cursor.record_failure("001a"), 1);
+        assert_eq!(cursor.record_failure("001a"), 2);
+        assert_eq!(cursor.record_failure("001b"), 1);
+
+        cursor.clear_failures("001a");
+        assert_eq!(cursor.record_failure("001a"), 1);
+
+        // Cursors saved before attempts were tracked have none
+        let json = r#"{"object":"Account","last_sync":"2024-01-01T00:00:00Z",
+            "last_record_id":null,"last_batch_count":0}"#;
+        let cursor: ObjectCursor = serde_json::from_str(json).unwrap();
+        assert!(cursor.failed_attempts.is_empty());
+    }
 }
@@ -0,0 +1,197 @@
+//! Echo suppression so the bridge does not sync its own writes back.
+//!
+//! Every write we make to one system shows up as a change in that system. Webhooks
+//! carry the actor, so those echoes are dropped up front; for polling paths we keep
+//! a short-lived fingerprint of what we wrote and skip records that still match it
+//! and whose other mapped fields still hold their last-synced values.
+
+use crate::attio::webhooks::AttioActor;
+use crate::salesforce::types::SalesforceRecord;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// How long a write fingerprint suppresses echoes
+pub const FINGERPRINT_TTL_SECS: i64 = 600;
+
+/// Attio actor type for API tokens
+const API_TOKEN_ACTOR: &str = "api-token";
+
+/// Salesforce field holding the last modifying user
+const LAST_MODIFIED_BY_FIELD: &str = "LastModifiedById";
+
+/// Which system a record lives in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum System {
+    /// Attio
+    Attio,
+    /// Salesforce
+    Salesforce,
+}
+
+/// Fingerprint of values the bridge recently wrote to a record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WriteFingerprint {
+    /// System the record lives in
+    pub system: System,
+
+    /// Object type (e.g., "companies" or "Account")
+    pub object: String,
+
+    /// Record ID
+    pub record_id: String,
+
+    /// Fields that were written (paths in the record's own format)
+    pub fields: Vec<String>,
+
+    /// Hash of the written field values
+    pub hash: String,
+
+    /// When the fingerprint stops suppressing changes
+    pub expires_at: DateTime<Utc>,
+}
+
+impl System {
+    /// Storage name of the system
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Attio => "attio",
+            Self::Salesforce => "salesforce",
+        }
+    }
+}
+
+impl WriteFingerprint {
+    /// Fingerprint a write of `fields` → `values` (one value per field, in order)
+    pub fn new(
+        system: System,
+        object: impl Into<String>,
+        record_id: impl Into<String>,
+        fields: Vec<String>,
+        values: &[Value],
+    ) -> Self {
+        Self {
+            system,
+            object: object.into(),
+            record_id: record_id.into(),
+            hash: content_hash(&Value::Array(values.to_vec())),
+            fields,
+            expires_at: Utc::now() + Duration::seconds(FINGERPRINT_TTL_SECS),
+        }
+    }
+
+    /// Check whether the current field values are exactly what we wrote
+    pub fn matches(&self, values: &[Value], now: DateTime<Utc>) -> bool {
+        self.expires_at > now && self.hash == content_hash(&Value::Array(values.to_vec()))
+    }
+}
+
+/// Stable SHA-256 hash of a JSON value (object keys are sorted)
+pub fn content_hash(value: &Value) -> String {
+    let canonical = canonicalize(value).to_string();
+    format!("{:x}", Sha256::digest(canonical.as_bytes()))
+}
+
+fn canonicalize(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k.clone(), canonicalize(v)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.iter().map(canonicalize).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Check whether an Attio event was triggered by the bridge's own API token
+pub fn is_own_attio_actor(actor: Option<&AttioActor>, api_token_id: Option<&str>) -> bool {
+    match (actor, api_token_id) {
+        (Some(actor), Some(token_id)) => {
+            actor.actor_type == API_TOKEN_ACTOR && actor.id.as_deref() == Some(token_id)
+        }
+        _ => false,
+    }
+}
+
+/// Check whether a Salesforce user ID is the bridge's integration user
+pub fn is_integration_user(user_id: Option<&str>, integration_user_id: Option<&str>) -> bool {
+    match (user_id, integration_user_id) {
+        (Some(user_id), Some(integration_user_id)) => {
+            same_salesforce_id(user_id, integration_user_id)
+        }
+        _ => false,
+    }
+}
+
+/// Check whether a Salesforce record was last modified by the integration user
+pub fn modified_by_integration_user(
+    record: &SalesforceRecord,
+    integration_user_id: Option<&str>,
+) -> bool {
+    let last_modified_by = record
+        .fields
+        .get(LAST_MODIFIED_BY_FIELD)
+        .and_then(Value::as_str);
+    is_integration_user(last_modified_by, integration_user_id)
+}
+
+/// Compare Salesforce IDs, treating 15- and 18-character forms as equal
+fn same_salesforce_id(a: &str, b: &str) -> bool {
+    match (a.get(..15), b.get(..15)) {
+        (Some(a15), Some(b15)) => a15 == b15,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_fingerprint_matches() {
+        let fingerprint = WriteFingerprint::new(
+            System::Salesforce,
+            "Account",
+            "001xxx",
+            vec!["Name".to_string(), "Website".to_string()],
+            &[json!("Acme"), json!({"b": 1, "a": 2})],
+        );
+
+        let now = Utc::now();
+        assert!(fingerprint.matches(&[json!("Acme"), json!({"a": 2, "b": 1})], now));
+        assert!(!fingerprint.matches(&[json!("Acme Inc"), json!({"a": 2, "b": 1})], now));
+        assert!(!fingerprint.matches(
+            &[json!("Acme"), json!({"a": 2, "b": 1})],
+            now + Duration::seconds(FINGERPRINT_TTL_SECS + 1)
+        ));
+    }
+
+    #[test]
+    fn test_own_actor_detection() {
+        let actor = AttioActor {
+            actor_type: "api-token".to_string(),
+            id: Some("tok_bridge".to_string()),
+        };
+        assert!(is_own_attio_actor(Some(&actor), Some("tok_bridge")));
+        assert!(!is_own_attio_actor(Some(&actor), Some("tok_other")));
+        assert!(!is_own_attio_actor(Some(&actor), None));
+
+        assert!(is_integration_user(
+            Some("005000000000001AAA"),
+            Some("005000000000001")
+        ));
+        assert!(!is_integration_user(
+            Some("005000000000002AAA"),
+            Some("005000000000001AAA")
+        ));
+    }
+}
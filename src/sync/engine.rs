@@ -1,12 +1,43 @@
 //! Core sync engine implementation.
 
+use crate::attio::webhooks::{AttioEventType, AttioWebhookEvent};
 use crate::attio::AttioClient;
-use crate::config::{Config, ConflictResolution, SyncDirection};
+use crate::config::mappings::{FieldMapping, FieldSyncDirection};
+use crate::config::{Config, DeletePolicy, MatchRule, ObjectMapping, SyncDirection, TransformType};
 use crate::error::{Error, Result};
+use crate::salesforce::webhooks::{SalesforceChangeType, SalesforceWebhookEvent};
 use crate::salesforce::SalesforceClient;
-use crate::sync::conflict::ConflictResolver;
-use crate::sync::cursor::SyncCursor;
-use crate::transform::TransformPipeline;
+use crate::storage::{MemoryStorage, Page, Storage, DEFAULT_PAGE_SIZE};
+use crate::sync::audit::{diff_fields, AuditAction, AuditContext, AuditEntry};
+use crate::sync::checkpoint::{FullSyncCheckpoint, FULL_SYNC_CHECKPOINT_KEY};
+use crate::sync::conflict::{
+    ConflictFilter, ConflictRecord, ConflictResolutionResult, ConflictResolver, ConflictStatus,
+    ConflictWinner, FieldChoice, FieldConflict, ManualResolution,
+};
+use crate::sync::cursor::{ObjectCursor, SyncCursor};
+use crate::sync::direction::SyncDirectionHandler;
+use crate::sync::echo::{
+    content_hash, is_integration_user, is_own_attio_actor, modified_by_integration_user, System,
+    WriteFingerprint,
+};
+use crate::sync::feed::{ApiChangeFeed, ChangeFeed};
+use crate::sync::history::{SyncRun, SyncTrigger};
+use crate::sync::lock::{Lock, LockLease, MemoryLock, DEFAULT_LOCK_TTL_SECS};
+use crate::sync::matching::{
+    match_indexes, CandidateFilter, CandidateStatus, LinkCandidate, MatchIndex, MatchPair,
+};
+use crate::sync::merge::{
+    diverged, field_values, plan_merge, BaseSnapshot, FieldValues, MergeContext, MergeStrategy,
+};
+use crate::sync::pending::{PendingReference, ReferenceCheck};
+use crate::sync::plan::{PlannedChange, SyncMode, SyncPlan};
+use crate::sync::queue::{FailedMessage, INCREMENTAL_SYNC_QUEUE};
+use crate::transform::reference::IdMapping;
+use crate::transform::{attio_reference_ids, ReferenceResolver, TransformPipeline};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 
 /// Main sync engine orchestrating bidirectional sync
 pub struct SyncEngine {
@@ -15,6 +46,16 @@ pub struct SyncEngine {
     salesforce: SalesforceClient,
     transform: TransformPipeline,
     conflict_resolver: ConflictResolver,
+    storage: Rc<dyn Storage>,
+    lock: Rc<dyn Lock>,
+    feed: Rc<dyn ChangeFeed>,
+    merge_strategies: HashMap<String, Rc<dyn MergeStrategy>>,
+    trigger: SyncTrigger,
+    last_run: Option<SyncRun>,
+    audit: AuditContext,
+    mode: SyncMode,
+    holder_id: String,
+    reference_checks: Vec<ReferenceCheck>,
 }
 
 /// Result of a sync operation
@@ -29,17 +70,38 @@ pub struct SyncResult {
     /// Number of records updated
     pub records_updated: u64,
 
+    /// Number of linked records deleted
+    pub records_deleted: u64,
+
     /// Number of records with conflicts
     pub conflicts: u64,
 
     /// Number of errors
     pub errors: u64,
 
+    /// Errors of the records that failed, one line each
+    pub failures: Vec<String>,
+
+    /// Number of records skipped because the mapped fields had not changed
+    pub records_unchanged: u64,
+
+    /// Number of changes skipped because they were our own writes
+    pub echoes_skipped: u64,
+
+    /// Number of record pairs linked by the matching phase
+    pub records_linked: u64,
+
+    /// Number of record pairs left for review by the matching phase
+    pub link_candidates: u64,
+
     /// Sync direction
     pub direction: SyncDirection,
 
     /// New cursor position
     pub cursor: Option<SyncCursor>,
+
+    /// What would be written (only filled in plan mode)
+    pub plan: SyncPlan,
 }
 
 impl SyncEngine {
@@ -49,6 +111,7 @@ impl SyncEngine {
         let salesforce = SalesforceClient::new(config.salesforce.clone());
         let transform = TransformPipeline::new();
         let conflict_resolver = ConflictResolver::new(config.sync.conflict_resolution);
+        let feed = Rc::new(ApiChangeFeed::new(&config));
 
         Self {
             config,
@@ -56,36 +119,191 @@ impl SyncEngine {
             salesforce,
             transform,
             conflict_resolver,
+            storage: Rc::new(MemoryStorage::new()),
+            lock: Rc::new(MemoryLock::new()),
+            feed,
+            merge_strategies: HashMap::new(),
+            trigger: SyncTrigger::Manual,
+            last_run: None,
+            audit: AuditContext::default(),
+            mode: SyncMode::Apply,
+            holder_id: uuid::Uuid::new_v4().to_string(),
+            reference_checks: Vec::new(),
         }
     }
 
+    /// Use persistent storage for ID mappings and write fingerprints
+    pub fn with_storage(mut self, storage: Rc<dyn Storage>) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    /// Use a shared lock so concurrent runs never sync the same mapping
+    pub fn with_lock(mut self, lock: Rc<dyn Lock>) -> Self {
+        self.lock = lock;
+        self
+    }
+
+    /// Read changed records for incremental sync from another feed
+    pub fn with_change_feed(mut self, feed: Rc<dyn ChangeFeed>) -> Self {
+        self.feed = feed;
+        self
+    }
+
+    /// Register a custom merge strategy, selectable by name from config
+    pub fn with_merge_strategy(mut self, strategy: Rc<dyn MergeStrategy>) -> Self {
+        self.merge_strategies
+            .insert(strategy.name().to_string(), strategy);
+        self
+    }
+
+    /// Set what starts this engine's runs (recorded in the run history)
+    pub fn with_trigger(mut self, trigger: SyncTrigger) -> Self {
+        self.trigger = trigger;
+        self
+    }
+
+    /// Choose whether sync calls write their changes or only return a plan
+    pub fn with_mode(mut self, mode: SyncMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Get whether sync calls write or only plan
+    pub fn mode(&self) -> SyncMode {
+        self.mode
+    }
+
+    /// Get the run of the most recent sync call, once it has finished
+    pub fn last_run(&self) -> Option<&SyncRun> {
+        self.last_run.as_ref()
+    }
+
+    /// Get the engine configuration
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
     /// Sync a single record from Attio to Salesforce
     pub async fn sync_attio_to_sf(
         &mut self,
         attio_object: &str,
         attio_record_id: &str,
     ) -> Result<SyncResult> {
+        let mapping = self
+            .config
+            .mapping_for_attio_object(attio_object)
+            .ok_or_else(|| {
+                Error::mapping(format!(
+                    "No enabled mapping for Attio object {}",
+                    attio_object
+                ))
+            })?;
+        let fields = mapping.attio_to_salesforce_fields();
+        let mut result = SyncResult {
+            records_processed: 1,
+            direction: SyncDirection::AttioToSalesforce,
+            ..SyncResult::default()
+        };
+
         // 1. Fetch record from Attio
-        let _attio_record = self.attio.get_record(attio_object, attio_record_id).await?;
+        let attio_record = self.attio.get_record(attio_object, attio_record_id).await?;
+        let attio_data = attio_record.to_json();
 
-        // 2. Transform to Salesforce format
-        // TODO: Implement transformation
+        // 2. Skip echoes of our own writes
+        if self
+            .is_recent_write(System::Attio, attio_object, attio_record_id, &attio_data)
+            .await?
+        {
+            result.echoes_skipped = 1;
+            return Ok(result);
+        }
 
-        // 3. Check for existing SF record (via ID mapping)
-        // TODO: Implement ID mapping lookup
+        // 3. Transform to Salesforce format
+        self.prefetch_references(&mapping, Some(&attio_data), None)
+            .await?;
+        let sf_data = self.transform.attio_to_salesforce(&attio_data, &fields)?;
 
-        // 4. Create or update in Salesforce
-        // TODO: Implement create/update
+        // 4. Merge into the linked Salesforce record, or create one, skipping no-op writes
+        let sf_object = &mapping.salesforce_object;
+        let sf_hash = content_hash(&sf_data);
+        let existing = self
+            .storage
+            .get_mapping_by_attio_id(attio_object, attio_record_id)
+            .await?;
+        // A record waiting for link review would be duplicated by a create
+        if existing.is_none() && self.awaiting_review(attio_object, attio_record_id).await? {
+            result.records_unchanged = 1;
+            return Ok(result);
+        }
+        let link = match existing {
+            Some(link) if link.salesforce_hash.as_deref() == Some(sf_hash.as_str()) => {
+                result.records_unchanged = 1;
+                return Ok(result);
+            }
+            Some(link) => {
+                let sf_record = self
+                    .salesforce
+                    .get_record(sf_object, &link.salesforce_id)
+                    .await?;
+                let sf_current = Value::Object(sf_record.fields.clone().into_iter().collect());
+                let linked = LinkedRecords {
+                    link,
+                    source: System::Attio,
+                    attio_data: &attio_data,
+                    salesforce_data: &sf_current,
+                    attio_modified: Some(attio_record.updated_at),
+                    salesforce_modified: sf_record.last_modified(),
+                };
+                self.reconcile(&mapping, linked, &mut result).await?;
+                return Ok(result);
+            }
+            None if self.mode == SyncMode::Plan => {
+                let sf_fields: Vec<_> = fields.iter().map(|f| f.salesforce_field.clone()).collect();
+                result.records_created = 1;
+                result.plan.push(PlannedChange::create(
+                    System::Salesforce,
+                    attio_object,
+                    attio_record_id,
+                    sf_object,
+                    diff_fields(&self.transform, &sf_fields, &FieldValues::new(), &sf_data),
+                ));
+                return Ok(result);
+            }
+            None => {
+                let sf_id = self
+                    .salesforce
+                    .create_record(sf_object, sf_data.clone())
+                    .await?;
+                result.records_created = 1;
+                IdMapping::new(attio_object, attio_record_id, sf_object, sf_id.0)
+            }
+        };
+        let link = IdMapping {
+            salesforce_hash: Some(sf_hash),
+            ..link
+        };
+        self.storage.save_id_mapping(&link).await?;
+        self.save_base(&link, &mapping.fields, &attio_data, &sf_data)
+            .await?;
 
-        Ok(SyncResult {
-            records_processed: 1,
-            records_created: 0,
-            records_updated: 0,
-            conflicts: 0,
-            errors: 0,
-            direction: SyncDirection::AttioToSalesforce,
-            cursor: None,
-        })
+        // 5. Audit the write and remember it so the resulting Salesforce change is not synced back
+        let sf_fields = fields.iter().map(|f| f.salesforce_field.clone()).collect();
+        self.record_write(
+            AuditAction::Create,
+            System::Salesforce,
+            &link,
+            sf_fields,
+            &FieldValues::new(),
+            &sf_data,
+        )
+        .await?;
+
+        // 6. Remember references to unlinked records, and fill in those waiting on this one
+        self.update_pending_references(&link).await?;
+        self.backfill_references(&link, &mut result).await?;
+
+        Ok(result)
     }
 
     /// Sync a single record from Salesforce to Attio
@@ -94,100 +312,2116 @@ impl SyncEngine {
         sf_object: &str,
         sf_record_id: &str,
     ) -> Result<SyncResult> {
+        let mapping = self
+            .config
+            .mapping_for_salesforce_object(sf_object)
+            .ok_or_else(|| {
+                Error::mapping(format!(
+                    "No enabled mapping for Salesforce object {}",
+                    sf_object
+                ))
+            })?;
+        let fields = mapping.salesforce_to_attio_fields();
+        let mut result = SyncResult {
+            records_processed: 1,
+            direction: SyncDirection::SalesforceToAttio,
+            ..SyncResult::default()
+        };
+
         // 1. Fetch record from Salesforce
-        let _sf_record = self.salesforce.get_record(sf_object, sf_record_id).await?;
+        let sf_record = self.salesforce.get_record(sf_object, sf_record_id).await?;
+        let sf_data = Value::Object(sf_record.fields.clone().into_iter().collect());
 
-        // 2. Transform to Attio format
-        // TODO: Implement transformation
+        // 2. Skip echoes of our own writes
+        let integration_user_id = self.config.salesforce.integration_user_id.as_deref();
+        if modified_by_integration_user(&sf_record, integration_user_id)
+            || self
+                .is_recent_write(System::Salesforce, sf_object, sf_record_id, &sf_data)
+                .await?
+        {
+            result.echoes_skipped = 1;
+            return Ok(result);
+        }
 
-        // 3. Check for existing Attio record (via ID mapping)
-        // TODO: Implement ID mapping lookup
+        // 3. Transform to Attio format
+        self.prefetch_references(&mapping, None, Some(&sf_data))
+            .await?;
+        let attio_data = self.transform.salesforce_to_attio(&sf_data, &fields)?;
 
-        // 4. Create or update in Attio
-        // TODO: Implement create/update
+        // 4. Merge into the linked Attio record, or create one, skipping no-op writes
+        let attio_object = &mapping.attio_object;
+        let attio_hash = content_hash(&attio_data);
+        let existing = self
+            .storage
+            .get_mapping_by_sf_id(sf_object, sf_record_id)
+            .await?;
+        // A record waiting for link review would be duplicated by a create
+        if existing.is_none() && self.awaiting_review(sf_object, sf_record_id).await? {
+            result.records_unchanged = 1;
+            return Ok(result);
+        }
+        let link = match existing {
+            Some(link) if link.attio_hash.as_deref() == Some(attio_hash.as_str()) => {
+                result.records_unchanged = 1;
+                return Ok(result);
+            }
+            Some(link) => {
+                let attio_record = self.attio.get_record(attio_object, &link.attio_id).await?;
+                let attio_current = attio_record.to_json();
+                let linked = LinkedRecords {
+                    link,
+                    source: System::Salesforce,
+                    attio_data: &attio_current,
+                    salesforce_data: &sf_data,
+                    attio_modified: Some(attio_record.updated_at),
+                    salesforce_modified: sf_record.last_modified(),
+                };
+                self.reconcile(&mapping, linked, &mut result).await?;
+                return Ok(result);
+            }
+            None if self.mode == SyncMode::Plan => {
+                let attio_fields: Vec<_> = fields.iter().map(|f| f.attio_field.clone()).collect();
+                result.records_created = 1;
+                result.plan.push(PlannedChange::create(
+                    System::Attio,
+                    sf_object,
+                    sf_record_id,
+                    attio_object,
+                    diff_fields(
+                        &self.transform,
+                        &attio_fields,
+                        &FieldValues::new(),
+                        &attio_data,
+                    ),
+                ));
+                return Ok(result);
+            }
+            None => {
+                let record = self
+                    .attio
+                    .create_record(attio_object, attio_data.clone())
+                    .await?;
+                result.records_created = 1;
+                IdMapping::new(attio_object, record.id.0, sf_object, sf_record_id)
+            }
+        };
+        let link = IdMapping {
+            attio_hash: Some(attio_hash),
+            ..link
+        };
+        self.storage.save_id_mapping(&link).await?;
+        self.save_base(&link, &mapping.fields, &attio_data, &sf_data)
+            .await?;
+
+        // 5. Audit the write and remember it so the resulting Attio change is not synced back
+        let attio_fields = fields.iter().map(|f| f.attio_field.clone()).collect();
+        self.record_write(
+            AuditAction::Create,
+            System::Attio,
+            &link,
+            attio_fields,
+            &FieldValues::new(),
+            &attio_data,
+        )
+        .await?;
+
+        // 6. Remember references to unlinked records, and fill in those waiting on this one
+        self.update_pending_references(&link).await?;
+        self.backfill_references(&link, &mut result).await?;
+
+        Ok(result)
+    }
 
-        Ok(SyncResult {
+    /// Apply the mapping's delete policy after a record was deleted on the `source` side.
+    ///
+    /// Records that were never linked are ignored. Unless the policy is
+    /// `ignore`, the link is removed so the deleted ID is never synced again.
+    pub async fn sync_delete(
+        &mut self,
+        source: System,
+        object: &str,
+        record_id: &str,
+    ) -> Result<SyncResult> {
+        if let Some(master_id) = self.feed.merged_into(source, object, record_id).await? {
+            return self.sync_merge(source, object, &master_id, record_id).await;
+        }
+        let (mapping, link, target) = match source {
+            System::Attio => (
+                self.config.mapping_for_attio_object(object),
+                self.storage
+                    .get_mapping_by_attio_id(object, record_id)
+                    .await?,
+                System::Salesforce,
+            ),
+            System::Salesforce => (
+                self.config.mapping_for_salesforce_object(object),
+                self.storage.get_mapping_by_sf_id(object, record_id).await?,
+                System::Attio,
+            ),
+        };
+        let mapping = mapping.ok_or_else(|| {
+            Error::mapping(format!(
+                "No enabled mapping for {} object {}",
+                source.as_str(),
+                object
+            ))
+        })?;
+        let mut result = SyncResult {
             records_processed: 1,
-            records_created: 0,
-            records_updated: 0,
-            conflicts: 0,
-            errors: 0,
-            direction: SyncDirection::SalesforceToAttio,
-            cursor: None,
-        })
-    }
-
-    /// Run incremental sync from a cursor
-    pub async fn incremental_sync(&mut self, cursor: Option<SyncCursor>) -> Result<SyncResult> {
-        let since = cursor
-            .map(|c| c.timestamp)
-            .unwrap_or_else(|| chrono::Utc::now() - chrono::Duration::hours(24));
-
-        match self.config.sync.direction {
-            SyncDirection::AttioToSalesforce => {
-                self.sync_attio_changes_since(since).await
-            }
-            SyncDirection::SalesforceToAttio => {
-                self.sync_sf_changes_since(since).await
-            }
-            SyncDirection::Bidirectional => {
-                // Sync both directions
-                let attio_result = self.sync_attio_changes_since(since).await?;
-                let sf_result = self.sync_sf_changes_since(since).await?;
-
-                Ok(SyncResult {
-                    records_processed: attio_result.records_processed + sf_result.records_processed,
-                    records_created: attio_result.records_created + sf_result.records_created,
-                    records_updated: attio_result.records_updated + sf_result.records_updated,
-                    conflicts: attio_result.conflicts + sf_result.conflicts,
-                    errors: attio_result.errors + sf_result.errors,
-                    direction: SyncDirection::Bidirectional,
-                    cursor: Some(SyncCursor::now()),
-                })
+            direction: match target {
+                System::Salesforce => SyncDirection::AttioToSalesforce,
+                System::Attio => SyncDirection::SalesforceToAttio,
+            },
+            ..SyncResult::default()
+        };
+        let Some(link) = link else {
+            result.records_unchanged = 1;
+            return Ok(result);
+        };
+
+        match &mapping.on_delete {
+            DeletePolicy::Ignore => {
+                result.records_unchanged = 1;
+                return Ok(result);
             }
+            DeletePolicy::Unlink => {}
+            DeletePolicy::Propagate => {
+                result.records_deleted = 1;
+                self.delete_linked(target, &link, &mut result).await?;
+            }
+            DeletePolicy::Flag {
+                salesforce_field,
+                attio_field,
+            } => {
+                let field = match target {
+                    System::Salesforce => salesforce_field,
+                    System::Attio => attio_field,
+                }
+                .clone()
+                .ok_or_else(|| {
+                    Error::config(format!(
+                        "Delete policy for {} has no {} flag field",
+                        mapping.key(),
+                        target.as_str()
+                    ))
+                })?;
+                let payload = serde_json::json!({ field.as_str(): true });
+                result.records_updated = 1;
+                self.write_linked(
+                    &link,
+                    vec![SideWrite {
+                        target,
+                        fields: vec![field],
+                        before: FieldValues::new(),
+                        payload,
+                    }],
+                    &mut result,
+                )
+                .await?;
+            }
+        }
+
+        if self.mode == SyncMode::Apply {
+            self.storage
+                .delete_mapping(&link.attio_object, &link.attio_id)
+                .await?;
         }
+        Ok(result)
     }
 
-    /// Sync changes from Attio since a timestamp
-    async fn sync_attio_changes_since(
+    /// Handle `loser_id` being merged into `winner_id` on the `source` side.
+    ///
+    /// The loser's link moves to the winner, or is dropped if the winner is
+    /// already linked (merging the counterparts too if `mirror_merges` is
+    /// set). Records whose synced values referenced the loser are re-synced.
+    pub async fn sync_merge(
         &mut self,
-        _since: chrono::DateTime<chrono::Utc>,
+        source: System,
+        object: &str,
+        winner_id: &str,
+        loser_id: &str,
     ) -> Result<SyncResult> {
-        // TODO: Implement
-        Ok(SyncResult {
-            records_processed: 0,
-            records_created: 0,
-            records_updated: 0,
-            conflicts: 0,
-            errors: 0,
-            direction: SyncDirection::AttioToSalesforce,
-            cursor: Some(SyncCursor::now()),
-        })
+        let (mapping, target) = match source {
+            System::Attio => (
+                self.config.mapping_for_attio_object(object),
+                System::Salesforce,
+            ),
+            System::Salesforce => (
+                self.config.mapping_for_salesforce_object(object),
+                System::Attio,
+            ),
+        };
+        let mapping = mapping.ok_or_else(|| {
+            Error::mapping(format!(
+                "No enabled mapping for {} object {}",
+                source.as_str(),
+                object
+            ))
+        })?;
+        let mut result = SyncResult {
+            records_processed: 1,
+            direction: match target {
+                System::Salesforce => SyncDirection::AttioToSalesforce,
+                System::Attio => SyncDirection::SalesforceToAttio,
+            },
+            ..SyncResult::default()
+        };
+
+        let Some(loser_link) = self.linked(source, object, loser_id).await? else {
+            result.records_unchanged = 1;
+            return Ok(result);
+        };
+        let winner_link = self.linked(source, object, winner_id).await?;
+
+        match &winner_link {
+            // Re-point the loser's link at the winner
+            None => {
+                let mut link = IdMapping {
+                    attio_hash: None,
+                    salesforce_hash: None,
+                    ..loser_link.clone()
+                };
+                match source {
+                    System::Attio => link.attio_id = winner_id.to_string(),
+                    System::Salesforce => link.salesforce_id = winner_id.to_string(),
+                }
+                // Only the link moves, so a plan has no record write to show
+                if self.mode == SyncMode::Apply {
+                    self.storage
+                        .delete_mapping(&loser_link.attio_object, &loser_link.attio_id)
+                        .await?;
+                    self.storage.save_id_mapping(&link).await?;
+                    self.move_link_state(&loser_link, Some(&link)).await?;
+                    self.backfill_references(&link, &mut result).await?;
+                    result.records_updated = 1;
+                } else {
+                    result.records_unchanged = 1;
+                }
+            }
+            // Both records had counterparts: keep the winner's link
+            Some(winner_link) => {
+                if mapping.mirror_merges && target == System::Salesforce {
+                    self.mirror_merge(winner_link, &loser_link, &mut result)
+                        .await?;
+                }
+                if self.mode == SyncMode::Apply {
+                    self.storage
+                        .delete_mapping(&loser_link.attio_object, &loser_link.attio_id)
+                        .await?;
+                    self.move_link_state(&loser_link, None).await?;
+                }
+            }
+        }
+
+        // Children that pointed at the loser (or its counterpart) need their references redone
+        let loser_counterpart = match target {
+            System::Salesforce => &loser_link.salesforce_id,
+            System::Attio => &loser_link.attio_id,
+        };
+        let mut children = self
+            .storage
+            .find_snapshots_referencing(source, loser_id)
+            .await?;
+        children.extend(
+            self.storage
+                .find_snapshots_referencing(target, loser_counterpart)
+                .await?,
+        );
+        children
+            .sort_by(|a, b| (&a.attio_object, &a.attio_id).cmp(&(&b.attio_object, &b.attio_id)));
+        children.dedup_by(|a, b| a.attio_object == b.attio_object && a.attio_id == b.attio_id);
+        for child in children {
+            let (child_object, child_id) = match source {
+                System::Attio => (&child.attio_object, &child.attio_id),
+                System::Salesforce => (&child.salesforce_object, &child.salesforce_id),
+            };
+            let synced = match source {
+                System::Attio => self.sync_attio_to_sf(child_object, child_id).await,
+                System::Salesforce => self.sync_sf_to_attio(child_object, child_id).await,
+            };
+            match synced {
+                Ok(child_result) => result.merge(child_result),
+                Err(e) => result.record_error(child_object, child_id, &e),
+            }
+        }
+        Ok(result)
+    }
+
+    /// Link of a record on the given side, if any
+    async fn linked(&self, system: System, object: &str, id: &str) -> Result<Option<IdMapping>> {
+        match system {
+            System::Attio => self.storage.get_mapping_by_attio_id(object, id).await,
+            System::Salesforce => self.storage.get_mapping_by_sf_id(object, id).await,
+        }
+    }
+
+    /// Move the base snapshot and pending references of a link to its new link, or drop them
+    async fn move_link_state(&self, from: &IdMapping, to: Option<&IdMapping>) -> Result<()> {
+        if let Some(mut base) = self
+            .storage
+            .get_base_snapshot(&from.attio_object, &from.attio_id)
+            .await?
+        {
+            self.storage
+                .delete_base_snapshot(&from.attio_object, &from.attio_id)
+                .await?;
+            if let Some(to) = to {
+                base.attio_id = to.attio_id.clone();
+                base.salesforce_id = to.salesforce_id.clone();
+                self.storage.save_base_snapshot(&base).await?;
+            }
+        }
+
+        let pending = self
+            .storage
+            .find_pending_references_of(&from.attio_object, &from.attio_id)
+            .await?;
+        for mut reference in pending {
+            self.storage.delete_pending_reference(&reference.id).await?;
+            if let Some(to) = to {
+                reference.id = PendingReference::key(reference.target, to, &reference.field);
+                reference.link = to.clone();
+                self.storage.save_pending_reference(&reference).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Merge the loser's Salesforce counterpart into the winner's, or plan to
+    async fn mirror_merge(
+        &mut self,
+        winner: &IdMapping,
+        loser: &IdMapping,
+        result: &mut SyncResult,
+    ) -> Result<()> {
+        result.records_deleted += 1;
+        if self.mode == SyncMode::Plan {
+            result.plan.push(PlannedChange::linked(
+                AuditAction::Delete,
+                System::Salesforce,
+                loser,
+                vec![],
+            ));
+            return Ok(());
+        }
+        self.salesforce
+            .merge_records(
+                &winner.salesforce_object,
+                &winner.salesforce_id,
+                &[loser.salesforce_id.as_str()],
+            )
+            .await?;
+        let entry = AuditEntry::new(
+            &self.audit,
+            self.trigger,
+            AuditAction::Delete,
+            System::Salesforce,
+            loser,
+            vec![],
+        );
+        self.storage.record_audit_entry(&entry).await
+    }
+
+    /// Delete the `target` side of a link, or plan to
+    async fn delete_linked(
+        &mut self,
+        target: System,
+        link: &IdMapping,
+        result: &mut SyncResult,
+    ) -> Result<()> {
+        if self.mode == SyncMode::Plan {
+            result.plan.push(PlannedChange::linked(
+                AuditAction::Delete,
+                target,
+                link,
+                vec![],
+            ));
+            return Ok(());
+        }
+        match target {
+            System::Salesforce => {
+                self.salesforce
+                    .delete_record(&link.salesforce_object, &link.salesforce_id)
+                    .await?
+            }
+            System::Attio => {
+                self.attio
+                    .delete_record(&link.attio_object, &link.attio_id)
+                    .await?
+            }
+        }
+        let entry = AuditEntry::new(
+            &self.audit,
+            self.trigger,
+            AuditAction::Delete,
+            target,
+            link,
+            vec![],
+        );
+        self.storage.record_audit_entry(&entry).await
     }
 
-    /// Sync changes from Salesforce since a timestamp
-    async fn sync_sf_changes_since(
+    /// Three-way merge a linked pair of records against their base snapshot.
+    ///
+    /// One-sided changes are copied across; fields changed on both sides go
+    /// through the conflict resolver, and stay unsynced if it defers to a human.
+    async fn reconcile(
         &mut self,
-        _since: chrono::DateTime<chrono::Utc>,
+        mapping: &ObjectMapping,
+        linked: LinkedRecords<'_>,
+        result: &mut SyncResult,
+    ) -> Result<()> {
+        let LinkedRecords {
+            link,
+            source,
+            attio_data,
+            salesforce_data,
+            attio_modified,
+            salesforce_modified,
+        } = linked;
+        self.prefetch_references(mapping, Some(attio_data), Some(salesforce_data))
+            .await?;
+        let direction = SyncDirectionHandler::new(self.config.sync.direction);
+        let resolver = self.conflict_resolver.for_mapping(mapping);
+        let mut attio_values =
+            field_values(&self.transform, attio_data, &mapping.fields, System::Attio);
+        let mut sf_values = field_values(
+            &self.transform,
+            salesforce_data,
+            &mapping.fields,
+            System::Salesforce,
+        );
+        let base = self
+            .storage
+            .get_base_snapshot(&link.attio_object, &link.attio_id)
+            .await?;
+
+        let mut plan = plan_merge(
+            &resolver,
+            &mapping.fields,
+            &direction,
+            source,
+            &attio_values,
+            &sf_values,
+            base.as_ref(),
+        );
+
+        // Resolve fields changed on both sides
+        let mut unresolved = Vec::new();
+        let mut writes = Vec::new();
+        if !plan.conflicts.is_empty() {
+            result.conflicts = 1;
+            let mut conflict = ConflictRecord::new(
+                &link.attio_object,
+                &link.attio_id,
+                &link.salesforce_object,
+                &link.salesforce_id,
+                attio_data.clone(),
+                salesforce_data.clone(),
+            );
+            for field in &plan.conflicts {
+                conflict.add_field_conflict(FieldConflict {
+                    attio_field: field.attio_field.clone(),
+                    salesforce_field: field.salesforce_field.clone(),
+                    attio_value: attio_values
+                        .get(&field.attio_field)
+                        .cloned()
+                        .unwrap_or(Value::Null),
+                    salesforce_value: sf_values
+                        .get(&field.salesforce_field)
+                        .cloned()
+                        .unwrap_or(Value::Null),
+                    attio_modified_at: attio_modified,
+                    salesforce_modified_at: salesforce_modified,
+                });
+            }
+
+            let resolution = match self.merge_strategy_for(mapping)? {
+                Some(strategy) => {
+                    let outcome = strategy.merge(&MergeContext {
+                        mapping,
+                        attio: attio_data,
+                        salesforce: salesforce_data,
+                        base: base.as_ref(),
+                        conflicts: &conflict.conflicting_fields,
+                    })?;
+                    let (merged, attio_merged, sf_merged) = self.choice_writes(
+                        &mapping.fields,
+                        &conflict.conflicting_fields,
+                        &outcome.fields,
+                    )?;
+                    writes.extend(merged);
+                    attio_values.extend(attio_merged);
+                    sf_values.extend(sf_merged);
+
+                    unresolved = std::mem::take(&mut plan.conflicts)
+                        .into_iter()
+                        .filter(|field| !outcome.fields.contains_key(&field.attio_field))
+                        .collect();
+                    unresolved.is_empty().then(|| ConflictResolutionResult {
+                        winner: outcome.winner,
+                        resolved_at: Utc::now(),
+                        resolved_by: "system".to_string(),
+                        notes: Some(format!("{} merge strategy", strategy.name())),
+                    })
+                }
+                None => {
+                    let merged = resolver.merge_values(&conflict);
+                    for (field, decision) in std::mem::take(&mut plan.conflicts)
+                        .into_iter()
+                        .zip(merged.decisions)
+                    {
+                        match decision.winner {
+                            Some(ConflictWinner::Attio) => plan.to_salesforce.push(field),
+                            Some(ConflictWinner::Salesforce) => plan.to_attio.push(field),
+                            _ => unresolved.push(field),
+                        }
+                    }
+                    merged.resolution
+                }
+            };
+
+            match resolution.clone() {
+                Some(resolution) => conflict.resolve(resolution),
+                // Fields already decided are written below, so only the rest await a choice
+                None => conflict.conflicting_fields.retain(|field| {
+                    unresolved
+                        .iter()
+                        .any(|open| open.attio_field == field.attio_field)
+                }),
+            }
+            match (self.mode, resolution) {
+                (SyncMode::Plan, _) => result.plan.conflicts.push(conflict),
+                (SyncMode::Apply, Some(_)) => self.storage.save_conflict(&conflict).await?,
+                (SyncMode::Apply, None) => self.queue_conflict(conflict).await?,
+            }
+        }
+
+        // Copy one-sided changes across
+        if !plan.to_salesforce.is_empty() {
+            let payload = self
+                .transform
+                .attio_to_salesforce(attio_data, &plan.to_salesforce)?;
+            let written = field_values(
+                &self.transform,
+                &payload,
+                &plan.to_salesforce,
+                System::Salesforce,
+            );
+            writes.push(SideWrite {
+                target: System::Salesforce,
+                fields: plan
+                    .to_salesforce
+                    .iter()
+                    .map(|f| f.salesforce_field.clone())
+                    .collect(),
+                before: sf_values.clone(),
+                payload,
+            });
+            sf_values.extend(written);
+        }
+        if !plan.to_attio.is_empty() {
+            let payload = self
+                .transform
+                .salesforce_to_attio(salesforce_data, &plan.to_attio)?;
+            let written = field_values(&self.transform, &payload, &plan.to_attio, System::Attio);
+            writes.push(SideWrite {
+                target: System::Attio,
+                fields: plan
+                    .to_attio
+                    .iter()
+                    .map(|f| f.attio_field.clone())
+                    .collect(),
+                before: attio_values.clone(),
+                payload,
+            });
+            attio_values.extend(written);
+        }
+
+        if writes.is_empty() {
+            result.records_unchanged = 1;
+        } else {
+            result.records_updated = 1;
+        }
+        self.write_linked(&link, writes, result).await?;
+        if self.mode == SyncMode::Plan {
+            return Ok(());
+        }
+
+        // Unresolved fields keep their old base so they still conflict next time
+        if let Some(base) = &base {
+            for field in &unresolved {
+                restore(&mut attio_values, &base.attio, &field.attio_field);
+                restore(&mut sf_values, &base.salesforce, &field.salesforce_field);
+            }
+        }
+        let snapshot = BaseSnapshot::new(&link, attio_values, sf_values);
+        self.storage.save_base_snapshot(&snapshot).await?;
+
+        let sf_payload = self
+            .transform
+            .attio_to_salesforce(attio_data, &mapping.attio_to_salesforce_fields())?;
+        let attio_payload = self
+            .transform
+            .salesforce_to_attio(salesforce_data, &mapping.salesforce_to_attio_fields())?;
+        let link = IdMapping {
+            attio_hash: Some(content_hash(&attio_payload)),
+            salesforce_hash: Some(content_hash(&sf_payload)),
+            ..link
+        };
+        self.storage.save_id_mapping(&link).await?;
+        self.update_pending_references(&link).await
+    }
+
+    /// Queue a conflict for manual resolution, replacing any pending one for the same link
+    async fn queue_conflict(&self, mut conflict: ConflictRecord) -> Result<()> {
+        let filter = ConflictFilter {
+            object: Some(conflict.attio_object.clone()),
+            record_id: Some(conflict.attio_record_id.clone()),
+            status: Some(ConflictStatus::Pending),
+            ..ConflictFilter::default()
+        };
+        let pending = self
+            .storage
+            .list_conflicts(&filter, Page::new(1, 1))
+            .await?;
+
+        match pending.into_iter().next() {
+            Some(existing) => {
+                conflict.id = existing.id;
+                conflict.detected_at = existing.detected_at;
+                self.storage.update_conflict(&conflict).await
+            }
+            None => self.storage.save_conflict(&conflict).await,
+        }
+    }
+
+    /// Load the links of the records that reference fields point at, with one
+    /// storage lookup per referenced object, and note which targets are unlinked
+    async fn prefetch_references(
+        &mut self,
+        mapping: &ObjectMapping,
+        attio_data: Option<&Value>,
+        sf_data: Option<&Value>,
+    ) -> Result<()> {
+        let direction = SyncDirectionHandler::new(self.config.sync.direction);
+        let mut found = Vec::new();
+        let mut attio_ids: HashMap<&str, Vec<String>> = HashMap::new();
+        let mut sf_ids: HashMap<&str, Vec<String>> = HashMap::new();
+        for field in &mapping.fields {
+            let TransformType::Reference {
+                attio_object,
+                salesforce_object,
+            } = &field.transform
+            else {
+                continue;
+            };
+            let attio_refs = attio_data
+                .and_then(|data| self.transform.get_nested_value(data, &field.attio_field))
+                .map(|value| attio_reference_ids(&value))
+                .unwrap_or_default();
+            let sf_ref = sf_data
+                .and_then(|data| data.get(&field.salesforce_field))
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            attio_ids
+                .entry(attio_object)
+                .or_default()
+                .extend(attio_refs.iter().cloned());
+            sf_ids
+                .entry(salesforce_object)
+                .or_default()
+                .extend(sf_ref.iter().cloned());
+            found.push((field, attio_object, salesforce_object, attio_refs, sf_ref));
+        }
+
+        let mut references = ReferenceResolver::new();
+        for (object, ids) in attio_ids.into_iter().filter(|(_, ids)| !ids.is_empty()) {
+            references.load_mappings(self.storage.get_mappings_by_attio_ids(object, &ids).await?);
+        }
+        for (object, ids) in sf_ids.into_iter().filter(|(_, ids)| !ids.is_empty()) {
+            references.load_mappings(self.storage.get_mappings_by_sf_ids(object, &ids).await?);
+        }
+
+        self.reference_checks.clear();
+        for (field, attio_object, salesforce_object, attio_refs, sf_ref) in found {
+            let to_sf = direction.attio_to_sf_enabled()
+                && matches!(
+                    field.direction,
+                    FieldSyncDirection::Bidirectional | FieldSyncDirection::AttioToSalesforce
+                );
+            let to_attio = direction.sf_to_attio_enabled()
+                && matches!(
+                    field.direction,
+                    FieldSyncDirection::Bidirectional | FieldSyncDirection::SalesforceToAttio
+                );
+            if let (true, Some(first)) = (to_sf, attio_refs.first()) {
+                let linked = attio_refs
+                    .iter()
+                    .any(|id| references.has_attio_mapping(attio_object, id));
+                self.reference_checks.push(ReferenceCheck {
+                    target: System::Salesforce,
+                    field: field.salesforce_field.clone(),
+                    unlinked: (!linked).then(|| (attio_object.clone(), first.clone())),
+                });
+            }
+            if let (true, Some(id)) = (to_attio, sf_ref) {
+                let linked = references.has_salesforce_mapping(salesforce_object, &id);
+                self.reference_checks.push(ReferenceCheck {
+                    target: System::Attio,
+                    field: field.attio_field.clone(),
+                    unlinked: (!linked).then(|| (salesforce_object.clone(), id)),
+                });
+            }
+        }
+        self.transform.set_references(references);
+        Ok(())
+    }
+
+    /// Save a pending entry for each reference of a written record whose target
+    /// was unlinked, and drop the entries of references that now resolved
+    async fn update_pending_references(&mut self, link: &IdMapping) -> Result<()> {
+        if self.mode == SyncMode::Plan {
+            return Ok(());
+        }
+        for check in std::mem::take(&mut self.reference_checks) {
+            let Some((object, record_id)) = check.unlinked else {
+                let key = PendingReference::key(check.target, link, &check.field);
+                self.storage.delete_pending_reference(&key).await?;
+                continue;
+            };
+            let mut pending =
+                PendingReference::new(check.target, link, &check.field, &object, &record_id);
+            // Keep the original age of an entry that is still waiting
+            if let Some(existing) = self
+                .storage
+                .find_pending_references(&object, &record_id)
+                .await?
+                .into_iter()
+                .find(|p| p.id == pending.id)
+            {
+                pending.created_at = existing.created_at;
+            }
+            self.storage.save_pending_reference(&pending).await?;
+        }
+        Ok(())
+    }
+
+    /// Patch the lookups that were waiting for a newly linked record.
+    ///
+    /// A failed patch is counted as an error and its entry kept.
+    async fn backfill_references(
+        &mut self,
+        link: &IdMapping,
+        result: &mut SyncResult,
+    ) -> Result<()> {
+        let mut waiting = self
+            .storage
+            .find_pending_references(&link.attio_object, &link.attio_id)
+            .await?;
+        waiting.extend(
+            self.storage
+                .find_pending_references(&link.salesforce_object, &link.salesforce_id)
+                .await?,
+        );
+
+        for pending in waiting {
+            let Some(value) = pending.resolved_by(link) else {
+                continue;
+            };
+            let mut payload = serde_json::Map::new();
+            payload.insert(pending.field.clone(), value);
+            let write = SideWrite {
+                target: pending.target,
+                fields: vec![pending.field.clone()],
+                before: FieldValues::new(),
+                payload: Value::Object(payload),
+            };
+            match self.write_linked(&pending.link, vec![write], result).await {
+                Ok(()) if self.mode == SyncMode::Apply => {
+                    self.storage.delete_pending_reference(&pending.id).await?
+                }
+                Ok(()) => {}
+                Err(_) => result.errors += 1,
+            }
+        }
+        Ok(())
+    }
+
+    /// Save the base snapshot for a newly created link
+    async fn save_base(
+        &self,
+        link: &IdMapping,
+        fields: &[FieldMapping],
+        attio_data: &Value,
+        sf_data: &Value,
+    ) -> Result<()> {
+        let snapshot = BaseSnapshot::new(
+            link,
+            field_values(&self.transform, attio_data, fields, System::Attio),
+            field_values(&self.transform, sf_data, fields, System::Salesforce),
+        );
+        self.storage.save_base_snapshot(&snapshot).await
+    }
+
+    /// Check whether a record still holds exactly the values we last wrote to it.
+    ///
+    /// Every other mapped field must also still hold its last-synced value, so
+    /// a change to a field we did not write is never taken for our echo.
+    async fn is_recent_write(
+        &self,
+        system: System,
+        object: &str,
+        record_id: &str,
+        data: &Value,
+    ) -> Result<bool> {
+        let Some(fingerprint) = self
+            .storage
+            .get_fingerprint(system, object, record_id)
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        let values: Vec<Value> = fingerprint
+            .fields
+            .iter()
+            .map(|field| {
+                self.transform
+                    .get_nested_value(data, field)
+                    .unwrap_or(Value::Null)
+            })
+            .collect();
+        if !fingerprint.matches(&values, chrono::Utc::now()) {
+            return Ok(false);
+        }
+
+        let mapping = match system {
+            System::Attio => self.config.mapping_for_attio_object(object),
+            System::Salesforce => self.config.mapping_for_salesforce_object(object),
+        };
+        let Some(link) = self.linked(system, object, record_id).await? else {
+            return Ok(true);
+        };
+        let base = self
+            .storage
+            .get_base_snapshot(&link.attio_object, &link.attio_id)
+            .await?;
+        let (Some(mapping), Some(base)) = (mapping, base) else {
+            return Ok(true);
+        };
+        let base = match system {
+            System::Attio => &base.attio,
+            System::Salesforce => &base.salesforce,
+        };
+        let current = field_values(&self.transform, data, &mapping.fields, system);
+        Ok(mapping.fields.iter().all(|field| {
+            let name = match system {
+                System::Attio => &field.attio_field,
+                System::Salesforce => &field.salesforce_field,
+            };
+            !diverged(field, base.get(name), current.get(name))
+        }))
+    }
+
+    /// Audit a write to one side of a link and remember it so its echo is skipped
+    async fn record_write(
+        &self,
+        action: AuditAction,
+        target: System,
+        link: &IdMapping,
+        fields: Vec<String>,
+        before: &FieldValues,
+        written: &Value,
+    ) -> Result<()> {
+        let changes = diff_fields(&self.transform, &fields, before, written);
+        let entry = AuditEntry::new(&self.audit, self.trigger, action, target, link, changes);
+        self.storage.record_audit_entry(&entry).await?;
+
+        let (object, record_id) = match target {
+            System::Attio => (&link.attio_object, &link.attio_id),
+            System::Salesforce => (&link.salesforce_object, &link.salesforce_id),
+        };
+        self.remember_write(target, object, record_id, fields, written)
+            .await
+    }
+
+    /// Store a fingerprint of the fields we wrote to a record
+    async fn remember_write(
+        &self,
+        system: System,
+        object: &str,
+        record_id: &str,
+        fields: Vec<String>,
+        written: &Value,
+    ) -> Result<()> {
+        let (fields, values): (Vec<String>, Vec<Value>) = fields
+            .into_iter()
+            .filter_map(|field| {
+                let value = self.transform.get_nested_value(written, &field)?;
+                Some((field, value))
+            })
+            .unzip();
+
+        let fingerprint = WriteFingerprint::new(system, object, record_id, fields, &values);
+        self.storage.save_fingerprint(&fingerprint).await
+    }
+
+    /// Process an Attio webhook event
+    pub async fn handle_attio_event(&mut self, event: &AttioWebhookEvent) -> Result<SyncResult> {
+        let direction = SyncDirectionHandler::new(self.config.sync.direction);
+        if !direction.attio_to_sf_enabled()
+            || self
+                .config
+                .mapping_for_attio_object(&event.object)
+                .is_none()
+        {
+            return Ok(SyncResult::default());
+        }
+
+        if is_own_attio_actor(
+            event.actor.as_ref(),
+            self.config.attio.api_token_id.as_deref(),
+        ) {
+            return Ok(SyncResult {
+                echoes_skipped: 1,
+                direction: SyncDirection::AttioToSalesforce,
+                ..SyncResult::default()
+            });
+        }
+
+        match event.event_type {
+            AttioEventType::RecordCreated
+            | AttioEventType::RecordUpdated
+            | AttioEventType::RecordDeleted => {}
+            AttioEventType::RecordMerged if event.duplicate_record_id.is_some() => {}
+            // List events are not propagated
+            _ => return Ok(SyncResult::default()),
+        }
+        let run = self
+            .start_run(
+                SyncDirection::AttioToSalesforce,
+                vec![event.object.clone()],
+                event
+                    .event_type
+                    .api_name()
+                    .map(|name| format!("attio {}", name)),
+            )
+            .await?;
+        let result = match (&event.event_type, &event.duplicate_record_id) {
+            (AttioEventType::RecordDeleted, _) => {
+                self.sync_delete(System::Attio, &event.object, &event.record_id)
+                    .await
+            }
+            (AttioEventType::RecordMerged, Some(duplicate)) => {
+                self.sync_merge(System::Attio, &event.object, &event.record_id, duplicate)
+                    .await
+            }
+            _ => self.sync_attio_to_sf(&event.object, &event.record_id).await,
+        };
+        self.finish_run(run, result).await
+    }
+
+    /// Process a Salesforce change event
+    pub async fn handle_salesforce_event(
+        &mut self,
+        event: &SalesforceWebhookEvent,
     ) -> Result<SyncResult> {
-        // TODO: Implement
-        Ok(SyncResult {
-            records_processed: 0,
-            records_created: 0,
-            records_updated: 0,
-            conflicts: 0,
-            errors: 0,
+        let direction = SyncDirectionHandler::new(self.config.sync.direction);
+        if !direction.sf_to_attio_enabled()
+            || self
+                .config
+                .mapping_for_salesforce_object(&event.entity_name)
+                .is_none()
+        {
+            return Ok(SyncResult::default());
+        }
+
+        let mut result = SyncResult {
             direction: SyncDirection::SalesforceToAttio,
-            cursor: Some(SyncCursor::now()),
-        })
+            ..SyncResult::default()
+        };
+
+        let integration_user_id = self.config.salesforce.integration_user_id.as_deref();
+        if is_integration_user(event.commit_user.as_deref(), integration_user_id) {
+            result.echoes_skipped = event.record_ids.len() as u64;
+            return Ok(result);
+        }
+
+        let deleted = match event.change_type {
+            SalesforceChangeType::Create
+            | SalesforceChangeType::Update
+            | SalesforceChangeType::Undelete => false,
+            SalesforceChangeType::Delete => true,
+            // Gap events are not propagated
+            SalesforceChangeType::Unknown => return Ok(result),
+        };
+        let objects = self
+            .config
+            .mapping_for_salesforce_object(&event.entity_name)
+            .map(|m| vec![m.attio_object.clone()])
+            .unwrap_or_default();
+        let run = self
+            .start_run(
+                SyncDirection::SalesforceToAttio,
+                objects,
+                Some(format!("salesforce {:?}", event.change_type).to_lowercase()),
+            )
+            .await?;
+        let mut synced = Ok(());
+        for record_id in &event.record_ids {
+            let record_result = if deleted {
+                self.sync_delete(System::Salesforce, &event.entity_name, record_id)
+                    .await
+            } else {
+                self.sync_sf_to_attio(&event.entity_name, record_id).await
+            };
+            match record_result {
+                Ok(record_result) => result.merge(record_result),
+                Err(e) => {
+                    synced = Err(e);
+                    break;
+                }
+            }
+        }
+        self.finish_run(run, synced.map(|_| result)).await
+    }
+
+    /// Apply a user's field-by-field resolution of a pending conflict.
+    ///
+    /// Writes the chosen values to whichever side needs them and moves the base
+    /// snapshot to the resolved values so the conflict does not reappear.
+    pub async fn resolve_conflict(
+        &mut self,
+        conflict_id: &str,
+        resolution: &ManualResolution,
+        resolved_by: &str,
+    ) -> Result<ConflictRecord> {
+        if self.mode == SyncMode::Plan {
+            return Err(Error::validation(
+                "Conflicts cannot be resolved in plan mode",
+            ));
+        }
+        let mut conflict = self
+            .storage
+            .get_conflict(conflict_id)
+            .await?
+            .ok_or_else(|| Error::not_found("Conflict", conflict_id))?;
+        if conflict.status != ConflictStatus::Pending {
+            return Err(Error::validation(format!(
+                "Conflict {} is not pending",
+                conflict_id
+            )));
+        }
+        resolution.validate(&conflict)?;
+
+        let mapping = self
+            .config
+            .enabled_mappings()
+            .into_iter()
+            .find(|m| {
+                m.attio_object == conflict.attio_object
+                    && m.salesforce_object == conflict.salesforce_object
+            })
+            .ok_or_else(|| {
+                Error::mapping(format!(
+                    "No enabled mapping for {} ↔ {}",
+                    conflict.attio_object, conflict.salesforce_object
+                ))
+            })?;
+        let link = IdMapping::new(
+            &conflict.attio_object,
+            &conflict.attio_record_id,
+            &conflict.salesforce_object,
+            &conflict.salesforce_record_id,
+        );
+        let mut base = self
+            .storage
+            .get_base_snapshot(&link.attio_object, &link.attio_id)
+            .await?
+            .unwrap_or_else(|| BaseSnapshot::new(&link, HashMap::new(), HashMap::new()));
+
+        self.audit.event = Some(format!(
+            "conflict {} resolved by {}",
+            conflict_id, resolved_by
+        ));
+        let (writes, attio_values, sf_values) = self.choice_writes(
+            &mapping.fields,
+            &conflict.conflicting_fields,
+            &resolution.fields,
+        )?;
+        let written = self
+            .write_linked(&link, writes, &mut SyncResult::default())
+            .await;
+        self.audit = AuditContext::default();
+        written?;
+        base.attio.extend(attio_values);
+        base.salesforce.extend(sf_values);
+        base.synced_at = Utc::now();
+        self.storage.save_base_snapshot(&base).await?;
+
+        conflict.resolve_manually(ConflictResolutionResult {
+            winner: resolution.winner(),
+            resolved_at: Utc::now(),
+            resolved_by: resolved_by.to_string(),
+            notes: resolution.note.clone(),
+        });
+        self.storage.update_conflict(&conflict).await?;
+        Ok(conflict)
+    }
+
+    /// Approve (link the pair) or reject a pending link candidate
+    pub async fn review_link_candidate(
+        &mut self,
+        candidate_id: &str,
+        approve: bool,
+        reviewed_by: &str,
+    ) -> Result<LinkCandidate> {
+        if self.mode == SyncMode::Plan {
+            return Err(Error::validation(
+                "Link candidates cannot be reviewed in plan mode",
+            ));
+        }
+        let mut candidate = self
+            .storage
+            .get_link_candidate(candidate_id)
+            .await?
+            .ok_or_else(|| Error::not_found("Link candidate", candidate_id))?;
+        if candidate.status != CandidateStatus::Pending {
+            return Err(Error::validation(format!(
+                "Link candidate {} is not pending",
+                candidate_id
+            )));
+        }
+
+        if approve {
+            let link = candidate.link();
+            self.check_unlinked(&link).await?;
+            self.save_reviewed_link(&link, reviewed_by).await?;
+            candidate.status = CandidateStatus::Approved;
+        } else {
+            candidate.status = CandidateStatus::Rejected;
+        }
+        candidate.reviewed_at = Some(Utc::now());
+        candidate.reviewed_by = Some(reviewed_by.to_string());
+        self.storage.save_link_candidate(&candidate).await?;
+        Ok(candidate)
+    }
+
+    /// Link a known pair of records, e.g. from a bulk import.
+    ///
+    /// Both records must exist and neither may be linked to another record.
+    /// Returns `false` if the pair was already linked.
+    pub async fn import_link(&mut self, link: &IdMapping, imported_by: &str) -> Result<bool> {
+        if self.mode == SyncMode::Plan {
+            return Err(Error::validation("Links cannot be imported in plan mode"));
+        }
+        let mapped = self.config.enabled_mappings().into_iter().any(|m| {
+            m.attio_object == link.attio_object && m.salesforce_object == link.salesforce_object
+        });
+        if !mapped {
+            return Err(Error::mapping(format!(
+                "No enabled mapping for {} ↔ {}",
+                link.attio_object, link.salesforce_object
+            )));
+        }
+        let existing = self
+            .linked(System::Attio, &link.attio_object, &link.attio_id)
+            .await?;
+        if existing.is_some_and(|l| l.salesforce_id == link.salesforce_id) {
+            return Ok(false);
+        }
+        self.check_unlinked(link).await?;
+
+        self.attio
+            .get_record(&link.attio_object, &link.attio_id)
+            .await?;
+        self.salesforce
+            .get_record(&link.salesforce_object, &link.salesforce_id)
+            .await?;
+        self.save_reviewed_link(link, imported_by).await?;
+        Ok(true)
+    }
+
+    /// Fail if either record of a proposed link is already linked
+    async fn check_unlinked(&self, link: &IdMapping) -> Result<()> {
+        let sides = [
+            (System::Attio, &link.attio_object, &link.attio_id),
+            (
+                System::Salesforce,
+                &link.salesforce_object,
+                &link.salesforce_id,
+            ),
+        ];
+        for (system, object, id) in sides {
+            if let Some(existing) = self.linked(system, object, id).await? {
+                return Err(Error::conflict(
+                    object.as_str(),
+                    id.as_str(),
+                    format!(
+                        "already linked to {}",
+                        match system {
+                            System::Attio => &existing.salesforce_id,
+                            System::Salesforce => &existing.attio_id,
+                        }
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Save a link confirmed by a person, closing the other candidates of its records
+    async fn save_reviewed_link(&mut self, link: &IdMapping, reviewed_by: &str) -> Result<()> {
+        self.storage.save_id_mapping(link).await?;
+        let mut result = SyncResult::default();
+        self.backfill_references(link, &mut result).await?;
+
+        let sides = [
+            (&link.attio_object, &link.attio_id),
+            (&link.salesforce_object, &link.salesforce_id),
+        ];
+        for (object, id) in sides {
+            let filter = CandidateFilter {
+                object: Some(object.clone()),
+                record_id: Some(id.clone()),
+                status: Some(CandidateStatus::Pending),
+            };
+            let pending = self
+                .storage
+                .list_link_candidates(&filter, Page::new(1, DEFAULT_PAGE_SIZE))
+                .await?;
+            for mut candidate in pending {
+                candidate.status = if candidate.attio_id == link.attio_id
+                    && candidate.salesforce_id == link.salesforce_id
+                {
+                    CandidateStatus::Approved
+                } else {
+                    CandidateStatus::Rejected
+                };
+                candidate.reviewed_at = Some(Utc::now());
+                candidate.reviewed_by = Some(reviewed_by.to_string());
+                self.storage.save_link_candidate(&candidate).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Work out the writes that apply the chosen value of each conflicting field.
+    ///
+    /// Fields without a choice are left alone. Each written value goes through
+    /// the field's transform toward its target. Also returns the resolved Attio
+    /// and Salesforce values, each in its own system's format, for the base snapshot.
+    fn choice_writes(
+        &self,
+        fields: &[FieldMapping],
+        conflicts: &[FieldConflict],
+        choices: &HashMap<String, FieldChoice>,
+    ) -> Result<(Vec<SideWrite>, FieldValues, FieldValues)> {
+        let mut attio_values = FieldValues::new();
+        let mut sf_values = FieldValues::new();
+        let mut attio_before = FieldValues::new();
+        let mut sf_before = FieldValues::new();
+        let mut sf_payload = serde_json::Map::new();
+        let mut attio_payload = Value::Object(serde_json::Map::new());
+        let mut attio_fields = Vec::new();
+        for field_conflict in conflicts {
+            let Some(choice) = choices.get(&field_conflict.attio_field) else {
+                continue;
+            };
+            let field = fields
+                .iter()
+                .find(|f| f.attio_field == field_conflict.attio_field)
+                .ok_or_else(|| {
+                    Error::mapping(format!(
+                        "Field {} is not mapped",
+                        field_conflict.attio_field
+                    ))
+                })?;
+            let sf_field = field.salesforce_field.clone();
+
+            let (attio_value, sf_value) = match choice {
+                FieldChoice::Attio => (
+                    field_conflict.attio_value.clone(),
+                    self.transform
+                        .transform(&field_conflict.attio_value, &field.transform)?,
+                ),
+                FieldChoice::Salesforce => {
+                    let sf_data =
+                        serde_json::json!({ sf_field.as_str(): field_conflict.salesforce_value });
+                    let attio_data = self
+                        .transform
+                        .salesforce_to_attio(&sf_data, std::slice::from_ref(field))?;
+                    let value = self
+                        .transform
+                        .get_nested_value(&attio_data, &field.attio_field)
+                        .ok_or_else(|| {
+                            Error::transform(
+                                &field.attio_field,
+                                "Salesforce value has no Attio equivalent",
+                            )
+                        })?;
+                    (value, field_conflict.salesforce_value.clone())
+                }
+                // Custom values are in Attio's format, like the Attio value
+                FieldChoice::Custom(value) => (
+                    value.clone(),
+                    self.transform.transform(value, &field.transform)?,
+                ),
+            };
+            if *choice != FieldChoice::Attio {
+                TransformPipeline::set_nested_value(
+                    &mut attio_payload,
+                    &field.attio_field,
+                    attio_value.clone(),
+                );
+                attio_fields.push(field.attio_field.clone());
+            }
+            if *choice != FieldChoice::Salesforce {
+                sf_payload.insert(sf_field.clone(), sf_value.clone());
+            }
+            attio_before.insert(
+                field.attio_field.clone(),
+                field_conflict.attio_value.clone(),
+            );
+            sf_before.insert(sf_field.clone(), field_conflict.salesforce_value.clone());
+            attio_values.insert(field.attio_field.clone(), attio_value);
+            sf_values.insert(sf_field, sf_value);
+        }
+
+        let mut writes = Vec::new();
+        if !sf_payload.is_empty() {
+            writes.push(SideWrite {
+                target: System::Salesforce,
+                fields: sf_payload.keys().cloned().collect(),
+                before: sf_before,
+                payload: Value::Object(sf_payload),
+            });
+        }
+        if !attio_fields.is_empty() {
+            writes.push(SideWrite {
+                target: System::Attio,
+                fields: attio_fields,
+                before: attio_before,
+                payload: attio_payload,
+            });
+        }
+
+        Ok((writes, attio_values, sf_values))
+    }
+
+    /// Update the linked records, or only add the updates to the plan in plan mode
+    async fn write_linked(
+        &mut self,
+        link: &IdMapping,
+        writes: Vec<SideWrite>,
+        result: &mut SyncResult,
+    ) -> Result<()> {
+        for write in writes {
+            if self.mode == SyncMode::Plan {
+                let changes = diff_fields(
+                    &self.transform,
+                    &write.fields,
+                    &write.before,
+                    &write.payload,
+                );
+                result.plan.push(PlannedChange::linked(
+                    AuditAction::Update,
+                    write.target,
+                    link,
+                    changes,
+                ));
+                continue;
+            }
+
+            match write.target {
+                System::Salesforce => {
+                    self.salesforce
+                        .update_record(
+                            &link.salesforce_object,
+                            &link.salesforce_id,
+                            write.payload.clone(),
+                        )
+                        .await?;
+                }
+                System::Attio => {
+                    self.attio
+                        .update_record(&link.attio_object, &link.attio_id, write.payload.clone())
+                        .await?;
+                }
+            }
+            self.record_write(
+                AuditAction::Update,
+                write.target,
+                link,
+                write.fields,
+                &write.before,
+                &write.payload,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Custom merge strategy configured for a mapping, if any
+    fn merge_strategy_for(&self, mapping: &ObjectMapping) -> Result<Option<Rc<dyn MergeStrategy>>> {
+        let configured = mapping.merge_strategy.as_ref();
+        let Some(name) = configured.or(self.config.sync.merge_strategy.as_ref()) else {
+            return Ok(None);
+        };
+        self.merge_strategies
+            .get(name)
+            .cloned()
+            .map(Some)
+            .ok_or_else(|| Error::config(format!("Merge strategy {} is not registered", name)))
+    }
+
+    /// Run incremental sync for one object mapping from a cursor
+    pub async fn incremental_sync(
+        &mut self,
+        mapping: &ObjectMapping,
+        cursor: Option<SyncCursor>,
+    ) -> Result<SyncResult> {
+        let (_, result) = self.incremental_sync_run(mapping, cursor).await?;
+        result
+    }
+
+    /// Sync one mapping incrementally, returning the run recorded for it with its result.
+    ///
+    /// Only failures to record the run or release the lock are returned as errors.
+    pub async fn incremental_sync_run(
+        &mut self,
+        mapping: &ObjectMapping,
+        cursor: Option<SyncCursor>,
+    ) -> Result<(SyncRun, Result<SyncResult>)> {
+        let run = self
+            .start_run(
+                self.config.sync.direction,
+                vec![mapping.attio_object.clone()],
+                None,
+            )
+            .await?;
+
+        let lease = match self.lock_mapping(mapping).await {
+            Ok(lease) => lease,
+            Err(e) => {
+                let result = Err(e);
+                let run = self.close_run(run, &result).await?;
+                return Ok((run, result));
+            }
+        };
+
+        let result = self.incremental_sync_locked(mapping, cursor).await;
+        // Finish the run even if the release fails, so it is not left running
+        let released = self.lock.release(&lease).await;
+        let run = self.close_run(run, &result).await?;
+        released?;
+        Ok((run, result))
     }
 
-    /// Full sync of all records (use sparingly)
-    pub async fn full_sync(&mut self) -> Result<SyncResult> {
-        // TODO: Implement full sync with batching
-        Err(Error::Internal {
-            message: "Full sync not implemented".to_string(),
-        })
+    /// Take the lock of a mapping, failing if another run holds it
+    async fn lock_mapping(&self, mapping: &ObjectMapping) -> Result<LockLease> {
+        self.lock_key(&mapping.key()).await
+    }
+
+    /// Take a lock, failing if another run holds it
+    async fn lock_key(&self, key: &str) -> Result<LockLease> {
+        self.lock
+            .acquire(
+                key,
+                &self.holder_id,
+                chrono::Duration::seconds(DEFAULT_LOCK_TTL_SECS),
+            )
+            .await?
+            .ok_or_else(|| Error::sync(format!("Sync already in progress for {}", key)))
+    }
+
+    /// Extend a lease, failing if it was lost
+    async fn renew_lease(&self, lease: &LockLease) -> Result<LockLease> {
+        self.lock
+            .renew(lease, chrono::Duration::seconds(DEFAULT_LOCK_TTL_SECS))
+            .await?
+            .ok_or_else(|| Error::sync(format!("Lost the sync lock for {}", lease.key)))
+    }
+
+    /// Incremental sync body, run while holding the mapping lock.
+    ///
+    /// Each side is driven by its own object cursor, which only moves to
+    /// modification timestamps actually observed on committed records.
+    async fn incremental_sync_locked(
+        &mut self,
+        mapping: &ObjectMapping,
+        cursor: Option<SyncCursor>,
+    ) -> Result<SyncResult> {
+        let mut cursor = cursor.unwrap_or_else(|| {
+            SyncCursor::from_timestamp(chrono::Utc::now() - chrono::Duration::hours(24))
+        });
+        let sources = match self.config.sync.direction {
+            SyncDirection::AttioToSalesforce => vec![System::Attio],
+            SyncDirection::SalesforceToAttio => vec![System::Salesforce],
+            SyncDirection::Bidirectional => vec![System::Attio, System::Salesforce],
+        };
+
+        let mut result = SyncResult {
+            direction: self.config.sync.direction,
+            ..SyncResult::default()
+        };
+        for source in sources {
+            let object = match source {
+                System::Attio => &mapping.attio_object,
+                System::Salesforce => &mapping.salesforce_object,
+            };
+            // Cursors saved before per-object tracking only have the global timestamp
+            let mut object_cursor = cursor
+                .get_object_cursor(object)
+                .filter(|c| c.last_modified.is_some())
+                .cloned()
+                .unwrap_or_else(|| ObjectCursor::at(object.as_str(), cursor.timestamp));
+            let synced = self
+                .sync_changes_since(source, object, &mut object_cursor)
+                .await?;
+            result.merge(synced);
+            cursor.update_object_cursor(object_cursor);
+        }
+
+        result.cursor = Some(cursor);
+        Ok(result)
+    }
+
+    /// Sync the changes to one source object after its cursor, advancing the cursor.
+    ///
+    /// Changes are re-queried from `cursor_overlap_secs` before the cursor and
+    /// synced oldest first, ties broken by record ID. The cursor stops at the
+    /// record before the first failure, so that record is retried next run,
+    /// until it has failed `max_record_attempts` times and is dead-lettered.
+    /// Deletes in the same window go through the mapping's delete policy.
+    async fn sync_changes_since(
+        &mut self,
+        source: System,
+        object: &str,
+        cursor: &mut ObjectCursor,
+    ) -> Result<SyncResult> {
+        let overlap = chrono::Duration::seconds(self.config.sync.cursor_overlap_secs);
+        let since = cursor.since(overlap).unwrap_or(cursor.last_sync);
+        let mut changes = self.feed.changes_since(source, object, since).await?;
+        // Oldest first, ties broken by record ID
+        changes.sort();
+
+        let mut result = SyncResult::default();
+        let mut committed = true;
+        // Deletes first: a failed one holds the cursor back so it is seen again
+        for deleted in self.feed.deleted_since(source, object, since).await? {
+            let id = &deleted.record_id;
+            let synced = self.sync_delete(source, object, id).await;
+            committed &= self
+                .settle_change(source, object, id, synced, cursor, &mut result)
+                .await?;
+        }
+        for change in &changes {
+            let id = &change.record_id;
+            let synced = match source {
+                System::Attio => self.sync_attio_to_sf(object, id).await,
+                System::Salesforce => self.sync_sf_to_attio(object, id).await,
+            };
+            committed &= self
+                .settle_change(source, object, id, synced, cursor, &mut result)
+                .await?;
+            if let Some(modified) = change.modified.filter(|_| committed) {
+                cursor.advance(modified, id);
+            }
+        }
+        cursor.last_sync = Utc::now();
+        cursor.last_batch_count = changes.len() as u64;
+        Ok(result)
+    }
+
+    /// Count the outcome of syncing one changed record.
+    ///
+    /// Returns whether the cursor may move past the record: it was synced, or
+    /// it has failed `max_record_attempts` times and was dead-lettered.
+    async fn settle_change(
+        &self,
+        source: System,
+        object: &str,
+        record_id: &str,
+        synced: Result<SyncResult>,
+        cursor: &mut ObjectCursor,
+        result: &mut SyncResult,
+    ) -> Result<bool> {
+        let error = match synced {
+            Ok(record) => {
+                result.merge(record);
+                cursor.clear_failures(record_id);
+                return Ok(true);
+            }
+            Err(e) => e,
+        };
+        result.record_error(object, record_id, &error);
+        if cursor.record_failure(record_id) < self.config.sync.max_record_attempts {
+            return Ok(false);
+        }
+
+        if self.mode == SyncMode::Apply {
+            let failure = FailedMessage::new(
+                format!("{}:{}", object, record_id),
+                INCREMENTAL_SYNC_QUEUE,
+                serde_json::json!({
+                    "source": source,
+                    "object": object,
+                    "record_id": record_id
+                }),
+                error.to_string(),
+            );
+            self.storage.record_failure(&failure).await?;
+        }
+        cursor.clear_failures(record_id);
+        Ok(true)
+    }
+
+    /// Full sync of all records (use sparingly).
+    ///
+    /// Pages through every enabled mapping in pages of `batch_size`, saving a
+    /// checkpoint after each page. A rerun resumes from the checkpoint unless
+    /// `restart` is set. The checkpoint has its own lock, so two full syncs
+    /// never resume or discard the same checkpoint.
+    pub async fn full_sync(&mut self, restart: bool) -> Result<SyncResult> {
+        let objects = self
+            .config
+            .ordered_mappings()?
+            .into_iter()
+            .map(|m| m.attio_object)
+            .collect();
+        let run = self
+            .start_run(self.config.sync.direction, objects, None)
+            .await?;
+
+        let mut lease = match self.lock_key(FULL_SYNC_CHECKPOINT_KEY).await {
+            Ok(lease) => lease,
+            Err(e) => return self.finish_run(run, Err(e)).await,
+        };
+        let result = self.full_sync_pages(restart, &mut lease).await;
+        // Finish the run even if the release fails, so it is not left running
+        let released = self.lock.release(&lease).await;
+        let result = self.finish_run(run, result).await;
+        released?;
+        result
+    }
+
+    /// Full sync body: resume or start a checkpoint and sync each mapping under its lock.
+    ///
+    /// `checkpoint_lease` is the checkpoint lock, renewed with each page.
+    async fn full_sync_pages(
+        &mut self,
+        restart: bool,
+        checkpoint_lease: &mut LockLease,
+    ) -> Result<SyncResult> {
+        let direction = self.config.sync.direction;
+        let stored = if restart {
+            if self.mode == SyncMode::Apply {
+                self.storage
+                    .delete_checkpoint(FULL_SYNC_CHECKPOINT_KEY)
+                    .await?;
+            }
+            None
+        } else {
+            self.storage
+                .get_checkpoint(FULL_SYNC_CHECKPOINT_KEY)
+                .await?
+        };
+        // A checkpoint taken in another direction covers different pages, so start over
+        let mut checkpoint = match stored {
+            Some(checkpoint) if checkpoint.direction == direction => checkpoint,
+            _ => FullSyncCheckpoint::new(direction),
+        };
+
+        let mut plan = SyncPlan::default();
+        for mapping in self.config.ordered_mappings()? {
+            let mut lease = self.lock_mapping(&mapping).await?;
+            let synced = self
+                .full_sync_mapping(
+                    &mapping,
+                    &mut checkpoint,
+                    &mut plan,
+                    &mut lease,
+                    checkpoint_lease,
+                )
+                .await;
+            self.lock.release(&lease).await?;
+            synced?;
+        }
+
+        if self.mode == SyncMode::Apply {
+            self.storage
+                .delete_checkpoint(FULL_SYNC_CHECKPOINT_KEY)
+                .await?;
+        }
+        let mut result = checkpoint.result();
+        result.plan = plan;
+        Ok(result)
+    }
+
+    /// Page through the source objects of one mapping, checkpointing and
+    /// renewing the mapping and checkpoint leases after each page
+    async fn full_sync_mapping(
+        &mut self,
+        mapping: &ObjectMapping,
+        checkpoint: &mut FullSyncCheckpoint,
+        plan: &mut SyncPlan,
+        lease: &mut LockLease,
+        checkpoint_lease: &mut LockLease,
+    ) -> Result<()> {
+        let sources = match checkpoint.direction {
+            SyncDirection::AttioToSalesforce => vec![System::Attio],
+            SyncDirection::SalesforceToAttio => vec![System::Salesforce],
+            SyncDirection::Bidirectional => vec![System::Attio, System::Salesforce],
+        };
+        let batch_size = self.config.sync.batch_size;
+
+        // Pair records that already exist on both sides before anything is created
+        let started = [&mapping.attio_object, &mapping.salesforce_object]
+            .iter()
+            .any(|o| checkpoint.is_completed(o) || checkpoint.last_record_id(o).is_some());
+        if !started && !checkpoint.is_matched(&mapping.key()) {
+            self.match_mapping_pages(mapping, checkpoint, lease, checkpoint_lease)
+                .await?;
+        }
+
+        for source in sources {
+            let object = match source {
+                System::Attio => &mapping.attio_object,
+                System::Salesforce => &mapping.salesforce_object,
+            };
+            if checkpoint.is_completed(object) {
+                continue;
+            }
+
+            loop {
+                let after = checkpoint.last_record_id(object).map(str::to_string);
+                let ids = self
+                    .page_record_ids(source, object, after.as_deref(), batch_size)
+                    .await?;
+
+                let mut page = SyncResult::default();
+                for id in &ids {
+                    let synced = match source {
+                        System::Attio => self.sync_attio_to_sf(object, id).await,
+                        System::Salesforce => self.sync_sf_to_attio(object, id).await,
+                    };
+                    match synced {
+                        Ok(record) => page.merge(record),
+                        // One bad record should not stop the rest of the full sync
+                        Err(_) => {
+                            page.records_processed += 1;
+                            page.errors += 1;
+                        }
+                    }
+                }
+                plan.merge(std::mem::take(&mut page.plan));
+
+                let last_page = ids.len() < batch_size;
+                checkpoint.page_done(object, ids.last().cloned(), &page);
+                if last_page {
+                    checkpoint.object_done(object);
+                }
+                if self.mode == SyncMode::Apply {
+                    self.storage
+                        .save_checkpoint(FULL_SYNC_CHECKPOINT_KEY, checkpoint)
+                        .await?;
+                }
+
+                *lease = self.renew_lease(lease).await?;
+                *checkpoint_lease = self.renew_lease(checkpoint_lease).await?;
+                if last_page {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Match the unlinked records of a mapping across both systems.
+    ///
+    /// Pairs at or above the mapping's `auto_link_threshold` are linked; the
+    /// rest are saved as link candidates for review.
+    pub async fn match_mapping(&mut self, mapping: &ObjectMapping) -> Result<SyncResult> {
+        let pairs = self.match_pairs(mapping).await?;
+        self.apply_matches(mapping, pairs).await
+    }
+
+    /// Match a mapping for a full sync, applying the pairs a page at a time in
+    /// Attio ID order and checkpointing and renewing both leases after each page.
+    ///
+    /// Resuming indexes both sides again; records linked before the restart
+    /// are no longer unlinked, and pairs up to the checkpoint are skipped.
+    async fn match_mapping_pages(
+        &mut self,
+        mapping: &ObjectMapping,
+        checkpoint: &mut FullSyncCheckpoint,
+        lease: &mut LockLease,
+        checkpoint_lease: &mut LockLease,
+    ) -> Result<()> {
+        let key = mapping.key();
+        let mut pairs = self.match_pairs(mapping).await?;
+        pairs.sort_by(|a, b| (&a.attio_id, &a.salesforce_id).cmp(&(&b.attio_id, &b.salesforce_id)));
+        if let Some(after) = checkpoint.last_matched_id(&key) {
+            pairs.retain(|pair| pair.attio_id.as_str() > after);
+        }
+
+        for page in pairs.chunks(self.config.sync.batch_size.max(1)) {
+            let matched = self.apply_matches(mapping, page.to_vec()).await?;
+            let last_attio_id = page.last().map(|pair| pair.attio_id.clone());
+            checkpoint.match_page_done(&key, last_attio_id, &matched);
+            if self.mode == SyncMode::Apply {
+                self.storage
+                    .save_checkpoint(FULL_SYNC_CHECKPOINT_KEY, checkpoint)
+                    .await?;
+            }
+            *lease = self.renew_lease(lease).await?;
+            *checkpoint_lease = self.renew_lease(checkpoint_lease).await?;
+        }
+
+        checkpoint.matching_done(&key);
+        if self.mode == SyncMode::Apply {
+            self.storage
+                .save_checkpoint(FULL_SYNC_CHECKPOINT_KEY, checkpoint)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Pair the unlinked records of a mapping, indexing each side a page at a time
+    async fn match_pairs(&mut self, mapping: &ObjectMapping) -> Result<Vec<MatchPair>> {
+        let rules = mapping.effective_match_rules();
+        let attio = self
+            .index_unlinked(System::Attio, &mapping.attio_object, &rules)
+            .await?;
+        let salesforce = self
+            .index_unlinked(System::Salesforce, &mapping.salesforce_object, &rules)
+            .await?;
+        Ok(match_indexes(&attio, &salesforce))
+    }
+
+    /// Match rule keys of every record of an object that has no link yet
+    async fn index_unlinked(
+        &mut self,
+        system: System,
+        object: &str,
+        rules: &[MatchRule],
+    ) -> Result<MatchIndex> {
+        let batch_size = self.config.sync.batch_size;
+        let mut index = MatchIndex::new(rules);
+        let mut after: Option<String> = None;
+        loop {
+            let page: Vec<(String, Value)> = match system {
+                System::Attio => self
+                    .attio
+                    .list_records_after(object, after.as_deref(), batch_size)
+                    .await?
+                    .into_iter()
+                    .map(|record| (record.id.0.clone(), record.to_json()))
+                    .collect(),
+                System::Salesforce => self
+                    .salesforce
+                    .get_records_after(object, after.as_deref(), batch_size)
+                    .await?
+                    .into_iter()
+                    .filter_map(|record| {
+                        let id = record.id?.0;
+                        Some((id, Value::Object(record.fields.into_iter().collect())))
+                    })
+                    .collect(),
+            };
+            let ids: Vec<String> = page.iter().map(|(id, _)| id.clone()).collect();
+            let linked: HashSet<String> = match system {
+                System::Attio => self
+                    .storage
+                    .get_mappings_by_attio_ids(object, &ids)
+                    .await?
+                    .into_iter()
+                    .map(|link| link.attio_id)
+                    .collect(),
+                System::Salesforce => self
+                    .storage
+                    .get_mappings_by_sf_ids(object, &ids)
+                    .await?
+                    .into_iter()
+                    .map(|link| link.salesforce_id)
+                    .collect(),
+            };
+
+            let last_page = page.len() < batch_size;
+            after = ids.last().cloned();
+            for (id, data) in page.iter().filter(|(id, _)| !linked.contains(id)) {
+                index.insert(&self.transform, system, id, data);
+            }
+            if last_page || after.is_none() {
+                return Ok(index);
+            }
+        }
+    }
+
+    /// Link confident pairs and queue the rest for review (only counted in plan mode)
+    async fn apply_matches(
+        &mut self,
+        mapping: &ObjectMapping,
+        pairs: Vec<MatchPair>,
+    ) -> Result<SyncResult> {
+        let mut result = SyncResult::default();
+        for pair in pairs {
+            let mut candidate = LinkCandidate::new(mapping, &pair);
+            let existing = self.storage.get_link_candidate(&candidate.id).await?;
+            match existing {
+                // A reviewer already rejected the pair
+                Some(existing) if existing.status == CandidateStatus::Rejected => continue,
+                Some(existing) => candidate.created_at = existing.created_at,
+                None => {}
+            }
+
+            if pair.confidence >= mapping.auto_link_threshold {
+                result.records_linked += 1;
+                if self.mode == SyncMode::Apply {
+                    self.storage.save_id_mapping(&candidate.link()).await?;
+                }
+            } else {
+                result.link_candidates += 1;
+                if self.mode == SyncMode::Apply {
+                    self.storage.save_link_candidate(&candidate).await?;
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Check whether a record is part of a link candidate waiting for review
+    async fn awaiting_review(&self, object: &str, record_id: &str) -> Result<bool> {
+        let filter = CandidateFilter {
+            object: Some(object.to_string()),
+            record_id: Some(record_id.to_string()),
+            status: Some(CandidateStatus::Pending),
+        };
+        Ok(!self
+            .storage
+            .list_link_candidates(&filter, Page::new(1, 1))
+            .await?
+            .is_empty())
+    }
+
+    /// IDs of the next page of records of a source object, ordered by ID
+    async fn page_record_ids(
+        &mut self,
+        source: System,
+        object: &str,
+        after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<String>> {
+        match source {
+            System::Attio => Ok(self
+                .attio
+                .list_records_after(object, after, limit)
+                .await?
+                .into_iter()
+                .map(|record| record.id.0)
+                .collect()),
+            System::Salesforce => Ok(self
+                .salesforce
+                .get_records_after(object, after, limit)
+                .await?
+                .into_iter()
+                .filter_map(|record| record.id.map(|id| id.0))
+                .collect()),
+        }
+    }
+
+    /// Record the start of a run in the history
+    async fn start_run(
+        &mut self,
+        direction: SyncDirection,
+        objects: Vec<String>,
+        event: Option<String>,
+    ) -> Result<SyncRun> {
+        self.last_run = None;
+        let run = SyncRun::start(self.trigger, direction, objects);
+        if self.mode == SyncMode::Apply {
+            self.storage.record_run(&run).await?;
+        }
+        self.audit = AuditContext {
+            run_id: Some(run.id.clone()),
+            event,
+        };
+        Ok(run)
+    }
+
+    /// Record the outcome of a run, passing the result through
+    async fn finish_run(&mut self, run: SyncRun, result: Result<SyncResult>) -> Result<SyncResult> {
+        self.close_run(run, &result).await?;
+        result
+    }
+
+    /// Record the outcome of a run, returning the finished run
+    async fn close_run(
+        &mut self,
+        mut run: SyncRun,
+        result: &Result<SyncResult>,
+    ) -> Result<SyncRun> {
+        self.audit = AuditContext::default();
+        run.finish(result);
+        if self.mode == SyncMode::Apply {
+            self.storage.record_run(&run).await?;
+        }
+        self.last_run = Some(run.clone());
+        Ok(run)
+    }
+}
+
+/// A pending write to one side of a link
+struct SideWrite {
+    target: System,
+    fields: Vec<String>,
+    before: FieldValues,
+    payload: Value,
+}
+
+/// Both sides of a link, as fetched for a merge
+struct LinkedRecords<'a> {
+    link: IdMapping,
+    source: System,
+    attio_data: &'a Value,
+    salesforce_data: &'a Value,
+    attio_modified: Option<DateTime<Utc>>,
+    salesforce_modified: Option<DateTime<Utc>>,
+}
+
+/// Put a field's base value back (or remove it if the base had none)
+fn restore(values: &mut HashMap<String, Value>, base: &HashMap<String, Value>, field: &str) {
+    match base.get(field) {
+        Some(value) => values.insert(field.to_string(), value.clone()),
+        None => values.remove(field),
+    };
+}
+
+impl SyncResult {
+    /// Count a record that failed to sync, keeping its error
+    pub fn record_error(&mut self, object: &str, record_id: &str, error: &Error) {
+        self.records_processed += 1;
+        self.errors += 1;
+        self.failures
+            .push(format!("{} {}: {}", object, record_id, error));
+    }
+
+    /// Add another result's counters to this one
+    pub fn merge(&mut self, other: SyncResult) {
+        self.records_processed += other.records_processed;
+        self.records_created += other.records_created;
+        self.records_updated += other.records_updated;
+        self.records_deleted += other.records_deleted;
+        self.conflicts += other.conflicts;
+        self.errors += other.errors;
+        self.failures.extend(other.failures);
+        self.records_unchanged += other.records_unchanged;
+        self.echoes_skipped += other.echoes_skipped;
+        self.records_linked += other.records_linked;
+        self.link_candidates += other.link_candidates;
+        self.plan.merge(other.plan);
     }
 }
 
@@ -197,10 +2431,17 @@ impl Default for SyncResult {
             records_processed: 0,
             records_created: 0,
             records_updated: 0,
+            records_deleted: 0,
             conflicts: 0,
             errors: 0,
+            failures: Vec::new(),
+            records_unchanged: 0,
+            echoes_skipped: 0,
+            records_linked: 0,
+            link_candidates: 0,
             direction: SyncDirection::Bidirectional,
             cursor: None,
+            plan: SyncPlan::default(),
         }
     }
 }
@@ -208,6 +2449,513 @@ impl Default for SyncResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sync::feed::{MemoryChangeFeed, RecordChange};
+    use crate::sync::history::RunStatus;
+
+    fn test_config() -> Config {
+        Config {
+            sync: crate::config::SyncConfig::default(),
+            attio: crate::config::AttioConfig {
+                api_key: "test_key".to_string(),
+                webhook_secret: None,
+                api_token_id: None,
+                base_url: "https://api.attio.com".to_string(),
+            },
+            salesforce: crate::config::SalesforceConfig {
+                client_id: "client_id".to_string(),
+                client_secret: "client_secret".to_string(),
+                instance_url: "https://test.salesforce.com".to_string(),
+                refresh_token: None,
+                api_version: "v59.0".to_string(),
+                webhook_secret: None,
+                integration_user_id: None,
+            },
+            mappings: std::collections::HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_incremental_sync_respects_lock() {
+        let lock = Rc::new(MemoryLock::new());
+        let storage = Rc::new(MemoryStorage::new());
+        let mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+        let mut engine = SyncEngine::new(test_config())
+            .with_lock(lock.clone())
+            .with_change_feed(Rc::new(MemoryChangeFeed::new()))
+            .with_storage(storage.clone());
+
+        let lease = lock
+            .acquire(&mapping.key(), "backfill", chrono::Duration::seconds(60))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(engine.incremental_sync(&mapping, None).await.is_err());
+        assert_eq!(engine.last_run().unwrap().status, RunStatus::Failed);
+
+        lock.release(&lease).await.unwrap();
+        assert!(engine.incremental_sync(&mapping, None).await.is_ok());
+        assert_eq!(engine.last_run().unwrap().status, RunStatus::Succeeded);
+
+        let runs = storage.list_runs(None, Page::default()).await.unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].objects, vec!["companies".to_string()]);
+    }
+
+    /// Lock whose releases always fail
+    struct StuckLock(MemoryLock);
+
+    #[async_trait::async_trait(?Send)]
+    impl Lock for StuckLock {
+        async fn acquire(
+            &self,
+            key: &str,
+            holder: &str,
+            ttl: chrono::Duration,
+        ) -> Result<Option<LockLease>> {
+            self.0.acquire(key, holder, ttl).await
+        }
+
+        async fn renew(
+            &self,
+            lease: &LockLease,
+            ttl: chrono::Duration,
+        ) -> Result<Option<LockLease>> {
+            self.0.renew(lease, ttl).await
+        }
+
+        async fn release(&self, _lease: &LockLease) -> Result<()> {
+            Err(Error::sync("release failed"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_release_still_finishes_run() {
+        let storage = Rc::new(MemoryStorage::new());
+        let mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+        let mut engine = SyncEngine::new(test_config())
+            .with_lock(Rc::new(StuckLock(MemoryLock::new())))
+            .with_change_feed(Rc::new(MemoryChangeFeed::new()))
+            .with_storage(storage.clone());
+
+        let err = engine.incremental_sync(&mapping, None).await.unwrap_err();
+        assert_eq!(err.to_string(), "Sync error: release failed");
+        let run = engine.last_run().unwrap();
+        assert_eq!(run.status, RunStatus::Succeeded);
+        let stored = storage.get_run(&run.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, RunStatus::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_cursor_stops_before_failed_record() {
+        let feed = Rc::new(MemoryChangeFeed::new());
+        let mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+        let start = Utc::now() - chrono::Duration::hours(1);
+        let mut config = test_config();
+        config.sync.direction = SyncDirection::SalesforceToAttio;
+        let mut engine = SyncEngine::new(config).with_change_feed(feed.clone());
+
+        // Too old even for the overlap window
+        feed.push(
+            System::Salesforce,
+            "Account",
+            RecordChange {
+                modified: Some(start - chrono::Duration::hours(1)),
+                record_id: "001old".to_string(),
+            },
+        );
+        // Fails to sync (the client cannot fetch it), so the cursor must not pass it
+        feed.push(
+            System::Salesforce,
+            "Account",
+            RecordChange {
+                modified: Some(start + chrono::Duration::minutes(5)),
+                record_id: "001new".to_string(),
+            },
+        );
+
+        let cursor = SyncCursor::from_timestamp(start);
+        let result = engine
+            .incremental_sync(&mapping, Some(cursor))
+            .await
+            .unwrap();
+        assert_eq!(result.records_processed, 1);
+        assert_eq!(result.errors, 1);
+        assert_eq!(engine.last_run().unwrap().status, RunStatus::Partial);
+
+        assert_eq!(result.failures.len(), 1);
+        assert!(result.failures[0].starts_with("Account 001new: "));
+
+        let next = result.cursor.unwrap();
+        let account = next.get_object_cursor("Account").unwrap();
+        assert_eq!(account.last_modified, Some(start));
+        assert_eq!(account.last_record_id, None);
+        assert_eq!(account.failed_attempts["001new"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_failing_record_is_dead_lettered() {
+        let feed = Rc::new(MemoryChangeFeed::new());
+        let storage = Rc::new(MemoryStorage::new());
+        let mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+        let start = Utc::now() - chrono::Duration::hours(1);
+        let modified = start + chrono::Duration::minutes(5);
+        let mut config = test_config();
+        config.sync.direction = SyncDirection::SalesforceToAttio;
+        config.sync.max_record_attempts = 2;
+        let mut engine = SyncEngine::new(config)
+            .with_change_feed(feed.clone())
+            .with_storage(storage.clone());
+        feed.push(
+            System::Salesforce,
+            "Account",
+            RecordChange {
+                modified: Some(modified),
+                record_id: "001bad".to_string(),
+            },
+        );
+
+        // The first failure holds the cursor back
+        let result = engine
+            .incremental_sync(&mapping, Some(SyncCursor::from_timestamp(start)))
+            .await
+            .unwrap();
+        let cursor = result.cursor.unwrap();
+        let account = cursor.get_object_cursor("Account").unwrap();
+        assert_eq!(account.last_modified, Some(start));
+        assert!(storage.list_failures(10).await.unwrap().is_empty());
+
+        // The last allowed attempt dead-letters the record and moves past it
+        let result = engine
+            .incremental_sync(&mapping, Some(cursor))
+            .await
+            .unwrap();
+        assert_eq!(result.errors, 1);
+        let cursor = result.cursor.unwrap();
+        let account = cursor.get_object_cursor("Account").unwrap();
+        assert_eq!(account.last_modified, Some(modified));
+        assert_eq!(account.last_record_id.as_deref(), Some("001bad"));
+        assert!(account.failed_attempts.is_empty());
+
+        let failures = storage.list_failures(10).await.unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].message_id, "Account:001bad");
+        assert_eq!(failures[0].queue, INCREMENTAL_SYNC_QUEUE);
+        assert_eq!(failures[0].body["record_id"], "001bad");
+    }
+
+    #[tokio::test]
+    async fn test_full_sync_resumes_from_checkpoint() {
+        let storage = Rc::new(MemoryStorage::new());
+        let mut engine = SyncEngine::new(test_config()).with_storage(storage.clone());
+
+        // Every object already paged through: the rerun only finishes the job
+        let mut checkpoint = FullSyncCheckpoint::new(SyncDirection::Bidirectional);
+        checkpoint.records_processed = 7;
+        for mapping in engine.config().enabled_mappings() {
+            checkpoint.object_done(&mapping.attio_object);
+            checkpoint.object_done(&mapping.salesforce_object);
+        }
+        storage
+            .save_checkpoint(FULL_SYNC_CHECKPOINT_KEY, &checkpoint)
+            .await
+            .unwrap();
+        let result = engine.full_sync(false).await.unwrap();
+        assert_eq!(result.records_processed, 7);
+        assert!(storage
+            .get_checkpoint(FULL_SYNC_CHECKPOINT_KEY)
+            .await
+            .unwrap()
+            .is_none());
+
+        // A failed page leaves the checkpoint for the next run; --restart discards it
+        let checkpoint = FullSyncCheckpoint::new(SyncDirection::Bidirectional);
+        storage
+            .save_checkpoint(FULL_SYNC_CHECKPOINT_KEY, &checkpoint)
+            .await
+            .unwrap();
+        assert!(engine.full_sync(false).await.is_err());
+        assert_eq!(engine.last_run().unwrap().status, RunStatus::Failed);
+        assert!(storage
+            .get_checkpoint(FULL_SYNC_CHECKPOINT_KEY)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(engine.full_sync(true).await.is_err());
+        assert!(storage
+            .get_checkpoint(FULL_SYNC_CHECKPOINT_KEY)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_full_sync_respects_checkpoint_lock() {
+        let storage = Rc::new(MemoryStorage::new());
+        let lock = Rc::new(MemoryLock::new());
+        let mut engine = SyncEngine::new(test_config())
+            .with_storage(storage.clone())
+            .with_lock(lock.clone());
+        let checkpoint = FullSyncCheckpoint::new(SyncDirection::Bidirectional);
+        storage
+            .save_checkpoint(FULL_SYNC_CHECKPOINT_KEY, &checkpoint)
+            .await
+            .unwrap();
+        let lease = lock
+            .acquire(
+                FULL_SYNC_CHECKPOINT_KEY,
+                "other-run",
+                chrono::Duration::seconds(60),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Another full sync owns the checkpoint: neither resume nor restart touches it
+        for restart in [false, true] {
+            let err = engine.full_sync(restart).await.unwrap_err();
+            assert!(err.to_string().contains("already in progress"));
+            assert_eq!(engine.last_run().unwrap().status, RunStatus::Failed);
+            assert!(storage
+                .get_checkpoint(FULL_SYNC_CHECKPOINT_KEY)
+                .await
+                .unwrap()
+                .is_some());
+        }
+
+        // Once released, the run takes the lock and gives it back when done
+        lock.release(&lease).await.unwrap();
+        assert!(engine.full_sync(true).await.is_err());
+        assert!(lock
+            .acquire(
+                FULL_SYNC_CHECKPOINT_KEY,
+                "other-run",
+                chrono::Duration::seconds(60)
+            )
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_echoes_are_skipped() {
+        let mut config = test_config();
+        config.attio.api_token_id = Some("tok_bridge".to_string());
+        config.salesforce.integration_user_id = Some("005000000000001AAA".to_string());
+        let mut engine = SyncEngine::new(config);
+
+        let attio_event: AttioWebhookEvent = serde_json::from_value(serde_json::json!({
+            "event_type": "record_updated",
+            "workspace_id": "ws_1",
+            "object": "companies",
+            "record_id": "rec_1",
+            "timestamp": "2024-01-01T00:00:00Z",
+            "actor": {"type": "api-token", "id": "tok_bridge"}
+        }))
+        .unwrap();
+        let result = engine.handle_attio_event(&attio_event).await.unwrap();
+        assert_eq!(result.echoes_skipped, 1);
+
+        let sf_event = crate::salesforce::webhooks::parse_webhook(
+            br#"{"entityName": "Account", "recordIds": ["001xxx"], "changeType": "UPDATE", "commitUser": "005000000000001"}"#,
+        )
+        .unwrap();
+        let result = engine.handle_salesforce_event(&sf_event).await.unwrap();
+        assert_eq!(result.echoes_skipped, 1);
+    }
+
+    #[tokio::test]
+    async fn test_fingerprint_suppresses_only_exact_echoes() {
+        let storage = Rc::new(MemoryStorage::new());
+        let mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+        let link = IdMapping::new("companies", "rec_1", "Account", "001xxx");
+        storage.save_id_mapping(&link).await.unwrap();
+        let base = BaseSnapshot::new(
+            &link,
+            HashMap::from([
+                ("name".to_string(), serde_json::json!("Acme")),
+                ("description".to_string(), serde_json::json!("Old")),
+            ]),
+            HashMap::from([
+                ("Name".to_string(), serde_json::json!("Acme")),
+                ("Description".to_string(), serde_json::json!("Old")),
+            ]),
+        );
+        storage.save_base_snapshot(&base).await.unwrap();
+        let mut engine = SyncEngine::new(test_config())
+            .with_storage(storage.clone())
+            .with_mode(SyncMode::Plan);
+        let written = serde_json::json!({"Name": "Acme", "Description": "Old"});
+
+        // Nothing written yet
+        assert!(!engine
+            .is_recent_write(System::Salesforce, "Account", "001xxx", &written)
+            .await
+            .unwrap());
+
+        // We wrote Name; the record still holding what we synced is our echo
+        engine
+            .remember_write(
+                System::Salesforce,
+                "Account",
+                "001xxx",
+                vec!["Name".to_string()],
+                &written,
+            )
+            .await
+            .unwrap();
+        let echo = serde_json::json!({"Name": "Acme", "Description": "Old", "Phone": "1"});
+        assert!(engine
+            .is_recent_write(System::Salesforce, "Account", "001xxx", &echo)
+            .await
+            .unwrap());
+
+        // A change on top of our write, or a cleared field, is a real change
+        for data in [
+            serde_json::json!({"Name": "Acme Inc", "Description": "Old"}),
+            serde_json::json!({"Description": "Old"}),
+        ] {
+            assert!(!engine
+                .is_recent_write(System::Salesforce, "Account", "001xxx", &data)
+                .await
+                .unwrap());
+        }
+
+        // So is a change to a mapped field we did not write, and it is synced
+        let edited = serde_json::json!({"Name": "Acme", "Description": "New"});
+        assert!(!engine
+            .is_recent_write(System::Salesforce, "Account", "001xxx", &edited)
+            .await
+            .unwrap());
+        let attio_data = serde_json::json!({"name": "Acme", "description": "Old"});
+        let mut result = SyncResult::default();
+        let linked = LinkedRecords {
+            link,
+            source: System::Salesforce,
+            attio_data: &attio_data,
+            salesforce_data: &edited,
+            attio_modified: None,
+            salesforce_modified: None,
+        };
+        engine
+            .reconcile(&mapping, linked, &mut result)
+            .await
+            .unwrap();
+        assert_eq!(result.plan.updates.len(), 1);
+        assert_eq!(result.plan.updates[0].changes[0].field, "description");
+        assert_eq!(
+            result.plan.updates[0].changes[0].after,
+            serde_json::json!("New")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_formatting_differences_are_not_written() {
+        let storage = Rc::new(MemoryStorage::new());
+        let mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+        let link = IdMapping::new("companies", "rec_1", "Account", "001xxx");
+        storage.save_id_mapping(&link).await.unwrap();
+        let base = BaseSnapshot::new(
+            &link,
+            HashMap::from([
+                ("name".to_string(), serde_json::json!("Acme")),
+                ("employee_range".to_string(), serde_json::json!(50)),
+            ]),
+            HashMap::from([
+                ("Name".to_string(), serde_json::json!("Acme")),
+                ("NumberOfEmployees".to_string(), serde_json::json!(50)),
+            ]),
+        );
+        storage.save_base_snapshot(&base).await.unwrap();
+        let mut engine = SyncEngine::new(test_config()).with_storage(storage.clone());
+
+        // Salesforce reformatted both values; a write would fail against the stub client
+        let attio_data = serde_json::json!({"name": "Acme", "employee_range": 50});
+        let sf_data = serde_json::json!({"Name": "Acme ", "NumberOfEmployees": 50.0});
+        let mut result = SyncResult::default();
+        let linked = LinkedRecords {
+            link,
+            source: System::Salesforce,
+            attio_data: &attio_data,
+            salesforce_data: &sf_data,
+            attio_modified: None,
+            salesforce_modified: None,
+        };
+        engine
+            .reconcile(&mapping, linked, &mut result)
+            .await
+            .unwrap();
+        assert_eq!(result.records_unchanged, 1);
+        assert_eq!(result.records_updated, 0);
+        assert!(storage
+            .list_audit_entries("Account", "001xxx", Page::default())
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(storage
+            .get_fingerprint(System::Salesforce, "Account", "001xxx")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_only_completed_writes_are_audited() {
+        let storage = Rc::new(MemoryStorage::new());
+        let mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+        let link = IdMapping::new("companies", "rec_1", "Account", "001xxx");
+        let base = BaseSnapshot::new(
+            &link,
+            HashMap::from([("name".to_string(), serde_json::json!("Acme"))]),
+            HashMap::from([("Name".to_string(), serde_json::json!("Acme"))]),
+        );
+        storage.save_base_snapshot(&base).await.unwrap();
+        let attio_data = serde_json::json!({"name": "Acme Inc"});
+        let sf_data = serde_json::json!({"Name": "Acme"});
+        let linked = || LinkedRecords {
+            link: link.clone(),
+            source: System::Attio,
+            attio_data: &attio_data,
+            salesforce_data: &sf_data,
+            attio_modified: None,
+            salesforce_modified: None,
+        };
+
+        // Planned writes carry their diff but are not audited
+        let mut engine = SyncEngine::new(test_config())
+            .with_storage(storage.clone())
+            .with_mode(SyncMode::Plan);
+        let mut result = SyncResult::default();
+        engine
+            .reconcile(&mapping, linked(), &mut result)
+            .await
+            .unwrap();
+        assert_eq!(
+            result.plan.updates[0].changes,
+            vec![crate::sync::audit::FieldChange {
+                field: "Name".to_string(),
+                before: Some(serde_json::json!("Acme")),
+                after: serde_json::json!("Acme Inc"),
+            }]
+        );
+
+        // A write the stub client rejects leaves no audit entry or fingerprint
+        let mut engine = SyncEngine::new(test_config()).with_storage(storage.clone());
+        let mut result = SyncResult::default();
+        assert!(engine
+            .reconcile(&mapping, linked(), &mut result)
+            .await
+            .is_err());
+        assert!(storage
+            .list_audit_entries("Account", "001xxx", Page::default())
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(storage
+            .get_fingerprint(System::Salesforce, "Account", "001xxx")
+            .await
+            .unwrap()
+            .is_none());
+    }
 
     #[test]
     fn test_sync_result_default() {
@@ -215,4 +2963,818 @@ mod tests {
         assert_eq!(result.records_processed, 0);
         assert_eq!(result.direction, SyncDirection::Bidirectional);
     }
+
+    #[tokio::test]
+    async fn test_resolve_conflict_validates_choices() {
+        let storage = Rc::new(MemoryStorage::new());
+        let mut engine = SyncEngine::new(test_config()).with_storage(storage.clone());
+
+        let mut conflict = ConflictRecord::new(
+            "companies",
+            "rec_1",
+            "Account",
+            "001xxx",
+            serde_json::json!({}),
+            serde_json::json!({}),
+        );
+        conflict.add_field_conflict(FieldConflict {
+            attio_field: "name".to_string(),
+            salesforce_field: "Name".to_string(),
+            attio_value: serde_json::json!("Acme"),
+            salesforce_value: serde_json::json!("Acme Corp"),
+            attio_modified_at: None,
+            salesforce_modified_at: None,
+        });
+        storage.save_conflict(&conflict).await.unwrap();
+
+        let empty = ManualResolution {
+            fields: HashMap::new(),
+            note: None,
+        };
+        assert!(matches!(
+            engine.resolve_conflict(&conflict.id, &empty, "ops").await,
+            Err(Error::Validation { .. })
+        ));
+        assert!(matches!(
+            engine.resolve_conflict("missing", &empty, "ops").await,
+            Err(Error::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_conflict_with_default_mappings() {
+        let vars: HashMap<&str, &str> = [
+            ("ATTIO_API_KEY", "attio_key"),
+            ("SALESFORCE_CLIENT_ID", "client_id"),
+            ("SALESFORCE_CLIENT_SECRET", "client_secret"),
+            ("SALESFORCE_INSTANCE_URL", "https://test.salesforce.com"),
+        ]
+        .into();
+        let config = Config::from_lookup(|name| vars.get(name).map(|v| v.to_string())).unwrap();
+        assert!(config.mappings.is_empty());
+        let storage = Rc::new(MemoryStorage::new());
+        let mut engine = SyncEngine::new(config).with_storage(storage.clone());
+
+        let mut conflict = ConflictRecord::new(
+            "companies",
+            "rec_1",
+            "Account",
+            "001xxx",
+            serde_json::json!({}),
+            serde_json::json!({}),
+        );
+        conflict.add_field_conflict(FieldConflict {
+            attio_field: "name".to_string(),
+            salesforce_field: "Name".to_string(),
+            attio_value: serde_json::json!("Acme"),
+            salesforce_value: serde_json::json!("Acme Corp"),
+            attio_modified_at: None,
+            salesforce_modified_at: None,
+        });
+        storage.save_conflict(&conflict).await.unwrap();
+        let resolution = ManualResolution {
+            fields: HashMap::from([("name".to_string(), FieldChoice::Attio)]),
+            note: None,
+        };
+
+        // The default mapping is found and the resolution gets as far as the
+        // Salesforce write, which fails without a token
+        let err = engine
+            .resolve_conflict(&conflict.id, &resolution, "ops")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::OAuth { .. }), "{:?}", err);
+        let stored = storage.get_conflict(&conflict.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, ConflictStatus::Pending);
+
+        // Objects outside the default mappings are still rejected
+        let mut unmapped = conflict.clone();
+        unmapped.id = "other".to_string();
+        unmapped.attio_object = "widgets".to_string();
+        storage.save_conflict(&unmapped).await.unwrap();
+        assert!(matches!(
+            engine.resolve_conflict("other", &resolution, "ops").await,
+            Err(Error::Mapping { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolved_values_do_not_conflict_again() {
+        let mut config = test_config();
+        config.sync.conflict_resolution = crate::config::ConflictResolution::Manual;
+        let storage = Rc::new(MemoryStorage::new());
+        let mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+        let link = IdMapping::new("companies", "rec_1", "Account", "001xxx");
+        let mut engine = SyncEngine::new(config)
+            .with_storage(storage.clone())
+            .with_mode(SyncMode::Plan);
+
+        let field_conflict =
+            |attio: &str, sf: &str, attio_value: &str, sf_value: &str| FieldConflict {
+                attio_field: attio.to_string(),
+                salesforce_field: sf.to_string(),
+                attio_value: serde_json::json!(attio_value),
+                salesforce_value: serde_json::json!(sf_value),
+                attio_modified_at: None,
+                salesforce_modified_at: None,
+            };
+        let conflicts = vec![
+            field_conflict("name", "Name", "Acme", "Acme Corp"),
+            field_conflict(
+                "primary_location.country_code",
+                "BillingCountry",
+                "FR",
+                "Spain",
+            ),
+        ];
+        let choices = HashMap::from([
+            ("name".to_string(), FieldChoice::Salesforce),
+            (
+                "primary_location.country_code".to_string(),
+                FieldChoice::Custom(serde_json::json!("DE")),
+            ),
+        ]);
+        let (writes, attio_values, sf_values) = engine
+            .choice_writes(&mapping.fields, &conflicts, &choices)
+            .unwrap();
+
+        // Salesforce gets the country name, Attio the code
+        let sf_write = writes.iter().find(|w| w.target == System::Salesforce);
+        let attio_write = writes.iter().find(|w| w.target == System::Attio);
+        assert_eq!(
+            sf_write.unwrap().payload,
+            serde_json::json!({"BillingCountry": "Germany"})
+        );
+        assert_eq!(
+            attio_write.unwrap().payload,
+            serde_json::json!({"name": "Acme Corp", "primary_location": {"country_code": "DE"}})
+        );
+
+        // Re-running the sync over what was written finds nothing to do
+        storage
+            .save_base_snapshot(&BaseSnapshot::new(&link, attio_values, sf_values))
+            .await
+            .unwrap();
+        let attio_data =
+            serde_json::json!({"name": "Acme Corp", "primary_location": {"country_code": "DE"}});
+        let sf_data = serde_json::json!({"Name": "Acme Corp", "BillingCountry": "Germany"});
+        let mut result = SyncResult::default();
+        let linked = LinkedRecords {
+            link,
+            source: System::Salesforce,
+            attio_data: &attio_data,
+            salesforce_data: &sf_data,
+            attio_modified: None,
+            salesforce_modified: None,
+        };
+        engine
+            .reconcile(&mapping, linked, &mut result)
+            .await
+            .unwrap();
+        assert_eq!(result.conflicts, 0);
+        assert!(result.plan.is_empty());
+    }
+
+    struct LargerAmount;
+
+    impl MergeStrategy for LargerAmount {
+        fn name(&self) -> &str {
+            "larger_amount"
+        }
+
+        fn merge(&self, context: &MergeContext<'_>) -> Result<crate::sync::MergeOutcome> {
+            let fields = context
+                .conflicts
+                .iter()
+                .map(|c| {
+                    let amount = |v: &Value| v.as_f64().unwrap_or_default();
+                    let choice = if amount(&c.salesforce_value) > amount(&c.attio_value) {
+                        FieldChoice::Salesforce
+                    } else {
+                        FieldChoice::Attio
+                    };
+                    (c.attio_field.clone(), choice)
+                })
+                .collect();
+            Ok(crate::sync::MergeOutcome {
+                fields,
+                winner: ConflictWinner::Merged,
+            })
+        }
+    }
+
+    #[test]
+    fn test_merge_strategy_selected_by_name() {
+        let mut config = test_config();
+        config.sync.merge_strategy = Some("larger_amount".to_string());
+        let mut mapping = ObjectMapping::from_defaults("deals", "Opportunity").unwrap();
+
+        let engine = SyncEngine::new(config.clone());
+        assert!(matches!(
+            engine.merge_strategy_for(&mapping),
+            Err(Error::Config { .. })
+        ));
+
+        let engine = SyncEngine::new(config).with_merge_strategy(Rc::new(LargerAmount));
+        let strategy = engine.merge_strategy_for(&mapping).unwrap().unwrap();
+        assert_eq!(strategy.name(), "larger_amount");
+
+        mapping.merge_strategy = Some("other".to_string());
+        assert!(engine.merge_strategy_for(&mapping).is_err());
+    }
+
+    /// Picks the given side for `name` only, or fails
+    struct NameOnly(Option<FieldChoice>);
+
+    impl MergeStrategy for NameOnly {
+        fn name(&self) -> &str {
+            "name_only"
+        }
+
+        fn merge(&self, _context: &MergeContext<'_>) -> Result<crate::sync::MergeOutcome> {
+            let choice = self.0.clone().ok_or_else(|| Error::Internal {
+                message: "merge failed".to_string(),
+            })?;
+            Ok(crate::sync::MergeOutcome {
+                fields: HashMap::from([("name".to_string(), choice)]),
+                winner: ConflictWinner::Merged,
+            })
+        }
+    }
+
+    /// Reconcile a companies pair in plan mode against a base of
+    /// `Acme`/`Old` on both sides, merging with `strategy`
+    async fn reconcile_with(
+        strategy: NameOnly,
+        attio_data: Value,
+        sf_data: Value,
+    ) -> Result<SyncResult> {
+        let mut config = test_config();
+        config.sync.merge_strategy = Some("name_only".to_string());
+        let mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+        let link = IdMapping::new("companies", "rec_1", "Account", "001xxx");
+        let storage = Rc::new(MemoryStorage::new());
+        storage
+            .save_base_snapshot(&BaseSnapshot::new(
+                &link,
+                HashMap::from([
+                    ("name".to_string(), serde_json::json!("Acme")),
+                    ("description".to_string(), serde_json::json!("Old")),
+                ]),
+                HashMap::from([
+                    ("Name".to_string(), serde_json::json!("Acme")),
+                    ("Description".to_string(), serde_json::json!("Old")),
+                ]),
+            ))
+            .await?;
+        let mut engine = SyncEngine::new(config)
+            .with_storage(storage)
+            .with_merge_strategy(Rc::new(strategy))
+            .with_mode(SyncMode::Plan);
+
+        let mut result = SyncResult::default();
+        let linked = LinkedRecords {
+            link,
+            source: System::Salesforce,
+            attio_data: &attio_data,
+            salesforce_data: &sf_data,
+            attio_modified: None,
+            salesforce_modified: None,
+        };
+        engine.reconcile(&mapping, linked, &mut result).await?;
+        Ok(result)
+    }
+
+    #[tokio::test]
+    async fn test_merge_strategy_outcomes() {
+        let attio = serde_json::json!({"name": "Acme Inc", "description": "Old"});
+        let sf = serde_json::json!({"Name": "Acme Corp", "Description": "Old"});
+
+        // A failing strategy fails the record
+        let failed = reconcile_with(NameOnly(None), attio.clone(), sf.clone()).await;
+        assert!(matches!(failed, Err(Error::Internal { .. })));
+
+        // A merged field resolves the conflict and is written to the other side
+        let result = reconcile_with(NameOnly(Some(FieldChoice::Salesforce)), attio, sf)
+            .await
+            .unwrap();
+        assert_eq!(result.conflicts, 1);
+        assert_eq!(
+            result.plan.conflicts[0].status,
+            ConflictStatus::AutoResolved
+        );
+        assert_eq!(result.plan.updates.len(), 1);
+        assert_eq!(result.plan.updates[0].target_object, "companies");
+
+        // Fields the strategy leaves out stay queued for manual resolution
+        let attio = serde_json::json!({"name": "Acme Inc", "description": "Newer"});
+        let sf = serde_json::json!({"Name": "Acme Corp", "Description": "New"});
+        let result = reconcile_with(NameOnly(Some(FieldChoice::Attio)), attio, sf)
+            .await
+            .unwrap();
+        let conflict = &result.plan.conflicts[0];
+        assert_eq!(conflict.status, ConflictStatus::Pending);
+        assert!(conflict.resolution.is_none());
+        assert_eq!(conflict.conflicting_fields.len(), 1);
+        assert_eq!(conflict.conflicting_fields[0].attio_field, "description");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_partially_merged_conflict() {
+        let attio = serde_json::json!({"name": "Acme Inc", "description": "Newer"});
+        let sf = serde_json::json!({"Name": "Acme Corp", "Description": "New"});
+        let result = reconcile_with(NameOnly(Some(FieldChoice::Attio)), attio, sf)
+            .await
+            .unwrap();
+        let conflict = result.plan.conflicts[0].clone();
+
+        let storage = Rc::new(MemoryStorage::new());
+        storage.save_conflict(&conflict).await.unwrap();
+        let mut engine = SyncEngine::new(test_config()).with_storage(storage);
+
+        // The name merged by the strategy no longer needs a choice
+        let with_name = ManualResolution {
+            fields: HashMap::from([
+                ("name".to_string(), FieldChoice::Attio),
+                ("description".to_string(), FieldChoice::Attio),
+            ]),
+            note: None,
+        };
+        assert!(matches!(
+            engine
+                .resolve_conflict(&conflict.id, &with_name, "ops")
+                .await,
+            Err(Error::Validation { .. })
+        ));
+
+        // Choosing only the open field passes validation and reaches the write
+        let description_only = ManualResolution {
+            fields: HashMap::from([("description".to_string(), FieldChoice::Attio)]),
+            note: None,
+        };
+        assert!(description_only.validate(&conflict).is_ok());
+        assert!(matches!(
+            engine
+                .resolve_conflict(&conflict.id, &description_only, "ops")
+                .await,
+            Err(Error::OAuth { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_delete_policies() {
+        let storage = Rc::new(MemoryStorage::new());
+        let link = IdMapping::new("companies", "rec_1", "Account", "001xxx");
+        storage.save_id_mapping(&link).await.unwrap();
+        let engine_with = |on_delete: DeletePolicy, mode: SyncMode| {
+            let mut mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+            mapping.on_delete = on_delete;
+            let mut config = test_config();
+            config.mappings.insert("companies".to_string(), mapping);
+            SyncEngine::new(config)
+                .with_storage(storage.clone())
+                .with_change_feed(Rc::new(MemoryChangeFeed::new()))
+                .with_mode(mode)
+        };
+        let flag = DeletePolicy::Flag {
+            salesforce_field: Some("Attio_Deleted__c".to_string()),
+            attio_field: None,
+        };
+
+        let mut engine = engine_with(DeletePolicy::Propagate, SyncMode::Plan);
+        let result = engine
+            .sync_delete(System::Attio, "companies", "rec_1")
+            .await
+            .unwrap();
+        assert_eq!(result.plan.deletes[0].target_id.as_deref(), Some("001xxx"));
+
+        let mut engine = engine_with(flag.clone(), SyncMode::Plan);
+        let result = engine
+            .sync_delete(System::Attio, "companies", "rec_1")
+            .await
+            .unwrap();
+        assert_eq!(result.plan.updates[0].changes[0].field, "Attio_Deleted__c");
+        assert_eq!(result.plan.updates[0].changes[0].after, Value::Bool(true));
+
+        // No Attio flag field configured for Salesforce deletes
+        let mut engine = engine_with(flag, SyncMode::Apply);
+        assert!(matches!(
+            engine
+                .sync_delete(System::Salesforce, "Account", "001xxx")
+                .await,
+            Err(Error::Config { .. })
+        ));
+
+        let mut engine = engine_with(DeletePolicy::Ignore, SyncMode::Apply);
+        engine
+            .sync_delete(System::Salesforce, "Account", "001xxx")
+            .await
+            .unwrap();
+        assert!(storage
+            .get_mapping_by_sf_id("Account", "001xxx")
+            .await
+            .unwrap()
+            .is_some());
+
+        let mut engine = engine_with(DeletePolicy::Unlink, SyncMode::Apply);
+        engine
+            .sync_delete(System::Salesforce, "Account", "001xxx")
+            .await
+            .unwrap();
+        assert!(storage
+            .get_mapping_by_attio_id("companies", "rec_1")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_policy_edge_cases() {
+        let storage = Rc::new(MemoryStorage::new());
+        let link = IdMapping::new("companies", "rec_1", "Account", "001xxx");
+        storage.save_id_mapping(&link).await.unwrap();
+        let engine_with = |on_delete: DeletePolicy, mode: SyncMode| {
+            let mut mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+            mapping.on_delete = on_delete;
+            let mut config = test_config();
+            config.mappings.insert("companies".to_string(), mapping);
+            SyncEngine::new(config)
+                .with_storage(storage.clone())
+                .with_change_feed(Rc::new(MemoryChangeFeed::new()))
+                .with_mode(mode)
+        };
+        let linked = || async {
+            storage
+                .get_mapping_by_attio_id("companies", "rec_1")
+                .await
+                .unwrap()
+                .is_some()
+        };
+
+        // Deletes of records that were never linked change nothing, whatever the policy
+        for policy in [
+            DeletePolicy::Propagate,
+            DeletePolicy::Unlink,
+            DeletePolicy::Flag {
+                salesforce_field: None,
+                attio_field: None,
+            },
+        ] {
+            let mut engine = engine_with(policy, SyncMode::Apply);
+            let result = engine
+                .sync_delete(System::Attio, "companies", "rec_unlinked")
+                .await
+                .unwrap();
+            assert_eq!(result.records_unchanged, 1);
+            assert!(result.plan.is_empty());
+        }
+
+        // Unmapped objects are an error
+        let mut engine = engine_with(DeletePolicy::Unlink, SyncMode::Apply);
+        assert!(matches!(
+            engine.sync_delete(System::Attio, "widgets", "rec_1").await,
+            Err(Error::Mapping { .. })
+        ));
+
+        // A flag with no field for the target fails before unlinking
+        let flag = DeletePolicy::Flag {
+            salesforce_field: None,
+            attio_field: Some("deleted_in_salesforce".to_string()),
+        };
+        let mut engine = engine_with(flag, SyncMode::Apply);
+        assert!(matches!(
+            engine
+                .sync_delete(System::Attio, "companies", "rec_1")
+                .await,
+            Err(Error::Config { .. })
+        ));
+        assert!(linked().await);
+
+        // A propagated delete the client rejects keeps the link and is not audited
+        let mut engine = engine_with(DeletePolicy::Propagate, SyncMode::Apply);
+        assert!(engine
+            .sync_delete(System::Attio, "companies", "rec_1")
+            .await
+            .is_err());
+        assert!(linked().await);
+        assert!(storage
+            .list_audit_entries("Account", "001xxx", Page::default())
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Unlink only plans nothing, then drops the link without writing
+        let mut engine = engine_with(DeletePolicy::Unlink, SyncMode::Plan);
+        let result = engine
+            .sync_delete(System::Attio, "companies", "rec_1")
+            .await
+            .unwrap();
+        assert!(result.plan.is_empty());
+        assert!(linked().await);
+
+        let mut engine = engine_with(DeletePolicy::Unlink, SyncMode::Apply);
+        let result = engine
+            .sync_delete(System::Attio, "companies", "rec_1")
+            .await
+            .unwrap();
+        assert_eq!(result.records_deleted, 0);
+        assert_eq!(result.records_updated, 0);
+        assert!(!linked().await);
+    }
+
+    #[tokio::test]
+    async fn test_merge_repoints_or_drops_links() {
+        let storage = Rc::new(MemoryStorage::new());
+        let engine_with = |mirror_merges: bool, mode: SyncMode| {
+            let mut mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+            mapping.mirror_merges = mirror_merges;
+            let mut config = test_config();
+            config.mappings.insert("companies".to_string(), mapping);
+            SyncEngine::new(config)
+                .with_storage(storage.clone())
+                .with_mode(mode)
+        };
+        let loser = IdMapping::new("companies", "rec_1", "Account", "001a");
+        storage.save_id_mapping(&loser).await.unwrap();
+        storage
+            .save_base_snapshot(&BaseSnapshot::new(
+                &loser,
+                FieldValues::new(),
+                FieldValues::new(),
+            ))
+            .await
+            .unwrap();
+        storage
+            .save_pending_reference(&PendingReference::new(
+                System::Salesforce,
+                &loser,
+                "ParentId",
+                "companies",
+                "rec_8",
+            ))
+            .await
+            .unwrap();
+
+        // Unlinked losers leave nothing to do
+        let mut engine = engine_with(true, SyncMode::Apply);
+        let result = engine
+            .sync_merge(System::Attio, "companies", "rec_2", "rec_9")
+            .await
+            .unwrap();
+        assert_eq!(result.records_unchanged, 1);
+
+        // Planning a re-point neither moves the link nor counts an update
+        let mut engine = engine_with(false, SyncMode::Plan);
+        let result = engine
+            .sync_merge(System::Attio, "companies", "rec_2", "rec_1")
+            .await
+            .unwrap();
+        assert_eq!(result.records_updated, 0);
+        assert!(result.plan.is_empty());
+        assert!(storage
+            .get_mapping_by_attio_id("companies", "rec_1")
+            .await
+            .unwrap()
+            .is_some());
+
+        // Winner has no counterpart: the loser's link moves to it
+        let mut engine = engine_with(false, SyncMode::Apply);
+        let result = engine
+            .sync_merge(System::Attio, "companies", "rec_2", "rec_1")
+            .await
+            .unwrap();
+        assert_eq!(result.records_updated, 1);
+        let link = storage
+            .get_mapping_by_sf_id("Account", "001a")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(link.attio_id, "rec_2");
+        // Its base snapshot and pending references move with it
+        assert!(storage
+            .get_base_snapshot("companies", "rec_1")
+            .await
+            .unwrap()
+            .is_none());
+        let base = storage
+            .get_base_snapshot("companies", "rec_2")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            (base.attio_id.as_str(), base.salesforce_id.as_str()),
+            ("rec_2", "001a")
+        );
+        assert!(storage
+            .find_pending_references_of("companies", "rec_1")
+            .await
+            .unwrap()
+            .is_empty());
+        let pending = storage
+            .find_pending_references_of("companies", "rec_2")
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(
+            pending[0].id,
+            PendingReference::key(System::Salesforce, &link, "ParentId")
+        );
+        assert_eq!(pending[0].target_id(), "001a");
+
+        // Both had counterparts: the loser's link is dropped, and its children re-synced
+        let loser = IdMapping::new("companies", "rec_3", "Account", "001b");
+        storage.save_id_mapping(&loser).await.unwrap();
+        storage
+            .save_base_snapshot(&BaseSnapshot::new(
+                &loser,
+                FieldValues::new(),
+                FieldValues::new(),
+            ))
+            .await
+            .unwrap();
+        let child = IdMapping::new("people", "per_1", "Contact", "003a");
+        let mut values = FieldValues::new();
+        values.insert("AccountId".to_string(), Value::String("001b".to_string()));
+        storage
+            .save_base_snapshot(&BaseSnapshot::new(&child, FieldValues::new(), values))
+            .await
+            .unwrap();
+
+        let mut engine = engine_with(true, SyncMode::Plan);
+        let result = engine
+            .sync_merge(System::Attio, "companies", "rec_2", "rec_3")
+            .await
+            .unwrap();
+        assert_eq!(result.plan.deletes[0].target_id.as_deref(), Some("001b"));
+
+        // Without mirroring, a survivor that is already linked keeps only its own link
+        let mut engine = engine_with(false, SyncMode::Plan);
+        let result = engine
+            .sync_merge(System::Attio, "companies", "rec_2", "rec_3")
+            .await
+            .unwrap();
+        assert!(result.plan.deletes.is_empty());
+
+        let mut engine = engine_with(false, SyncMode::Apply);
+        let result = engine
+            .sync_merge(System::Attio, "companies", "rec_2", "rec_3")
+            .await
+            .unwrap();
+        // The child fetch fails against the stub client
+        assert_eq!(result.errors, 1);
+        assert_eq!(result.failures.len(), 1);
+        assert!(result.failures[0].starts_with("people per_1: "));
+        assert!(storage
+            .get_mapping_by_sf_id("Account", "001b")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(storage
+            .get_mapping_by_attio_id("companies", "rec_2")
+            .await
+            .unwrap()
+            .is_some());
+        // The dropped link's base snapshot goes with it; the survivor keeps its own
+        assert!(storage
+            .get_base_snapshot("companies", "rec_3")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(storage
+            .get_base_snapshot("companies", "rec_2")
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_pending_reference_lifecycle() {
+        let storage = Rc::new(MemoryStorage::new());
+        let mapping = ObjectMapping::from_defaults("people", "Contact").unwrap();
+        let contact = IdMapping::new("people", "per_1", "Contact", "003a");
+        let account = IdMapping::new("companies", "rec_1", "Account", "001a");
+        let attio_data = serde_json::json!({
+            "company": {"target_object": "companies", "target_record_id": "rec_1"}
+        });
+        let mut engine = SyncEngine::new(test_config()).with_storage(storage.clone());
+
+        // The Account is not linked yet: the Contact is left waiting for it
+        engine
+            .prefetch_references(&mapping, Some(&attio_data), None)
+            .await
+            .unwrap();
+        engine.update_pending_references(&contact).await.unwrap();
+        let waiting = storage
+            .find_pending_references("companies", "rec_1")
+            .await
+            .unwrap();
+        assert_eq!(waiting[0].field, "AccountId");
+
+        // Linking the Account patches the Contact's lookup
+        let mut engine = engine.with_mode(SyncMode::Plan);
+        let mut result = SyncResult::default();
+        engine
+            .backfill_references(&account, &mut result)
+            .await
+            .unwrap();
+        let update = &result.plan.updates[0];
+        assert_eq!(update.target_id.as_deref(), Some("003a"));
+        assert_eq!(update.changes[0].after, Value::String("001a".to_string()));
+
+        // Once the reference resolves, the entry is dropped
+        storage.save_id_mapping(&account).await.unwrap();
+        let mut engine = engine.with_mode(SyncMode::Apply);
+        engine
+            .prefetch_references(&mapping, Some(&attio_data), None)
+            .await
+            .unwrap();
+        engine.update_pending_references(&contact).await.unwrap();
+        assert!(storage
+            .list_pending_references(10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_matches_link_or_wait_for_review() {
+        let storage = Rc::new(MemoryStorage::new());
+        let mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+        let pair = |attio: &str, sf: &str, confidence| MatchPair {
+            attio_id: attio.to_string(),
+            salesforce_id: sf.to_string(),
+            confidence,
+            rule: 0,
+        };
+        let mut rejected = LinkCandidate::new(&mapping, &pair("rec_3", "001c", 0.5));
+        rejected.status = CandidateStatus::Rejected;
+        storage.save_link_candidate(&rejected).await.unwrap();
+        let mut engine = SyncEngine::new(test_config()).with_storage(storage.clone());
+
+        let pairs = vec![
+            pair("rec_1", "001a", 1.0),
+            pair("rec_2", "001b", 0.8),
+            pair("rec_3", "001c", 0.5),
+        ];
+        let result = engine.apply_matches(&mapping, pairs).await.unwrap();
+        assert_eq!((result.records_linked, result.link_candidates), (1, 1));
+        assert!(storage
+            .get_mapping_by_attio_id("companies", "rec_1")
+            .await
+            .unwrap()
+            .is_some());
+        assert!(storage
+            .get_mapping_by_attio_id("companies", "rec_2")
+            .await
+            .unwrap()
+            .is_none());
+
+        // The record waiting for review is not created on the other side
+        assert!(engine.awaiting_review("Account", "001b").await.unwrap());
+        assert!(!engine.awaiting_review("Account", "001c").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_review_and_import_links() {
+        let storage = Rc::new(MemoryStorage::new());
+        let mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+        let pair = |attio: &str, sf: &str| MatchPair {
+            attio_id: attio.to_string(),
+            salesforce_id: sf.to_string(),
+            confidence: 0.4,
+            rule: 1,
+        };
+        let chosen = LinkCandidate::new(&mapping, &pair("rec_1", "001a"));
+        let competing = LinkCandidate::new(&mapping, &pair("rec_1", "001b"));
+        storage.save_link_candidate(&chosen).await.unwrap();
+        storage.save_link_candidate(&competing).await.unwrap();
+        let mut engine = SyncEngine::new(test_config()).with_storage(storage.clone());
+
+        // Approving links the pair and closes the other candidate of the record
+        let approved = engine
+            .review_link_candidate(&chosen.id, true, "ops")
+            .await
+            .unwrap();
+        assert_eq!(approved.status, CandidateStatus::Approved);
+        assert!(storage
+            .get_mapping_by_sf_id("Account", "001a")
+            .await
+            .unwrap()
+            .is_some());
+        let closed = storage.get_link_candidate(&competing.id).await.unwrap();
+        assert_eq!(closed.unwrap().status, CandidateStatus::Rejected);
+        assert!(matches!(
+            engine.review_link_candidate(&chosen.id, false, "ops").await,
+            Err(Error::Validation { .. })
+        ));
+
+        // Imports skip the same pair and refuse records linked elsewhere
+        let same = IdMapping::new("companies", "rec_1", "Account", "001a");
+        assert!(!engine.import_link(&same, "ops").await.unwrap());
+        let taken = IdMapping::new("companies", "rec_2", "Account", "001a");
+        assert!(matches!(
+            engine.import_link(&taken, "ops").await,
+            Err(Error::Conflict { .. })
+        ));
+    }
 }
@@ -0,0 +1,239 @@
+//! Change feeds: where incremental sync learns which records changed.
+
+use crate::attio::AttioClient;
+use crate::config::{Config, SalesforceConfig};
+use crate::error::Result;
+use crate::salesforce::SalesforceClient;
+use crate::sync::echo::System;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// A changed record, as reported by a change feed
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RecordChange {
+    /// Modification timestamp (`updated_at` in Attio, `SystemModstamp` in Salesforce)
+    pub modified: Option<DateTime<Utc>>,
+
+    /// Record ID
+    pub record_id: String,
+}
+
+/// Change feed trait used by the sync engine
+#[async_trait(?Send)]
+pub trait ChangeFeed {
+    /// Records of an object modified at or after `since`
+    async fn changes_since(
+        &self,
+        system: System,
+        object: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<RecordChange>>;
+
+    /// Records of an object deleted at or after `since` (`modified` is the deletion time)
+    async fn deleted_since(
+        &self,
+        system: System,
+        object: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<RecordChange>>;
+
+    /// Record a deleted record was merged into, if it was deleted by a merge
+    async fn merged_into(
+        &self,
+        system: System,
+        object: &str,
+        record_id: &str,
+    ) -> Result<Option<String>>;
+}
+
+/// Change feed backed by the Attio and Salesforce APIs
+pub struct ApiChangeFeed {
+    attio: AttioClient,
+    salesforce: SalesforceConfig,
+}
+
+impl ApiChangeFeed {
+    /// Create a feed from the API configuration
+    pub fn new(config: &Config) -> Self {
+        Self {
+            attio: AttioClient::new(config.attio.clone()),
+            salesforce: config.salesforce.clone(),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl ChangeFeed for ApiChangeFeed {
+    async fn changes_since(
+        &self,
+        system: System,
+        object: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<RecordChange>> {
+        match system {
+            System::Attio => Ok(self
+                .attio
+                .get_changes_since(object, since)
+                .await?
+                .into_iter()
+                .map(|record| RecordChange {
+                    modified: Some(record.updated_at),
+                    record_id: record.id.0,
+                })
+                .collect()),
+            System::Salesforce => Ok(SalesforceClient::new(self.salesforce.clone())
+                .get_changes_since(object, since)
+                .await?
+                .into_iter()
+                .filter_map(|record| {
+                    Some(RecordChange {
+                        modified: record.system_modstamp(),
+                        record_id: record.id?.0,
+                    })
+                })
+                .collect()),
+        }
+    }
+
+    async fn deleted_since(
+        &self,
+        system: System,
+        object: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<RecordChange>> {
+        match system {
+            // Attio only reports deletes through record.deleted webhooks
+            System::Attio => Ok(Vec::new()),
+            System::Salesforce => Ok(SalesforceClient::new(self.salesforce.clone())
+                .get_deleted(object, since, Utc::now())
+                .await?
+                .deleted_records
+                .into_iter()
+                .map(|record| RecordChange {
+                    modified: Some(record.deleted_date),
+                    record_id: record.id.0,
+                })
+                .collect()),
+        }
+    }
+
+    async fn merged_into(
+        &self,
+        system: System,
+        object: &str,
+        record_id: &str,
+    ) -> Result<Option<String>> {
+        match system {
+            // Attio reports merges through record.merged webhooks instead
+            System::Attio => Ok(None),
+            System::Salesforce => {
+                SalesforceClient::new(self.salesforce.clone())
+                    .get_merge_master(object, record_id)
+                    .await
+            }
+        }
+    }
+}
+
+/// In-memory change feed for tests and the CLI
+pub struct MemoryChangeFeed {
+    changes: RwLock<HashMap<(System, String), Vec<RecordChange>>>,
+    deletes: RwLock<HashMap<(System, String), Vec<RecordChange>>>,
+    merges: RwLock<HashMap<(System, String, String), String>>,
+}
+
+impl MemoryChangeFeed {
+    /// Create an empty in-memory feed
+    pub fn new() -> Self {
+        Self {
+            changes: RwLock::new(HashMap::new()),
+            deletes: RwLock::new(HashMap::new()),
+            merges: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Report a record as changed
+    pub fn push(&self, system: System, object: &str, change: RecordChange) {
+        let mut changes = self.changes.write().unwrap();
+        changes
+            .entry((system, object.to_string()))
+            .or_default()
+            .push(change);
+    }
+
+    /// Report a record as deleted
+    pub fn push_deleted(&self, system: System, object: &str, change: RecordChange) {
+        let mut deletes = self.deletes.write().unwrap();
+        deletes
+            .entry((system, object.to_string()))
+            .or_default()
+            .push(change);
+    }
+
+    /// Report a deleted record as merged into another
+    pub fn push_merged(&self, system: System, object: &str, record_id: &str, master_id: &str) {
+        let mut merges = self.merges.write().unwrap();
+        merges.insert(
+            (system, object.to_string(), record_id.to_string()),
+            master_id.to_string(),
+        );
+    }
+}
+
+impl Default for MemoryChangeFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait(?Send)]
+impl ChangeFeed for MemoryChangeFeed {
+    async fn changes_since(
+        &self,
+        system: System,
+        object: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<RecordChange>> {
+        Ok(entries_since(&self.changes, system, object, since))
+    }
+
+    async fn deleted_since(
+        &self,
+        system: System,
+        object: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<RecordChange>> {
+        Ok(entries_since(&self.deletes, system, object, since))
+    }
+
+    async fn merged_into(
+        &self,
+        system: System,
+        object: &str,
+        record_id: &str,
+    ) -> Result<Option<String>> {
+        let merges = self.merges.read().unwrap();
+        Ok(merges
+            .get(&(system, object.to_string(), record_id.to_string()))
+            .cloned())
+    }
+}
+
+/// Entries of a feed table for an object at or after `since`
+fn entries_since(
+    table: &RwLock<HashMap<(System, String), Vec<RecordChange>>>,
+    system: System,
+    object: &str,
+    since: DateTime<Utc>,
+) -> Vec<RecordChange> {
+    let table = table.read().unwrap();
+    table
+        .get(&(system, object.to_string()))
+        .into_iter()
+        .flatten()
+        .filter(|c| c.modified.is_none_or(|t| t >= since))
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,186 @@
+//! Sync run history.
+
+use crate::config::SyncDirection;
+use crate::error::{Error, Result};
+use crate::sync::engine::SyncResult;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A single sync run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncRun {
+    /// Run ID
+    pub id: String,
+
+    /// What started the run
+    pub trigger: SyncTrigger,
+
+    /// Sync direction
+    pub direction: SyncDirection,
+
+    /// Attio objects included in the run
+    pub objects: Vec<String>,
+
+    /// When the run started
+    pub started_at: DateTime<Utc>,
+
+    /// When the run completed (if finished)
+    pub completed_at: Option<DateTime<Utc>>,
+
+    /// Number of records processed
+    pub records_processed: u64,
+
+    /// Number of records created
+    pub records_created: u64,
+
+    /// Number of records updated
+    pub records_updated: u64,
+
+    /// Number of linked records deleted
+    #[serde(default)]
+    pub records_deleted: u64,
+
+    /// Number of records skipped as unchanged
+    #[serde(default)]
+    pub records_unchanged: u64,
+
+    /// Number of records with conflicts
+    pub conflicts: u64,
+
+    /// Number of errors
+    pub errors: u64,
+
+    /// Run status
+    pub status: RunStatus,
+
+    /// Error that aborted the run (if failed)
+    pub error: Option<String>,
+
+    /// Wall-clock duration in milliseconds (if finished)
+    #[serde(default)]
+    pub duration_ms: Option<i64>,
+}
+
+/// What started a sync run
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncTrigger {
+    /// Webhook event
+    Webhook,
+    /// Cron trigger
+    Scheduled,
+    /// API or CLI request
+    Manual,
+}
+
+/// Status of a sync run
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    /// Still running
+    Running,
+    /// Completed without errors
+    Succeeded,
+    /// Completed with record-level errors
+    Partial,
+    /// Aborted
+    Failed,
+}
+
+impl std::str::FromStr for RunStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "running" => Ok(Self::Running),
+            "succeeded" => Ok(Self::Succeeded),
+            "partial" => Ok(Self::Partial),
+            "failed" => Ok(Self::Failed),
+            _ => Err(Error::validation(format!("Invalid run status: {}", s))),
+        }
+    }
+}
+
+impl SyncRun {
+    /// Start a new run
+    pub fn start(trigger: SyncTrigger, direction: SyncDirection, objects: Vec<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            trigger,
+            direction,
+            objects,
+            started_at: Utc::now(),
+            completed_at: None,
+            records_processed: 0,
+            records_created: 0,
+            records_updated: 0,
+            records_deleted: 0,
+            records_unchanged: 0,
+            conflicts: 0,
+            errors: 0,
+            status: RunStatus::Running,
+            error: None,
+            duration_ms: None,
+        }
+    }
+
+    /// Mark the run as finished with the outcome of the sync
+    pub fn finish(&mut self, result: &Result<SyncResult>) {
+        let completed_at = Utc::now();
+        self.completed_at = Some(completed_at);
+        self.duration_ms = Some((completed_at - self.started_at).num_milliseconds());
+        match result {
+            Ok(result) => {
+                self.records_processed = result.records_processed;
+                self.records_created = result.records_created;
+                self.records_updated = result.records_updated;
+                self.records_deleted = result.records_deleted;
+                self.records_unchanged = result.records_unchanged;
+                self.conflicts = result.conflicts;
+                self.errors = result.errors;
+                self.status = if result.errors > 0 {
+                    RunStatus::Partial
+                } else {
+                    RunStatus::Succeeded
+                };
+            }
+            Err(e) => {
+                self.status = RunStatus::Failed;
+                self.error = Some(e.to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finish_status() {
+        let mut run = SyncRun::start(
+            SyncTrigger::Scheduled,
+            SyncDirection::Bidirectional,
+            vec!["companies".to_string()],
+        );
+        assert_eq!(run.status, RunStatus::Running);
+
+        let partial = SyncResult {
+            records_processed: 4,
+            records_deleted: 1,
+            records_unchanged: 2,
+            errors: 1,
+            ..SyncResult::default()
+        };
+        run.finish(&Ok(partial));
+        assert_eq!(run.status, RunStatus::Partial);
+        assert_eq!(run.records_deleted, 1);
+        assert_eq!(run.records_unchanged, 2);
+        assert!(run.completed_at.is_some());
+        assert!(run.duration_ms.is_some());
+
+        run.finish(&Err(Error::sync("boom")));
+        assert_eq!(run.status, RunStatus::Failed);
+        assert_eq!(run.error.as_deref(), Some("Sync error: boom"));
+    }
+}
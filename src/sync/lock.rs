@@ -0,0 +1,189 @@
+//! Lease-based locking so the same object mapping is never synced concurrently.
+
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Default lease duration for sync locks
+pub const DEFAULT_LOCK_TTL_SECS: i64 = 300;
+
+/// Lease on a lock, valid until `expires_at`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockLease {
+    /// Lock key (object mapping key)
+    pub key: String,
+
+    /// ID of the lease holder
+    pub holder: String,
+
+    /// When the lease expires unless renewed
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Lock trait used by the sync engine
+#[async_trait(?Send)]
+pub trait Lock {
+    /// Try to acquire a lease; returns `None` if another holder has an unexpired lease
+    async fn acquire(&self, key: &str, holder: &str, ttl: Duration) -> Result<Option<LockLease>>;
+
+    /// Extend a lease; returns `None` if the lease was lost
+    async fn renew(&self, lease: &LockLease, ttl: Duration) -> Result<Option<LockLease>>;
+
+    /// Release a lease (no-op if it is no longer held)
+    async fn release(&self, lease: &LockLease) -> Result<()>;
+}
+
+impl LockLease {
+    /// Check whether the lease has expired
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// Compute the lease granted to `holder`, given the current lease
+pub fn acquire_lease(
+    current: Option<&LockLease>,
+    key: &str,
+    holder: &str,
+    ttl: Duration,
+    now: DateTime<Utc>,
+) -> Option<LockLease> {
+    match current {
+        Some(lease) if lease.holder != holder && !lease.is_expired(now) => None,
+        _ => Some(LockLease {
+            key: key.to_string(),
+            holder: holder.to_string(),
+            expires_at: now + ttl,
+        }),
+    }
+}
+
+/// Compute the renewed lease, given the current lease
+pub fn renew_lease(
+    current: Option<&LockLease>,
+    lease: &LockLease,
+    ttl: Duration,
+    now: DateTime<Utc>,
+) -> Option<LockLease> {
+    match current {
+        Some(current) if current.holder == lease.holder && !current.is_expired(now) => {
+            Some(LockLease {
+                expires_at: now + ttl,
+                ..current.clone()
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Check whether `lease` may release the current lease
+pub fn may_release(current: Option<&LockLease>, lease: &LockLease) -> bool {
+    current.is_some_and(|current| current.holder == lease.holder)
+}
+
+/// In-memory lock for tests and the CLI
+pub struct MemoryLock {
+    leases: RwLock<HashMap<String, LockLease>>,
+}
+
+impl MemoryLock {
+    /// Create a new in-memory lock
+    pub fn new() -> Self {
+        Self {
+            leases: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for MemoryLock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait(?Send)]
+impl Lock for MemoryLock {
+    async fn acquire(&self, key: &str, holder: &str, ttl: Duration) -> Result<Option<LockLease>> {
+        let mut leases = self.leases.write().unwrap();
+        let lease = acquire_lease(leases.get(key), key, holder, ttl, Utc::now());
+        if let Some(lease) = &lease {
+            leases.insert(key.to_string(), lease.clone());
+        }
+        Ok(lease)
+    }
+
+    async fn renew(&self, lease: &LockLease, ttl: Duration) -> Result<Option<LockLease>> {
+        let mut leases = self.leases.write().unwrap();
+        let renewed = renew_lease(leases.get(&lease.key), lease, ttl, Utc::now());
+        if let Some(renewed) = &renewed {
+            leases.insert(lease.key.clone(), renewed.clone());
+        }
+        Ok(renewed)
+    }
+
+    async fn release(&self, lease: &LockLease) -> Result<()> {
+        let mut leases = self.leases.write().unwrap();
+        if may_release(leases.get(&lease.key), lease) {
+            leases.remove(&lease.key);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_lock() {
+        let lock = MemoryLock::new();
+        let ttl = Duration::seconds(60);
+
+        let lease = lock
+            .acquire("companies_Account", "cron", ttl)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(lock
+            .acquire("companies_Account", "manual", ttl)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(lock
+            .acquire("people_Contact", "manual", ttl)
+            .await
+            .unwrap()
+            .is_some());
+
+        assert!(lock.renew(&lease, ttl).await.unwrap().is_some());
+        lock.release(&lease).await.unwrap();
+        assert!(lock
+            .acquire("companies_Account", "manual", ttl)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_expired_lease_can_be_taken() {
+        let now = Utc::now();
+        let stale = LockLease {
+            key: "companies_Account".to_string(),
+            holder: "cron".to_string(),
+            expires_at: now - Duration::seconds(1),
+        };
+
+        let lease = acquire_lease(
+            Some(&stale),
+            &stale.key,
+            "manual",
+            Duration::seconds(60),
+            now,
+        );
+        assert_eq!(lease.unwrap().holder, "manual");
+        assert!(renew_lease(Some(&stale), &stale, Duration::seconds(60), now).is_none());
+    }
+}
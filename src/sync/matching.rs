@@ -0,0 +1,624 @@
+//! Initial matching of records that exist on both sides but are not linked.
+//!
+//! Before the first full sync writes anything, unlinked Attio and Salesforce
+//! records are paired with the mapping's match rules (e.g., company domain to
+//! `Website`). Pairs scoring at or above the mapping's auto-link threshold are
+//! linked straight away; the rest are kept as candidates for a human to review.
+//!
+//! Only the normalized rule keys of each record are indexed. Exact rules look
+//! keys up directly, and fuzzy rules only score keys within the same block
+//! (domain, or leading characters), so no rule compares every pair.
+
+use crate::config::{MatchNormalization, MatchRule, ObjectMapping};
+use crate::error::{Error, Result};
+use crate::sync::echo::System;
+use crate::transform::normalize::{self, jaro_winkler};
+use crate::transform::reference::IdMapping;
+use crate::transform::TransformPipeline;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Separator between the values of a multi-field rule key
+const KEY_SEPARATOR: &str = "\u{1f}";
+
+/// Leading characters fuzzy rules compare records within, unless they compare domains
+const BLOCK_PREFIX_LEN: usize = 3;
+
+/// Review status of a link candidate
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CandidateStatus {
+    /// Waiting for review
+    Pending,
+    /// Approved and linked
+    Approved,
+    /// Rejected; the pair is not proposed again
+    Rejected,
+}
+
+/// Pair of records matched by a rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchPair {
+    /// Attio record ID
+    pub attio_id: String,
+
+    /// Salesforce record ID
+    pub salesforce_id: String,
+
+    /// Confidence of the match (0.0 to 1.0)
+    pub confidence: f64,
+
+    /// Index of the rule that matched
+    pub rule: usize,
+}
+
+/// A proposed link below the auto-link threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkCandidate {
+    /// Candidate ID (one per pair of records)
+    pub id: String,
+
+    /// Attio object type
+    pub attio_object: String,
+
+    /// Attio record ID
+    pub attio_id: String,
+
+    /// Salesforce object type
+    pub salesforce_object: String,
+
+    /// Salesforce record ID
+    pub salesforce_id: String,
+
+    /// Confidence of the match (0.0 to 1.0)
+    pub confidence: f64,
+
+    /// Index of the mapping's match rule that paired the records
+    pub rule: usize,
+
+    /// Review status
+    pub status: CandidateStatus,
+
+    /// When the pair was proposed
+    pub created_at: DateTime<Utc>,
+
+    /// When the pair was approved or rejected
+    #[serde(default)]
+    pub reviewed_at: Option<DateTime<Utc>>,
+
+    /// Who approved or rejected the pair
+    #[serde(default)]
+    pub reviewed_by: Option<String>,
+}
+
+/// Criteria for listing link candidates
+#[derive(Debug, Clone, Default)]
+pub struct CandidateFilter {
+    /// Attio or Salesforce object type
+    pub object: Option<String>,
+
+    /// Attio or Salesforce record ID
+    pub record_id: Option<String>,
+
+    /// Review status
+    pub status: Option<CandidateStatus>,
+}
+
+/// Match rule keys of one side's records, without the record data
+#[derive(Debug, Clone)]
+pub struct MatchIndex {
+    /// Rules the keys are built with
+    rules: Vec<MatchRule>,
+
+    /// Key of each record per rule (`None` where a field is missing), by record ID
+    records: BTreeMap<String, Vec<Option<String>>>,
+
+    /// Record IDs by key, per rule
+    by_key: Vec<HashMap<String, Vec<String>>>,
+
+    /// Keys by block, per fuzzy rule
+    by_block: Vec<HashMap<String, Vec<String>>>,
+}
+
+impl std::str::FromStr for CandidateStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "pending" => Ok(Self::Pending),
+            "approved" => Ok(Self::Approved),
+            "rejected" => Ok(Self::Rejected),
+            _ => Err(Error::validation(format!(
+                "Invalid candidate status: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl LinkCandidate {
+    /// Pending candidate for a matched pair of a mapping
+    pub fn new(mapping: &ObjectMapping, pair: &MatchPair) -> Self {
+        Self {
+            id: Self::key(mapping, &pair.attio_id, &pair.salesforce_id),
+            attio_object: mapping.attio_object.clone(),
+            attio_id: pair.attio_id.clone(),
+            salesforce_object: mapping.salesforce_object.clone(),
+            salesforce_id: pair.salesforce_id.clone(),
+            confidence: pair.confidence,
+            rule: pair.rule,
+            status: CandidateStatus::Pending,
+            created_at: Utc::now(),
+            reviewed_at: None,
+            reviewed_by: None,
+        }
+    }
+
+    /// ID of the candidate for a pair of records
+    pub fn key(mapping: &ObjectMapping, attio_id: &str, salesforce_id: &str) -> String {
+        format!("{}:{}:{}", mapping.key(), attio_id, salesforce_id)
+    }
+
+    /// Link the candidate proposes
+    pub fn link(&self) -> IdMapping {
+        IdMapping::new(
+            &self.attio_object,
+            &self.attio_id,
+            &self.salesforce_object,
+            &self.salesforce_id,
+        )
+    }
+}
+
+impl MatchIndex {
+    /// Empty index for a mapping's match rules
+    pub fn new(rules: &[MatchRule]) -> Self {
+        Self {
+            rules: rules.to_vec(),
+            records: BTreeMap::new(),
+            by_key: vec![HashMap::new(); rules.len()],
+            by_block: vec![HashMap::new(); rules.len()],
+        }
+    }
+
+    /// Add the keys of a record
+    pub fn insert(&mut self, pipeline: &TransformPipeline, system: System, id: &str, data: &Value) {
+        let keys: Vec<Option<String>> = self
+            .rules
+            .iter()
+            .map(|rule| rule_key(pipeline, rule, data, system))
+            .collect();
+        for (i, key) in keys.iter().enumerate() {
+            let Some(key) = key else {
+                continue;
+            };
+            let ids = self.by_key[i].entry(key.clone()).or_default();
+            if ids.is_empty() && self.rules[i].similarity.is_some() {
+                self.by_block[i]
+                    .entry(block_key(&self.rules[i], key))
+                    .or_default()
+                    .push(key.clone());
+            }
+            ids.push(id.to_string());
+        }
+        self.records.insert(id.to_string(), keys);
+    }
+
+    /// Keys equal to `key` under a rule, or at least the rule's similarity
+    /// alike within its block for fuzzy rules, with their records and score
+    fn similar_keys(&self, rule: usize, key: &str) -> Vec<(&String, &Vec<String>, f64)> {
+        let by_key = &self.by_key[rule];
+        match self.rules[rule].similarity {
+            None => by_key
+                .get_key_value(key)
+                .map(|(key, ids)| (key, ids, 1.0))
+                .into_iter()
+                .collect(),
+            Some(min) => self.by_block[rule]
+                .get(&block_key(&self.rules[rule], key))
+                .into_iter()
+                .flatten()
+                .filter_map(|other| by_key.get_key_value(other))
+                .map(|(other, ids)| (other, ids, jaro_winkler(key, other)))
+                .filter(|(_, _, score)| *score >= min)
+                .collect(),
+        }
+    }
+}
+
+impl CandidateFilter {
+    /// Check whether a candidate matches the filter
+    pub fn matches(&self, candidate: &LinkCandidate) -> bool {
+        self.object.as_ref().is_none_or(|object| {
+            candidate.attio_object == *object || candidate.salesforce_object == *object
+        }) && self
+            .record_id
+            .as_ref()
+            .is_none_or(|id| candidate.attio_id == *id || candidate.salesforce_id == *id)
+            && self.status.is_none_or(|status| candidate.status == status)
+    }
+}
+
+/// Pair unlinked records with a mapping's match rules.
+///
+/// Convenience over [`match_indexes`] for records already in memory.
+pub fn match_records(
+    pipeline: &TransformPipeline,
+    rules: &[MatchRule],
+    attio: &[(String, Value)],
+    salesforce: &[(String, Value)],
+) -> Vec<MatchPair> {
+    let index = |records: &[(String, Value)], system: System| {
+        let mut index = MatchIndex::new(rules);
+        for (id, data) in records {
+            index.insert(pipeline, system, id, data);
+        }
+        index
+    };
+    match_indexes(
+        &index(attio, System::Attio),
+        &index(salesforce, System::Salesforce),
+    )
+}
+
+/// Pair the records of two indexes built with the same match rules.
+///
+/// Rules are tried in order, and a record pairs through the first rule that
+/// matches it at all. Exact rules look keys up directly; fuzzy rules only
+/// compare keys in the same block, and scale the rule's confidence by the
+/// values' similarity. Matching several records under that rule splits the
+/// confidence between them. Each record ends up in at most one pair,
+/// highest confidence first.
+pub fn match_indexes(attio: &MatchIndex, salesforce: &MatchIndex) -> Vec<MatchPair> {
+    let mut pairs = Vec::new();
+    for (attio_id, keys) in &attio.records {
+        for (i, rule) in attio.rules.iter().enumerate() {
+            let Some(key) = &keys[i] else {
+                continue;
+            };
+            // Salesforce records with an equal key, or a similar one for fuzzy rules
+            let matches = salesforce.similar_keys(i, key);
+            if matches.is_empty() {
+                continue;
+            }
+            let sf_rivals: usize = matches.iter().map(|(_, ids, _)| ids.len()).sum();
+            for (sf_key, sf_ids, score) in matches {
+                // Attio records (this one included) that also match the Salesforce key
+                let attio_rivals: usize = attio
+                    .similar_keys(i, sf_key)
+                    .iter()
+                    .map(|(_, ids, _)| ids.len())
+                    .sum();
+                let ambiguity = sf_rivals.max(attio_rivals);
+                pairs.extend(sf_ids.iter().map(|sf_id| MatchPair {
+                    attio_id: attio_id.clone(),
+                    salesforce_id: sf_id.clone(),
+                    confidence: rule.confidence * score / ambiguity as f64,
+                    rule: i,
+                }));
+            }
+            break;
+        }
+    }
+
+    pairs.sort_by(|a, b| {
+        b.confidence
+            .total_cmp(&a.confidence)
+            .then_with(|| (&a.attio_id, &a.salesforce_id).cmp(&(&b.attio_id, &b.salesforce_id)))
+    });
+    let mut attio_paired = HashSet::new();
+    let mut sf_paired = HashSet::new();
+    let mut matched = Vec::new();
+    for pair in pairs {
+        if attio_paired.contains(&pair.attio_id) || sf_paired.contains(&pair.salesforce_id) {
+            continue;
+        }
+        attio_paired.insert(pair.attio_id.clone());
+        sf_paired.insert(pair.salesforce_id.clone());
+        matched.push(pair);
+    }
+    matched
+}
+
+/// Columns of a link import file
+const IMPORT_COLUMNS: [&str; 4] = [
+    "attio_object",
+    "attio_id",
+    "salesforce_object",
+    "salesforce_id",
+];
+
+/// Parse a CSV file of record pairs to link, with a header row naming the
+/// `attio_object`, `attio_id`, `salesforce_object` and `salesforce_id` columns.
+///
+/// Returns each pair with its line number.
+pub fn parse_link_csv(csv: &str) -> Result<Vec<(usize, IdMapping)>> {
+    let mut lines = csv
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+    let header = lines
+        .next()
+        .map(|(_, line)| split_csv_line(line))
+        .unwrap_or_default();
+    let positions = IMPORT_COLUMNS
+        .iter()
+        .map(|column| {
+            header
+                .iter()
+                .position(|h| h.eq_ignore_ascii_case(column))
+                .ok_or_else(|| Error::validation(format!("Missing column: {}", column)))
+        })
+        .collect::<Result<Vec<usize>>>()?;
+
+    lines
+        .map(|(number, line)| {
+            let cells = split_csv_line(line);
+            let cell = |i: usize| match cells.get(positions[i]).map(String::as_str) {
+                Some(value) if !value.is_empty() => Ok(value),
+                _ => Err(Error::validation(format!(
+                    "Line {}: missing {}",
+                    number, IMPORT_COLUMNS[i]
+                ))),
+            };
+            Ok((
+                number,
+                IdMapping::new(cell(0)?, cell(1)?, cell(2)?, cell(3)?),
+            ))
+        })
+        .collect()
+}
+
+/// Cells of a CSV line, trimmed, with double-quoted cells unquoted
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            c => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+/// Normalized values of a rule's fields on one side, `None` if any is missing
+fn rule_key(
+    pipeline: &TransformPipeline,
+    rule: &MatchRule,
+    data: &Value,
+    system: System,
+) -> Option<String> {
+    let parts = rule
+        .fields
+        .iter()
+        .map(|field| {
+            let path = match system {
+                System::Attio => &field.attio_field,
+                System::Salesforce => &field.salesforce_field,
+            };
+            let value = match pipeline.get_nested_value(data, path)? {
+                Value::String(s) => s,
+                Value::Number(n) => n.to_string(),
+                _ => return None,
+            };
+            normalize(field.normalize, &value)
+        })
+        .collect::<Option<Vec<String>>>()?;
+    (!parts.is_empty()).then(|| parts.join(KEY_SEPARATOR))
+}
+
+/// Block of a fuzzy rule key: its domain if the rule compares one, else the
+/// first characters of its first value
+fn block_key(rule: &MatchRule, key: &str) -> String {
+    let parts: Vec<&str> = key.split(KEY_SEPARATOR).collect();
+    let domain = rule
+        .fields
+        .iter()
+        .position(|field| field.normalize == MatchNormalization::Domain);
+    match domain.and_then(|i| parts.get(i)) {
+        Some(domain) => domain.to_string(),
+        None => parts[0].chars().take(BLOCK_PREFIX_LEN).collect(),
+    }
+}
+
+/// Normalize a value for comparison, `None` if nothing is left
+pub fn normalize(kind: MatchNormalization, value: &str) -> Option<String> {
+    let normalized = match kind {
+        MatchNormalization::Text => value.trim().to_lowercase(),
+        MatchNormalization::Domain => normalize::registrable_domain(value)?,
+        MatchNormalization::Email => normalize::email(value)?,
+        MatchNormalization::Name => normalize::fold(value),
+        MatchNormalization::Company => normalize::company_name(value),
+    };
+    (!normalized.is_empty()).then_some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MatchField;
+    use serde_json::json;
+
+    #[test]
+    fn test_match_records_by_rule_order() {
+        let rules = ObjectMapping::from_defaults("companies", "Account")
+            .unwrap()
+            .effective_match_rules();
+        let attio = vec![
+            (
+                "rec_1".to_string(),
+                json!({"domains": [{"domain": "acme.com"}], "name": "Acme"}),
+            ),
+            (
+                "rec_2".to_string(),
+                json!({"name": "Globex Corp.", "primary_location": {"locality": "Springfield"}}),
+            ),
+            ("rec_3".to_string(), json!({"name": "Initech"})),
+        ];
+        let salesforce = vec![
+            (
+                "001a".to_string(),
+                json!({"Website": "https://www.acme.com/", "Name": "ACME"}),
+            ),
+            (
+                "001b".to_string(),
+                json!({"Name": "GLOBEX Corporation", "BillingCity": "springfield"}),
+            ),
+            ("001c".to_string(), json!({"Name": "Initech"})),
+        ];
+
+        let pairs = match_records(&TransformPipeline::new(), &rules, &attio, &salesforce);
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(
+            (pairs[0].attio_id.as_str(), pairs[0].salesforce_id.as_str()),
+            ("rec_1", "001a")
+        );
+        assert_eq!(pairs[0].confidence, 1.0);
+        assert_eq!((pairs[1].attio_id.as_str(), pairs[1].rule), ("rec_2", 1));
+        assert_eq!(pairs[1].confidence, 0.8);
+    }
+
+    #[test]
+    fn test_ambiguous_matches_split_confidence() {
+        let rules = ObjectMapping::from_defaults("people", "Contact")
+            .unwrap()
+            .effective_match_rules();
+        let attio = vec![(
+            "per_1".to_string(),
+            json!({"email_addresses": [{"email_address": "Jane@Example.com"}]}),
+        )];
+        let salesforce = vec![
+            ("003a".to_string(), json!({"Email": "jane@example.com"})),
+            ("003b".to_string(), json!({"Email": "jane@example.com "})),
+        ];
+
+        let pairs = match_records(&TransformPipeline::new(), &rules, &attio, &salesforce);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].confidence, 0.5);
+    }
+
+    #[test]
+    fn test_fuzzy_rule_scales_confidence() {
+        let rules = vec![MatchRule {
+            fields: vec![MatchField {
+                attio_field: "name".to_string(),
+                salesforce_field: "Name".to_string(),
+                normalize: MatchNormalization::Company,
+            }],
+            confidence: 1.0,
+            similarity: Some(0.9),
+        }];
+        let attio = vec![(
+            "rec_1".to_string(),
+            json!({"name": "Initech Systems, Inc."}),
+        )];
+        let salesforce = vec![
+            ("001a".to_string(), json!({"Name": "Initech Sytems"})),
+            ("001b".to_string(), json!({"Name": "Umbrella"})),
+        ];
+
+        let pairs = match_records(&TransformPipeline::new(), &rules, &attio, &salesforce);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].salesforce_id, "001a");
+        assert!(pairs[0].confidence > 0.9 && pairs[0].confidence < 1.0);
+    }
+
+    #[test]
+    fn test_fuzzy_rule_counts_similar_attio_records() {
+        let rules = vec![MatchRule {
+            fields: vec![MatchField {
+                attio_field: "name".to_string(),
+                salesforce_field: "Name".to_string(),
+                normalize: MatchNormalization::Company,
+            }],
+            confidence: 1.0,
+            similarity: Some(0.9),
+        }];
+        // Near-duplicates in Attio: neither may auto-link with full confidence
+        let attio = vec![
+            ("rec_1".to_string(), json!({"name": "Initech Systems"})),
+            ("rec_2".to_string(), json!({"name": "Initech Sytems"})),
+        ];
+        let salesforce = vec![("001a".to_string(), json!({"Name": "Initech Systems"}))];
+
+        let pairs = match_records(&TransformPipeline::new(), &rules, &attio, &salesforce);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].attio_id, "rec_1");
+        assert_eq!(pairs[0].confidence, 0.5);
+    }
+
+    #[test]
+    fn test_fuzzy_rule_only_scores_within_block() {
+        let rules = vec![MatchRule {
+            fields: vec![MatchField {
+                attio_field: "name".to_string(),
+                salesforce_field: "Name".to_string(),
+                normalize: MatchNormalization::Company,
+            }],
+            confidence: 1.0,
+            similarity: Some(0.8),
+        }];
+        assert!(jaro_winkler("initech systems", "xinitech systems") >= 0.8);
+
+        // Indexed a page at a time, keeping only keys
+        let mut attio = MatchIndex::new(&rules);
+        attio.insert(
+            &TransformPipeline::new(),
+            System::Attio,
+            "rec_1",
+            &json!({"name": "Initech Systems"}),
+        );
+        let mut salesforce = MatchIndex::new(&rules);
+        for (id, name) in [("001a", "XInitech Systems"), ("001b", "Initech Sytems")] {
+            salesforce.insert(
+                &TransformPipeline::new(),
+                System::Salesforce,
+                id,
+                &json!({ "Name": name }),
+            );
+        }
+
+        let pairs = match_indexes(&attio, &salesforce);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].salesforce_id, "001b");
+        // The record outside the block is not a rival either
+        assert!(pairs[0].confidence > 0.9);
+    }
+
+    #[test]
+    fn test_parse_link_csv() {
+        let csv = "attio_id,attio_object,salesforce_object,salesforce_id\n\
+                   rec_1,companies,Account,001a\n\
+                   \n\
+                   \"rec_2\",companies,Account, 001b \n";
+        let links = parse_link_csv(csv).unwrap();
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[1].0, 4);
+        assert_eq!(
+            (
+                links[1].1.attio_id.as_str(),
+                links[1].1.salesforce_id.as_str()
+            ),
+            ("rec_2", "001b")
+        );
+
+        assert!(parse_link_csv("attio_object,attio_id\n").is_err());
+        let err = parse_link_csv(
+            "attio_object,attio_id,salesforce_object,salesforce_id\ncompanies,rec_1,Account\n",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Line 2"));
+    }
+}
@@ -0,0 +1,341 @@
+//! Three-way merge of linked records against their last-synced base snapshot.
+//!
+//! A field only conflicts when both sides moved away from the value we last
+//! synced; a change on one side simply flows to the other. Conflicting fields
+//! go to the [`ConflictResolver`], or to a [`MergeStrategy`] registered by the
+//! embedding application and named in config.
+
+use crate::config::mappings::{FieldMapping, FieldSyncDirection, ObjectMapping, TransformType};
+use crate::error::Result;
+use crate::sync::compare::equivalent;
+use crate::sync::conflict::{ConflictResolver, ConflictWinner, FieldChoice, FieldConflict};
+use crate::sync::direction::SyncDirectionHandler;
+use crate::sync::echo::System;
+use crate::transform::reference::IdMapping;
+use crate::transform::TransformPipeline;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Mapped field values of one side, keyed by that side's field name
+pub type FieldValues = HashMap<String, Value>;
+
+/// Values of each mapped field as of the last successful sync of a link
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaseSnapshot {
+    /// Attio object type
+    pub attio_object: String,
+
+    /// Attio record ID
+    pub attio_id: String,
+
+    /// Salesforce object type
+    pub salesforce_object: String,
+
+    /// Salesforce record ID
+    pub salesforce_id: String,
+
+    /// Attio values (attio_field → value)
+    pub attio: FieldValues,
+
+    /// Salesforce values (salesforce_field → value)
+    pub salesforce: FieldValues,
+
+    /// When the snapshot was taken
+    pub synced_at: DateTime<Utc>,
+}
+
+/// Which fields to copy in each direction, and which need the resolver
+#[derive(Debug, Default)]
+pub struct MergePlan {
+    /// Fields to copy from Attio to Salesforce
+    pub to_salesforce: Vec<FieldMapping>,
+
+    /// Fields to copy from Salesforce to Attio
+    pub to_attio: Vec<FieldMapping>,
+
+    /// Fields changed on both sides since the base, to different values
+    pub conflicts: Vec<FieldMapping>,
+}
+
+/// Everything a custom merge strategy sees about a conflicting pair of records
+#[derive(Debug)]
+pub struct MergeContext<'a> {
+    /// Object mapping of the pair
+    pub mapping: &'a ObjectMapping,
+
+    /// Full Attio record
+    pub attio: &'a Value,
+
+    /// Full Salesforce record
+    pub salesforce: &'a Value,
+
+    /// Values as of the last sync, if the pair has been synced before
+    pub base: Option<&'a BaseSnapshot>,
+
+    /// Fields changed on both sides since the base
+    pub conflicts: &'a [FieldConflict],
+}
+
+/// Result of a custom merge
+#[derive(Debug, Clone)]
+pub struct MergeOutcome {
+    /// Merged value per conflicting field (keyed by Attio field); fields left
+    /// out are queued for manual resolution
+    pub fields: HashMap<String, FieldChoice>,
+
+    /// Overall winner recorded on the conflict
+    pub winner: ConflictWinner,
+}
+
+/// Domain-specific conflict merge supplied by the embedding application
+pub trait MergeStrategy {
+    /// Name used to select the strategy from config
+    fn name(&self) -> &str;
+
+    /// Merge the conflicting fields of a linked pair
+    fn merge(&self, context: &MergeContext<'_>) -> Result<MergeOutcome>;
+}
+
+impl BaseSnapshot {
+    /// Snapshot the given values for a link
+    pub fn new(link: &IdMapping, attio: FieldValues, salesforce: FieldValues) -> Self {
+        Self {
+            attio_object: link.attio_object.clone(),
+            attio_id: link.attio_id.clone(),
+            salesforce_object: link.salesforce_object.clone(),
+            salesforce_id: link.salesforce_id.clone(),
+            attio,
+            salesforce,
+            synced_at: Utc::now(),
+        }
+    }
+}
+
+impl MergePlan {
+    /// Check whether nothing needs to be written or resolved
+    pub fn is_empty(&self) -> bool {
+        self.to_salesforce.is_empty() && self.to_attio.is_empty() && self.conflicts.is_empty()
+    }
+}
+
+/// Extract the mapped field values of one side from a record
+pub fn field_values(
+    pipeline: &TransformPipeline,
+    data: &Value,
+    fields: &[FieldMapping],
+    system: System,
+) -> FieldValues {
+    fields
+        .iter()
+        .filter_map(|field| {
+            let name = match system {
+                System::Attio => &field.attio_field,
+                System::Salesforce => &field.salesforce_field,
+            };
+            let value = pipeline.get_nested_value(data, name)?;
+            Some((name.clone(), value))
+        })
+        .collect()
+}
+
+/// Check whether a field's value moved away from its base
+pub fn diverged(field: &FieldMapping, base: Option<&Value>, current: Option<&Value>) -> bool {
+    !equivalent(&field.value_type(), base, current)
+}
+
+/// Check whether both sides hold the same value of a field.
+///
+/// Only fields copied across without conversion can be compared; for the
+/// rest the two sides are never considered the same.
+pub fn converged(field: &FieldMapping, attio: Option<&Value>, salesforce: Option<&Value>) -> bool {
+    let unconverted = match &field.transform {
+        TransformType::Direct | TransformType::ExtractFirst => true,
+        TransformType::ExtractNested { path } => path.is_empty(),
+        _ => false,
+    };
+    unconverted && equivalent(&field.value_type(), attio, salesforce)
+}
+
+/// Decide, field by field, what to copy where.
+///
+/// Without a base snapshot only the `source` side is treated as changed.
+/// Fields both sides changed to the same value are left alone, so the base
+/// simply catches up with them.
+pub fn plan_merge(
+    resolver: &ConflictResolver,
+    fields: &[FieldMapping],
+    direction: &SyncDirectionHandler,
+    source: System,
+    attio: &FieldValues,
+    salesforce: &FieldValues,
+    base: Option<&BaseSnapshot>,
+) -> MergePlan {
+    let mut plan = MergePlan::default();
+
+    for field in fields {
+        let to_sf = direction.attio_to_sf_enabled()
+            && matches!(
+                field.direction,
+                FieldSyncDirection::Bidirectional | FieldSyncDirection::AttioToSalesforce
+            );
+        let to_attio = direction.sf_to_attio_enabled()
+            && matches!(
+                field.direction,
+                FieldSyncDirection::Bidirectional | FieldSyncDirection::SalesforceToAttio
+            );
+
+        let attio_value = attio.get(&field.attio_field);
+        let sf_value = salesforce.get(&field.salesforce_field);
+        let (attio_changed, sf_changed) = match base {
+            Some(base) => (
+                diverged(field, base.attio.get(&field.attio_field), attio_value),
+                diverged(
+                    field,
+                    base.salesforce.get(&field.salesforce_field),
+                    sf_value,
+                ),
+            ),
+            None => (source == System::Attio, source == System::Salesforce),
+        };
+
+        let conflicting = base.is_some_and(|base| {
+            resolver.detect_conflict(
+                field,
+                base.attio.get(&field.attio_field),
+                attio_value,
+                base.salesforce.get(&field.salesforce_field),
+                sf_value,
+            )
+        });
+
+        if attio_changed && sf_changed && converged(field, attio_value, sf_value) {
+            continue;
+        }
+        if conflicting && to_sf && to_attio {
+            plan.conflicts.push(field.clone());
+        } else if attio_changed && to_sf {
+            plan.to_salesforce.push(field.clone());
+        } else if sf_changed && to_attio {
+            plan.to_attio.push(field.clone());
+        }
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ConflictResolution, SyncDirection};
+    use serde_json::json;
+
+    fn field(attio: &str, sf: &str) -> FieldMapping {
+        FieldMapping {
+            attio_field: attio.to_string(),
+            salesforce_field: sf.to_string(),
+            transform: Default::default(),
+            required: false,
+            direction: FieldSyncDirection::Bidirectional,
+            field_type: None,
+            conflict_resolution: None,
+        }
+    }
+
+    fn values(pairs: &[(&str, Value)]) -> FieldValues {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_merge_three_way() {
+        let resolver = ConflictResolver::new(ConflictResolution::LastWrite);
+        let direction = SyncDirectionHandler::new(SyncDirection::Bidirectional);
+        let fields = vec![
+            field("name", "Name"),
+            field("description", "Description"),
+            field("industry", "Industry"),
+            field("city", "BillingCity"),
+            field("website", "Website"),
+        ];
+        let link = IdMapping::new("companies", "rec_1", "Account", "001xxx");
+        let base = BaseSnapshot::new(
+            &link,
+            values(&[
+                ("name", json!("Acme")),
+                ("description", json!("Old")),
+                ("industry", json!("Tech")),
+                ("city", json!("Paris")),
+                ("website", json!("acme.cmo")),
+            ]),
+            values(&[
+                ("Name", json!("Acme")),
+                ("Description", json!("Old")),
+                ("Industry", json!("Tech")),
+                ("BillingCity", json!("Paris")),
+                ("Website", json!("acme.cmo")),
+            ]),
+        );
+
+        let attio = values(&[
+            ("name", json!("Acme Inc")),
+            ("description", json!("Old")),
+            ("industry", json!("Software")),
+            ("city", json!("Paris")),
+            ("website", json!("acme.com")),
+        ]);
+        // Both sides fixed the same typo: neither a conflict nor a copy
+        let salesforce = values(&[
+            ("Name", json!("Acme")),
+            ("Description", json!("New")),
+            ("Industry", json!("Hardware")),
+            ("BillingCity", json!("Paris")),
+            ("Website", json!("https://acme.com/")),
+        ]);
+
+        let plan = plan_merge(
+            &resolver,
+            &fields,
+            &direction,
+            System::Attio,
+            &attio,
+            &salesforce,
+            Some(&base),
+        );
+        assert_eq!(plan.to_salesforce.len(), 1);
+        assert_eq!(plan.to_salesforce[0].attio_field, "name");
+        assert_eq!(plan.to_attio.len(), 1);
+        assert_eq!(plan.to_attio[0].attio_field, "description");
+        assert_eq!(plan.conflicts.len(), 1);
+        assert_eq!(plan.conflicts[0].attio_field, "industry");
+    }
+
+    #[test]
+    fn test_plan_merge_without_base_follows_source() {
+        let resolver = ConflictResolver::new(ConflictResolution::LastWrite);
+        let direction = SyncDirectionHandler::new(SyncDirection::Bidirectional);
+        let mut one_way = field("description", "Description");
+        one_way.direction = FieldSyncDirection::AttioToSalesforce;
+        let fields = vec![field("name", "Name"), one_way];
+
+        let attio = values(&[("name", json!("Acme")), ("description", json!("A"))]);
+        let salesforce = values(&[("Name", json!("Other")), ("Description", json!("B"))]);
+
+        let plan = plan_merge(
+            &resolver,
+            &fields,
+            &direction,
+            System::Salesforce,
+            &attio,
+            &salesforce,
+            None,
+        );
+        assert_eq!(plan.to_attio.len(), 1);
+        assert_eq!(plan.to_attio[0].attio_field, "name");
+        assert!(plan.to_salesforce.is_empty());
+        assert!(plan.conflicts.is_empty());
+    }
+}
@@ -1,13 +1,37 @@
 //! Sync engine for bidirectional data synchronization.
 
+pub mod audit;
 mod batch;
+pub mod checkpoint;
+pub mod compare;
 mod conflict;
 pub mod cursor;
 mod direction;
+pub mod echo;
 mod engine;
+pub mod feed;
+pub mod history;
+pub mod lock;
+pub mod matching;
+pub mod merge;
+pub mod pending;
+pub mod plan;
+pub mod queue;
+pub mod scheduled;
 
 pub use batch::BatchProcessor;
-pub use conflict::{ConflictRecord, ConflictResolver};
+pub use checkpoint::FullSyncCheckpoint;
+pub use conflict::{
+    ConflictFilter, ConflictRecord, ConflictResolver, ConflictStatus, ConflictWinner, FieldChoice,
+    FieldConflict, ManualResolution,
+};
 pub use cursor::SyncCursor;
 pub use direction::SyncDirectionHandler;
-pub use engine::SyncEngine;
+pub use engine::{SyncEngine, SyncResult};
+pub use feed::{ChangeFeed, MemoryChangeFeed, RecordChange};
+pub use history::{RunStatus, SyncRun, SyncTrigger};
+pub use lock::{Lock, LockLease, MemoryLock};
+pub use matching::{CandidateFilter, CandidateStatus, LinkCandidate};
+pub use merge::{MergeContext, MergeOutcome, MergeStrategy};
+pub use pending::PendingReference;
+pub use plan::{SyncMode, SyncPlan};
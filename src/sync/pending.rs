@@ -0,0 +1,160 @@
+//! Pending references: lookups written once their target is linked.
+//!
+//! When a record references one that has no link yet (e.g., a Contact whose
+//! Account has not synced), the record is written without the lookup and a
+//! pending entry remembers it. As soon as the referenced record is linked,
+//! the engine patches the lookup field and drops the entry.
+
+use crate::error::Result;
+use crate::storage::Storage;
+use crate::sync::echo::System;
+use crate::transform::reference::{attio_reference, IdMapping};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A reference field left unwritten because its target was not linked
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingReference {
+    /// Entry ID (one per record and field)
+    pub id: String,
+
+    /// Side of the link that is missing the reference
+    pub target: System,
+
+    /// Link of the record to patch
+    pub link: IdMapping,
+
+    /// Field to patch on the target side
+    pub field: String,
+
+    /// Object of the referenced record, on the source side
+    pub referenced_object: String,
+
+    /// ID of the referenced record, on the source side
+    pub referenced_id: String,
+
+    /// When the reference was first left unwritten
+    pub created_at: DateTime<Utc>,
+}
+
+/// Outcome of translating one reference field of a record
+#[derive(Debug, Clone)]
+pub struct ReferenceCheck {
+    /// Side the translated reference is written to
+    pub target: System,
+
+    /// Field on the target side
+    pub field: String,
+
+    /// Referenced record (object, ID) on the source side, if it is not linked
+    pub unlinked: Option<(String, String)>,
+}
+
+impl PendingReference {
+    /// Entry for a reference field of a linked record
+    pub fn new(
+        target: System,
+        link: &IdMapping,
+        field: &str,
+        referenced_object: &str,
+        referenced_id: &str,
+    ) -> Self {
+        Self {
+            id: Self::key(target, link, field),
+            target,
+            link: link.clone(),
+            field: field.to_string(),
+            referenced_object: referenced_object.to_string(),
+            referenced_id: referenced_id.to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// ID of the entry for a field of a linked record
+    pub fn key(target: System, link: &IdMapping, field: &str) -> String {
+        let (object, record_id) = match target {
+            System::Attio => (&link.attio_object, &link.attio_id),
+            System::Salesforce => (&link.salesforce_object, &link.salesforce_id),
+        };
+        format!("{}:{}:{}:{}", target.as_str(), object, record_id, field)
+    }
+
+    /// Object of the record to patch
+    pub fn target_object(&self) -> &str {
+        match self.target {
+            System::Attio => &self.link.attio_object,
+            System::Salesforce => &self.link.salesforce_object,
+        }
+    }
+
+    /// ID of the record to patch
+    pub fn target_id(&self) -> &str {
+        match self.target {
+            System::Attio => &self.link.attio_id,
+            System::Salesforce => &self.link.salesforce_id,
+        }
+    }
+
+    /// Value to patch the field with, if `link` is the referenced record's new link
+    pub fn resolved_by(&self, link: &IdMapping) -> Option<Value> {
+        match self.target {
+            System::Salesforce => (self.referenced_object == link.attio_object
+                && self.referenced_id == link.attio_id)
+                .then(|| Value::String(link.salesforce_id.clone())),
+            System::Attio => (self.referenced_object == link.salesforce_object
+                && self.referenced_id == link.salesforce_id)
+                .then(|| attio_reference(&link.attio_object, &link.attio_id)),
+        }
+    }
+
+    /// Check whether the entry has been waiting longer than `max_age`
+    pub fn is_stale(&self, now: DateTime<Utc>, max_age: Duration) -> bool {
+        now - self.created_at > max_age
+    }
+}
+
+/// Pending references waiting longer than `max_age`, oldest first
+pub async fn list_stale(
+    storage: &dyn Storage,
+    max_age: Duration,
+    limit: usize,
+) -> Result<Vec<PendingReference>> {
+    let now = Utc::now();
+    Ok(storage
+        .list_pending_references(limit)
+        .await?
+        .into_iter()
+        .take_while(|pending| pending.is_stale(now, max_age))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolved_by_referenced_link() {
+        let contact = IdMapping::new("people", "per_1", "Contact", "003a");
+        let pending = PendingReference::new(
+            System::Salesforce,
+            &contact,
+            "AccountId",
+            "companies",
+            "rec_1",
+        );
+        assert_eq!(pending.id, "salesforce:Contact:003a:AccountId");
+        assert_eq!(pending.target_id(), "003a");
+
+        let other = IdMapping::new("companies", "rec_2", "Account", "001b");
+        assert!(pending.resolved_by(&other).is_none());
+        let account = IdMapping::new("companies", "rec_1", "Account", "001a");
+        assert_eq!(
+            pending.resolved_by(&account),
+            Some(Value::String("001a".to_string()))
+        );
+
+        assert!(!pending.is_stale(Utc::now(), Duration::hours(1)));
+        assert!(pending.is_stale(Utc::now() + Duration::hours(2), Duration::hours(1)));
+    }
+}
@@ -0,0 +1,175 @@
+//! Dry-run sync plans.
+//!
+//! In [`SyncMode::Plan`] the engine reads both systems and runs the same
+//! transforms and conflict detection as a real sync, but records what it
+//! would write instead of writing it.
+
+use crate::config::SyncDirection;
+use crate::sync::audit::{AuditAction, FieldChange};
+use crate::sync::conflict::ConflictRecord;
+use crate::sync::echo::System;
+use crate::transform::reference::IdMapping;
+use serde::{Deserialize, Serialize};
+
+/// Whether the engine writes its changes or only plans them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncMode {
+    /// Write changes to both systems
+    #[default]
+    Apply,
+    /// Read only and return a [`SyncPlan`]
+    Plan,
+}
+
+/// A write the engine would make
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedChange {
+    /// Kind of write
+    pub action: AuditAction,
+
+    /// Direction of the write
+    pub direction: SyncDirection,
+
+    /// Object type the values come from
+    pub source_object: String,
+
+    /// Record ID the values come from
+    pub source_id: String,
+
+    /// Object type to write to
+    pub target_object: String,
+
+    /// Record ID to write to (`None` for creates)
+    pub target_id: Option<String>,
+
+    /// Field-level diff
+    pub changes: Vec<FieldChange>,
+}
+
+/// Everything a sync would do
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncPlan {
+    /// Records that would be created
+    pub creates: Vec<PlannedChange>,
+
+    /// Records that would be updated
+    pub updates: Vec<PlannedChange>,
+
+    /// Records that would be deleted
+    pub deletes: Vec<PlannedChange>,
+
+    /// Conflicts that would be detected
+    pub conflicts: Vec<ConflictRecord>,
+}
+
+impl PlannedChange {
+    /// New record on the `target` side, copied from a source record
+    pub fn create(
+        target: System,
+        source_object: &str,
+        source_id: &str,
+        target_object: &str,
+        changes: Vec<FieldChange>,
+    ) -> Self {
+        Self {
+            action: AuditAction::Create,
+            direction: direction_to(target),
+            source_object: source_object.to_string(),
+            source_id: source_id.to_string(),
+            target_object: target_object.to_string(),
+            target_id: None,
+            changes,
+        }
+    }
+
+    /// Update or delete of the `target` side of a link
+    pub fn linked(
+        action: AuditAction,
+        target: System,
+        link: &IdMapping,
+        changes: Vec<FieldChange>,
+    ) -> Self {
+        let attio = (&link.attio_object, &link.attio_id);
+        let salesforce = (&link.salesforce_object, &link.salesforce_id);
+        let (source, target_record) = match target {
+            System::Salesforce => (attio, salesforce),
+            System::Attio => (salesforce, attio),
+        };
+        Self {
+            action,
+            direction: direction_to(target),
+            source_object: source.0.clone(),
+            source_id: source.1.clone(),
+            target_object: target_record.0.clone(),
+            target_id: Some(target_record.1.clone()),
+            changes,
+        }
+    }
+}
+
+impl SyncPlan {
+    /// Add a planned write under its action
+    pub fn push(&mut self, change: PlannedChange) {
+        match change.action {
+            AuditAction::Create => self.creates.push(change),
+            AuditAction::Update => self.updates.push(change),
+            AuditAction::Delete => self.deletes.push(change),
+        }
+    }
+
+    /// Append another plan
+    pub fn merge(&mut self, other: SyncPlan) {
+        self.creates.extend(other.creates);
+        self.updates.extend(other.updates);
+        self.deletes.extend(other.deletes);
+        self.conflicts.extend(other.conflicts);
+    }
+
+    /// Check whether the sync would do nothing
+    pub fn is_empty(&self) -> bool {
+        self.creates.is_empty()
+            && self.updates.is_empty()
+            && self.deletes.is_empty()
+            && self.conflicts.is_empty()
+    }
+}
+
+/// Direction of a write to the given side
+fn direction_to(target: System) -> SyncDirection {
+    match target {
+        System::Salesforce => SyncDirection::AttioToSalesforce,
+        System::Attio => SyncDirection::SalesforceToAttio,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_groups_by_action() {
+        let link = IdMapping::new("companies", "rec_1", "Account", "001xxx");
+        let mut plan = SyncPlan::default();
+        assert!(plan.is_empty());
+
+        plan.push(PlannedChange::create(
+            System::Salesforce,
+            "companies",
+            "rec_2",
+            "Account",
+            vec![],
+        ));
+        plan.push(PlannedChange::linked(
+            AuditAction::Update,
+            System::Attio,
+            &link,
+            vec![],
+        ));
+        assert_eq!(plan.creates.len(), 1);
+        assert_eq!(plan.creates[0].target_id, None);
+        assert_eq!(plan.updates[0].source_id, "001xxx");
+        assert_eq!(plan.updates[0].target_id.as_deref(), Some("rec_1"));
+        assert_eq!(plan.updates[0].direction, SyncDirection::SalesforceToAttio);
+    }
+}
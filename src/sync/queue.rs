@@ -0,0 +1,129 @@
+//! Queue message types for asynchronous webhook processing.
+
+use crate::attio::webhooks::AttioWebhookEvent;
+use crate::error::{Error, Result};
+use crate::salesforce::webhooks::SalesforceWebhookEvent;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Default delay before retrying a message that failed with a retryable error
+pub const DEFAULT_RETRY_DELAY_SECS: u32 = 30;
+
+/// Queue name recorded on records incremental sync gave up on
+pub const INCREMENTAL_SYNC_QUEUE: &str = "incremental_sync";
+
+/// Verified webhook event enqueued for processing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "source", content = "event", rename_all = "snake_case")]
+pub enum WebhookMessage {
+    /// Event delivered by Attio
+    Attio(AttioWebhookEvent),
+    /// Change event delivered by the Salesforce relay
+    Salesforce(SalesforceWebhookEvent),
+}
+
+/// What to do with a queue message after processing it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageOutcome {
+    /// Processing succeeded, acknowledge the message
+    Ack,
+    /// Processing failed with a retryable error, redeliver after a delay
+    Retry { delay_seconds: u32 },
+    /// Processing failed permanently, record the failure and acknowledge
+    Fail { error: String },
+}
+
+/// Message that could not be processed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedMessage {
+    /// Queue message ID
+    pub message_id: String,
+
+    /// Queue the message was consumed from
+    pub queue: String,
+
+    /// Raw message body
+    pub body: serde_json::Value,
+
+    /// Last error
+    pub error: String,
+
+    /// When the failure was recorded
+    pub failed_at: DateTime<Utc>,
+}
+
+impl MessageOutcome {
+    /// Decide the outcome for a processing result
+    pub fn from_result<T>(result: &Result<T>) -> Self {
+        match result {
+            Ok(_) => Self::Ack,
+            Err(Error::RateLimit {
+                retry_after_secs, ..
+            }) => Self::Retry {
+                delay_seconds: u32::try_from(*retry_after_secs).unwrap_or(u32::MAX),
+            },
+            Err(e) if e.is_retryable() => Self::Retry {
+                delay_seconds: DEFAULT_RETRY_DELAY_SECS,
+            },
+            Err(e) => Self::Fail {
+                error: e.to_string(),
+            },
+        }
+    }
+}
+
+impl FailedMessage {
+    /// Create a failure record timestamped now
+    pub fn new(
+        message_id: impl Into<String>,
+        queue: impl Into<String>,
+        body: serde_json::Value,
+        error: impl Into<String>,
+    ) -> Self {
+        Self {
+            message_id: message_id.into(),
+            queue: queue.into(),
+            body,
+            error: error.into(),
+            failed_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_outcome() {
+        assert_eq!(MessageOutcome::from_result(&Ok(())), MessageOutcome::Ack);
+        assert_eq!(
+            MessageOutcome::from_result::<()>(&Err(Error::rate_limit("attio", 120))),
+            MessageOutcome::Retry { delay_seconds: 120 }
+        );
+        assert_eq!(
+            MessageOutcome::from_result::<()>(&Err(Error::attio_api("get_record", "timeout"))),
+            MessageOutcome::Retry {
+                delay_seconds: DEFAULT_RETRY_DELAY_SECS
+            }
+        );
+        assert!(matches!(
+            MessageOutcome::from_result::<()>(&Err(Error::validation("bad payload"))),
+            MessageOutcome::Fail { .. }
+        ));
+    }
+
+    #[test]
+    fn test_webhook_message_roundtrip() {
+        let payload = r#"{
+            "source": "salesforce",
+            "event": {"entityName": "Contact", "recordIds": ["003xxx"], "changeType": "CREATE"}
+        }"#;
+
+        let message: WebhookMessage = serde_json::from_str(payload).unwrap();
+        assert!(matches!(message, WebhookMessage::Salesforce(ref e) if e.entity_name == "Contact"));
+
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["source"], "salesforce");
+    }
+}
@@ -0,0 +1,231 @@
+//! Scheduled incremental sync.
+//!
+//! Runs on the worker's cron trigger as a safety net for missed webhooks, and
+//! on demand from the API and CLI.
+
+use crate::config::ObjectMapping;
+use crate::error::Result;
+use crate::storage::Storage;
+use crate::sync::engine::SyncEngine;
+use crate::sync::engine::SyncResult;
+use crate::sync::history::{RunStatus, SyncRun};
+use crate::sync::plan::SyncMode;
+
+/// Storage key for the incremental sync cursor of a mapping
+pub fn cursor_key(mapping: &ObjectMapping) -> String {
+    format!("incremental:{}", mapping.key())
+}
+
+/// Run incremental sync for each enabled mapping, referenced objects first; the engine records one run per mapping.
+///
+/// The cursor for a mapping only advances when its run succeeds.
+pub async fn run_scheduled_sync(
+    engine: &mut SyncEngine,
+    storage: &dyn Storage,
+) -> Result<Vec<SyncRun>> {
+    let config = engine.config().clone();
+    if !config.sync.scheduled_enabled {
+        return Ok(vec![]);
+    }
+
+    let mut runs = Vec::new();
+    for mapping in config.ordered_mappings()? {
+        // A failed sync is recorded in its run; only failing to record it stops the schedule
+        let (run, _) = sync_mapping(engine, storage, &mapping).await?;
+        runs.push(run);
+    }
+
+    Ok(runs)
+}
+
+/// Run incremental sync now for the enabled mappings, optionally only those of one object.
+///
+/// In plan mode the merged result carries the plan and no cursor is saved.
+pub async fn run_manual_sync(
+    engine: &mut SyncEngine,
+    storage: &dyn Storage,
+    object: Option<&str>,
+) -> Result<SyncResult> {
+    let config = engine.config().clone();
+    let mut merged = SyncResult {
+        direction: config.sync.direction,
+        ..SyncResult::default()
+    };
+    for mapping in config.ordered_mappings()? {
+        if object.is_some_and(|o| o != mapping.attio_object && o != mapping.salesforce_object) {
+            continue;
+        }
+        let (_, result) = sync_mapping(engine, storage, &mapping).await?;
+        merged.merge(result?);
+    }
+    Ok(merged)
+}
+
+/// Sync one mapping from its stored cursor, saving the cursor unless the run failed.
+///
+/// A partial run still saves: its cursor stops before the first failed record.
+async fn sync_mapping(
+    engine: &mut SyncEngine,
+    storage: &dyn Storage,
+    mapping: &ObjectMapping,
+) -> Result<(SyncRun, Result<SyncResult>)> {
+    let key = cursor_key(mapping);
+    let cursor = storage.get_cursor(&key).await?;
+    let (run, result) = engine.incremental_sync_run(mapping, cursor).await?;
+
+    if let Ok(synced) = &result {
+        if run.status != RunStatus::Failed && engine.mode() == SyncMode::Apply {
+            if let Some(cursor) = &synced.cursor {
+                storage.save_cursor(&key, cursor).await?;
+            }
+        }
+    }
+    Ok((run, result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AttioConfig, Config, SalesforceConfig, SyncConfig};
+    use crate::storage::{MemoryStorage, Page};
+    use crate::sync::feed::MemoryChangeFeed;
+    use crate::sync::lock::{Lock, MemoryLock};
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    fn test_config(scheduled_enabled: bool) -> Config {
+        Config {
+            sync: SyncConfig {
+                scheduled_enabled,
+                ..SyncConfig::default()
+            },
+            attio: AttioConfig {
+                api_key: "test_key".to_string(),
+                webhook_secret: None,
+                api_token_id: None,
+                base_url: "https://api.attio.com".to_string(),
+            },
+            salesforce: SalesforceConfig {
+                client_id: "client_id".to_string(),
+                client_secret: "client_secret".to_string(),
+                instance_url: "https://test.salesforce.com".to_string(),
+                refresh_token: None,
+                api_version: "v59.0".to_string(),
+                webhook_secret: None,
+                integration_user_id: None,
+            },
+            mappings: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_sync_saves_cursors() {
+        let storage = MemoryStorage::new();
+        let mut engine =
+            SyncEngine::new(test_config(true)).with_change_feed(Rc::new(MemoryChangeFeed::new()));
+
+        let runs = run_scheduled_sync(&mut engine, &storage).await.unwrap();
+        assert!(!runs.is_empty());
+        assert!(runs.iter().all(|r| r.status == RunStatus::Succeeded));
+
+        let mapping = engine.config().enabled_mappings().remove(0);
+        assert!(storage
+            .get_cursor(&cursor_key(&mapping))
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_locked_mapping_fails_without_saving_cursor() {
+        let storage = MemoryStorage::new();
+        let lock = Rc::new(MemoryLock::new());
+        let mut engine = SyncEngine::new(test_config(true))
+            .with_lock(lock.clone())
+            .with_change_feed(Rc::new(MemoryChangeFeed::new()));
+        let mappings = engine.config().ordered_mappings().unwrap();
+        let locked = &mappings[0];
+        lock.acquire(&locked.key(), "backfill", chrono::Duration::seconds(60))
+            .await
+            .unwrap()
+            .unwrap();
+
+        // The locked mapping fails; the others still run and save their cursors
+        let runs = run_scheduled_sync(&mut engine, &storage).await.unwrap();
+        assert_eq!(runs.len(), mappings.len());
+        assert_eq!(runs[0].status, RunStatus::Failed);
+        assert!(runs[1..].iter().all(|r| r.status == RunStatus::Succeeded));
+        // Each run is the one recorded for its own mapping
+        for (run, mapping) in runs.iter().zip(&mappings) {
+            assert_eq!(run.objects, vec![mapping.attio_object.clone()]);
+        }
+        assert_ne!(runs[0].id, runs[1].id);
+        assert!(storage
+            .get_cursor(&cursor_key(locked))
+            .await
+            .unwrap()
+            .is_none());
+        assert!(storage
+            .get_cursor(&cursor_key(&mappings[1]))
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_manual_sync_fails_on_locked_mapping() {
+        let storage = MemoryStorage::new();
+        let lock = Rc::new(MemoryLock::new());
+        let mut engine = SyncEngine::new(test_config(false))
+            .with_lock(lock.clone())
+            .with_change_feed(Rc::new(MemoryChangeFeed::new()));
+        let mapping = ObjectMapping::from_defaults("companies", "Account").unwrap();
+        lock.acquire(&mapping.key(), "cron", chrono::Duration::seconds(60))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(run_manual_sync(&mut engine, &storage, Some("companies"))
+            .await
+            .is_err());
+        assert!(storage
+            .get_cursor(&cursor_key(&mapping))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_sync_disabled() {
+        let storage = MemoryStorage::new();
+        let mut engine = SyncEngine::new(test_config(false));
+
+        let runs = run_scheduled_sync(&mut engine, &storage).await.unwrap();
+        assert!(runs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_manual_sync_plan_writes_nothing() {
+        let storage = Rc::new(MemoryStorage::new());
+        let mut engine = SyncEngine::new(test_config(false))
+            .with_storage(storage.clone())
+            .with_change_feed(Rc::new(MemoryChangeFeed::new()))
+            .with_mode(SyncMode::Plan);
+
+        let result = run_manual_sync(&mut engine, &*storage, Some("Account"))
+            .await
+            .unwrap();
+        assert!(result.plan.is_empty());
+        let mapping = engine.config().enabled_mappings().remove(0);
+        assert!(storage
+            .get_cursor(&cursor_key(&mapping))
+            .await
+            .unwrap()
+            .is_none());
+        assert!(storage
+            .list_runs(None, Page::default())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
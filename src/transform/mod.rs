@@ -1,12 +1,13 @@
 //! Field transformation pipeline.
 
 mod builtin;
-mod field;
+pub mod field;
+pub mod normalize;
 pub mod reference;
 
 pub use builtin::BuiltinTransforms;
 pub use field::FieldTransformer;
-pub use reference::ReferenceResolver;
+pub use reference::{attio_reference_ids, ReferenceResolver};
 
 use crate::config::mappings::{FieldMapping, TransformType};
 use crate::error::{Error, Result};
@@ -15,6 +16,7 @@ use serde_json::Value;
 /// Transform pipeline for converting between Attio and Salesforce formats
 pub struct TransformPipeline {
     builtin: BuiltinTransforms,
+    references: ReferenceResolver,
 }
 
 impl TransformPipeline {
@@ -22,9 +24,15 @@ impl TransformPipeline {
     pub fn new() -> Self {
         Self {
             builtin: BuiltinTransforms::new(),
+            references: ReferenceResolver::new(),
         }
     }
 
+    /// Replace the ID mappings used to translate reference fields
+    pub fn set_references(&mut self, references: ReferenceResolver) {
+        self.references = references;
+    }
+
     /// Transform a value using the specified transform type
     pub fn transform(&self, value: &Value, transform: &TransformType) -> Result<Value> {
         match transform {
@@ -42,6 +50,11 @@ impl TransformPipeline {
 
             TransformType::EmployeeRangeToNumber => self.builtin.employee_range_to_number(value),
 
+            TransformType::Reference { attio_object, .. } => self
+                .references
+                .reference_to_salesforce(attio_object, value)
+                .ok_or_else(|| Error::not_found(value.to_string(), "ID mapping")),
+
             TransformType::Custom { function_name } => {
                 Err(Error::transform(
                     function_name,
@@ -61,7 +74,11 @@ impl TransformPipeline {
 
         for mapping in mappings {
             if let Some(attio_value) = self.get_nested_value(attio_data, &mapping.attio_field) {
-                let transformed = self.transform(&attio_value, &mapping.transform)?;
+                let transformed = match self.transform(&attio_value, &mapping.transform) {
+                    // References to unlinked records are left out rather than failing the record
+                    Err(Error::NotFound { .. }) if mapping.is_reference() => continue,
+                    transformed => transformed?,
+                };
                 sf_data.insert(mapping.salesforce_field.clone(), transformed);
             }
         }
@@ -75,21 +92,38 @@ impl TransformPipeline {
         sf_data: &Value,
         mappings: &[FieldMapping],
     ) -> Result<Value> {
-        let mut attio_data = serde_json::Map::new();
+        let mut attio_data = Value::Object(serde_json::Map::new());
 
         for mapping in mappings {
             if let Some(sf_value) = sf_data.get(&mapping.salesforce_field) {
-                // For reverse transform, we need inverse transforms
-                // For now, just do direct copy
-                attio_data.insert(mapping.attio_field.clone(), sf_value.clone());
+                let attio_value = match &mapping.transform {
+                    TransformType::Reference {
+                        attio_object,
+                        salesforce_object,
+                    } => match self.references.reference_to_attio(
+                        salesforce_object,
+                        attio_object,
+                        sf_value,
+                    ) {
+                        Some(reference) => reference,
+                        None => continue,
+                    },
+                    // For reverse transform, we need inverse transforms
+                    // For now, just do direct copy
+                    _ => sf_value.clone(),
+                };
+                Self::set_nested_value(&mut attio_data, &mapping.attio_field, attio_value);
             }
         }
 
-        Ok(Value::Object(attio_data))
+        Ok(attio_data)
     }
 
-    /// Get a nested value from JSON using dot notation path
-    fn get_nested_value(&self, data: &Value, path: &str) -> Option<Value> {
+    /// Get a nested value from JSON using dot notation path.
+    ///
+    /// Paths are lenient about single values: `[0]` on a non-array returns the
+    /// value itself, and a key lookup on an array looks in its first element.
+    pub fn get_nested_value(&self, data: &Value, path: &str) -> Option<Value> {
         let parts: Vec<&str> = path.split('.').collect();
         let mut current = data.clone();
 
@@ -100,14 +134,60 @@ impl TransformPipeline {
                 let index_str = &part[bracket_pos + 1..part.len() - 1];
                 let index: usize = index_str.parse().ok()?;
 
-                current = current.get(field_name)?.get(index)?.clone();
+                current = match Self::get_field(&current, field_name)? {
+                    Value::Array(items) => items.get(index)?.clone(),
+                    single if index == 0 => single.clone(),
+                    _ => return None,
+                };
             } else {
-                current = current.get(part)?.clone();
+                current = Self::get_field(&current, part)?.clone();
             }
         }
 
         Some(current)
     }
+
+    fn get_field<'a>(value: &'a Value, field: &str) -> Option<&'a Value> {
+        match value {
+            Value::Array(items) => items.first()?.get(field),
+            _ => value.get(field),
+        }
+    }
+
+    /// Set a nested value in JSON using dot notation path, creating objects
+    /// and single-element arrays as needed
+    pub fn set_nested_value(data: &mut Value, path: &str, value: Value) {
+        let mut current = data;
+
+        for part in path.split('.') {
+            let (field_name, is_array) = match part.find('[') {
+                Some(bracket_pos) => (&part[..bracket_pos], true),
+                None => (part, false),
+            };
+
+            if !current.is_object() {
+                *current = Value::Object(serde_json::Map::new());
+            }
+            let mut slot = current
+                .as_object_mut()
+                .expect("value was just made an object")
+                .entry(field_name.to_string())
+                .or_insert(Value::Null);
+
+            if is_array {
+                if !slot.is_array() {
+                    *slot = Value::Array(vec![Value::Null]);
+                }
+                slot = slot
+                    .as_array_mut()
+                    .and_then(|items| items.first_mut())
+                    .expect("array has an element");
+            }
+            current = slot;
+        }
+
+        *current = value;
+    }
 }
 
 impl Default for TransformPipeline {
@@ -151,5 +231,96 @@ mod tests {
             pipeline.get_nested_value(&data, "emails[0].email"),
             Some(serde_json::json!("primary@example.com"))
         );
+
+        // Single values are treated as one-element arrays
+        assert_eq!(
+            pipeline.get_nested_value(&data, "name[0].first_name"),
+            Some(serde_json::json!("John"))
+        );
+        assert_eq!(
+            pipeline.get_nested_value(&data, "emails.email"),
+            Some(serde_json::json!("primary@example.com"))
+        );
+    }
+
+    #[test]
+    fn test_salesforce_to_attio_nests_paths() {
+        let pipeline = TransformPipeline::new();
+        let mapping = |attio: &str, sf: &str| FieldMapping {
+            attio_field: attio.to_string(),
+            salesforce_field: sf.to_string(),
+            transform: TransformType::Direct,
+            required: false,
+            direction: Default::default(),
+            field_type: None,
+            conflict_resolution: None,
+        };
+        let sf_data = serde_json::json!({
+            "Email": "jane@example.com",
+            "FirstName": "Jane",
+            "MailingCity": "Berlin"
+        });
+
+        let attio_data = pipeline
+            .salesforce_to_attio(
+                &sf_data,
+                &[
+                    mapping("email_addresses[0].email_address", "Email"),
+                    mapping("name.first_name", "FirstName"),
+                    mapping("primary_location.locality", "MailingCity"),
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(
+            attio_data,
+            serde_json::json!({
+                "email_addresses": [{"email_address": "jane@example.com"}],
+                "name": {"first_name": "Jane"},
+                "primary_location": {"locality": "Berlin"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_reference_fields_use_links() {
+        let mut pipeline = TransformPipeline::new();
+        let mut references = ReferenceResolver::new();
+        references.add_mapping(crate::transform::reference::IdMapping::new(
+            "companies",
+            "rec_1",
+            "Account",
+            "001a",
+        ));
+        pipeline.set_references(references);
+        let field = FieldMapping {
+            attio_field: "company".to_string(),
+            salesforce_field: "AccountId".to_string(),
+            transform: TransformType::Reference {
+                attio_object: "companies".to_string(),
+                salesforce_object: "Account".to_string(),
+            },
+            required: false,
+            direction: Default::default(),
+            field_type: None,
+            conflict_resolution: None,
+        };
+        let fields = [field];
+
+        let attio = serde_json::json!({
+            "company": {"target_object": "companies", "target_record_id": "rec_1"}
+        });
+        let sf_data = pipeline.attio_to_salesforce(&attio, &fields).unwrap();
+        assert_eq!(sf_data, serde_json::json!({"AccountId": "001a"}));
+
+        let attio_data = pipeline.salesforce_to_attio(&sf_data, &fields).unwrap();
+        assert_eq!(attio_data["company"][0]["target_record_id"], "rec_1");
+
+        // Unlinked targets are left out
+        let unlinked = serde_json::json!({
+            "company": {"target_object": "companies", "target_record_id": "rec_2"}
+        });
+        let sf_data = pipeline.attio_to_salesforce(&unlinked, &fields).unwrap();
+        assert_eq!(sf_data, serde_json::json!({}));
     }
 }
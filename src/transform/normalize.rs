@@ -0,0 +1,240 @@
+//! Normalization of names, domains and emails for record matching.
+//!
+//! The same company is often spelled differently in each system ("Acme, Inc."
+//! vs "ACME Incorporated", "https://www.acme.com/" vs "acme.com"). These
+//! functions reduce values to a canonical form, and [`jaro_winkler`] scores
+//! how close two values are when they still differ.
+
+use url::Url;
+
+/// Legal-form tokens dropped from the end of company names
+const LEGAL_SUFFIXES: &[&str] = &[
+    "ab",
+    "ag",
+    "as",
+    "bv",
+    "co",
+    "company",
+    "corp",
+    "corporation",
+    "gmbh",
+    "inc",
+    "incorporated",
+    "kg",
+    "kk",
+    "limited",
+    "llc",
+    "llp",
+    "lp",
+    "ltd",
+    "mbh",
+    "nv",
+    "oy",
+    "plc",
+    "pte",
+    "pty",
+    "sa",
+    "sarl",
+    "sas",
+    "se",
+    "sl",
+    "spa",
+    "srl",
+];
+
+/// Public suffixes with two labels, under which the registrable domain has three
+const TWO_LABEL_SUFFIXES: &[&str] = &[
+    "ac.uk", "co.in", "co.jp", "co.kr", "co.nz", "co.uk", "co.za", "com.au", "com.br", "com.cn",
+    "com.mx", "com.sg", "com.tr", "gov.uk", "net.au", "org.au", "org.uk",
+];
+
+/// Lowercase, fold diacritics and replace punctuation with single spaces.
+///
+/// Periods and apostrophes are removed rather than replaced, so "S.A." and
+/// "O'Brien" stay one word, and `&` becomes "and".
+pub fn fold(value: &str) -> String {
+    let mut folded = String::with_capacity(value.len());
+    for c in value.to_lowercase().chars() {
+        match c {
+            '.' | '\'' | '’' => {}
+            '&' => folded.push_str(" and "),
+            c if c.is_alphanumeric() => match fold_char(c) {
+                Some(ascii) => folded.push_str(ascii),
+                None => folded.push(c),
+            },
+            _ => folded.push(' '),
+        }
+    }
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Company name without legal suffixes (Inc, LLC, GmbH, ...) or a leading "the"
+pub fn company_name(value: &str) -> String {
+    let folded = fold(value);
+    let mut words: Vec<&str> = folded.split(' ').filter(|w| !w.is_empty()).collect();
+    if words.len() > 1 && words[0] == "the" {
+        words.remove(0);
+    }
+    // "Smith & Co. KG" loses "kg", "co" and the dangling "and"
+    while words.len() > 1 {
+        let last = words[words.len() - 1];
+        if !LEGAL_SUFFIXES.contains(&last) && last != "and" {
+            break;
+        }
+        words.pop();
+    }
+    words.join(" ")
+}
+
+/// Registrable domain of a URL, host or email address (e.g., `acme.co.uk`)
+pub fn registrable_domain(value: &str) -> Option<String> {
+    let value = value.trim().to_lowercase();
+    let value = value.rsplit('@').next().unwrap_or_default();
+    if value.is_empty() {
+        return None;
+    }
+    let url = if value.contains("://") {
+        Url::parse(value)
+    } else {
+        Url::parse(&format!("http://{}", value))
+    };
+    let host = url.ok()?.host_str()?.trim_end_matches('.').to_string();
+
+    let labels: Vec<&str> = host.split('.').filter(|l| !l.is_empty()).collect();
+    if labels.len() < 2 || labels.iter().all(|l| l.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+    let suffix = labels[labels.len() - 2..].join(".");
+    let keep = if TWO_LABEL_SUFFIXES.contains(&suffix.as_str()) {
+        3
+    } else {
+        2
+    };
+    Some(labels[labels.len().saturating_sub(keep)..].join("."))
+}
+
+/// Email address, trimmed and lowercased, if it looks like one
+pub fn email(value: &str) -> Option<String> {
+    let email = value.trim().trim_start_matches("mailto:").to_lowercase();
+    let (local, domain) = email.split_once('@')?;
+    (!local.is_empty() && domain.contains('.')).then_some(email)
+}
+
+/// Jaro-Winkler similarity of two strings (1.0 for identical strings)
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    // Characters match if equal and no further apart than half the longer string
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0usize;
+    for (i, &c) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+        if let Some(j) = (start..end).find(|&j| !b_matched[j] && b[j] == c) {
+            a_matched[i] = true;
+            b_matched[j] = true;
+            matches += 1;
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let a_seq = a.iter().zip(&a_matched).filter(|(_, &m)| m).map(|(c, _)| c);
+    let b_seq = b.iter().zip(&b_matched).filter(|(_, &m)| m).map(|(c, _)| c);
+    let transpositions = a_seq.zip(b_seq).filter(|(x, y)| x != y).count() / 2;
+
+    let m = matches as f64;
+    let jaro = (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m) / 3.0;
+    let prefix = a.iter().zip(&b).take(4).take_while(|(x, y)| x == y).count();
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
+/// ASCII spelling of a lowercase letter with a diacritic
+fn fold_char(c: char) -> Option<&'static str> {
+    Some(match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'ğ' => "g",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'ı' => "i",
+        'ł' | 'ľ' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'œ' => "oe",
+        'ř' => "r",
+        'ß' => "ss",
+        'ś' | 'š' | 'ş' => "s",
+        'ť' | 'ţ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn fixtures() -> Value {
+        serde_json::from_str(include_str!("../../tests/fixtures/normalization.json")).unwrap()
+    }
+
+    #[test]
+    fn test_company_name_corpus() {
+        for case in fixtures()["company_names"].as_array().unwrap() {
+            let (a, b) = (case["a"].as_str().unwrap(), case["b"].as_str().unwrap());
+            let same = case["same"].as_bool().unwrap();
+            assert_eq!(
+                company_name(a) == company_name(b),
+                same,
+                "{:?} vs {:?}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn test_registrable_domain_corpus() {
+        for case in fixtures()["domains"].as_array().unwrap() {
+            let input = case["input"].as_str().unwrap();
+            assert_eq!(
+                registrable_domain(input).as_deref(),
+                case["domain"].as_str(),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_jaro_winkler_corpus() {
+        for case in fixtures()["similarity"].as_array().unwrap() {
+            let (a, b) = (case["a"].as_str().unwrap(), case["b"].as_str().unwrap());
+            let score = jaro_winkler(a, b);
+            let expected = case["score"].as_f64().unwrap();
+            assert!(
+                (score - expected).abs() < 0.001,
+                "{:?} vs {:?}: {}",
+                a,
+                b,
+                score
+            );
+        }
+    }
+}
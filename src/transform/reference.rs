@@ -1,6 +1,8 @@
 //! Reference resolution for foreign key relationships.
 
 use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 
 /// Reference resolver for looking up cross-system IDs
@@ -23,7 +25,7 @@ pub struct IdMappingKey {
 }
 
 /// ID mapping entry
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IdMapping {
     /// Attio object type
     pub attio_object: String,
@@ -36,6 +38,14 @@ pub struct IdMapping {
 
     /// Salesforce record ID
     pub salesforce_id: String,
+
+    /// Hash of the mapped fields last written to Attio
+    #[serde(default)]
+    pub attio_hash: Option<String>,
+
+    /// Hash of the mapped fields last written to Salesforce
+    #[serde(default)]
+    pub salesforce_hash: Option<String>,
 }
 
 impl ReferenceResolver {
@@ -123,6 +133,28 @@ impl ReferenceResolver {
         self.salesforce_to_attio(object, sf_id).is_some()
     }
 
+    /// Translate an Attio record-reference value into the linked Salesforce ID.
+    ///
+    /// Multi-record references resolve to their first linked target, since a
+    /// Salesforce lookup holds one ID.
+    pub fn reference_to_salesforce(&self, attio_object: &str, value: &Value) -> Option<Value> {
+        attio_reference_ids(value)
+            .iter()
+            .find_map(|id| self.attio_to_salesforce(attio_object, id))
+            .map(|sf_id| Value::String(sf_id.to_string()))
+    }
+
+    /// Translate a Salesforce lookup value into an Attio record-reference value
+    pub fn reference_to_attio(
+        &self,
+        salesforce_object: &str,
+        attio_object: &str,
+        value: &Value,
+    ) -> Option<Value> {
+        let attio_id = self.salesforce_to_attio(salesforce_object, value.as_str()?)?;
+        Some(attio_reference(attio_object, attio_id))
+    }
+
     /// Get all mappings (for persistence)
     pub fn get_all_mappings(&self) -> Vec<IdMapping> {
         // This is a simplified implementation - in practice, we'd need
@@ -137,6 +169,28 @@ impl Default for ReferenceResolver {
     }
 }
 
+/// Attio record-reference value pointing at one record
+pub fn attio_reference(attio_object: &str, attio_id: &str) -> Value {
+    serde_json::json!([{
+        "target_object": attio_object,
+        "target_record_id": attio_id,
+    }])
+}
+
+/// Target record IDs of an Attio record-reference value (one reference or a list)
+pub fn attio_reference_ids(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(items) => items.iter().flat_map(attio_reference_ids).collect(),
+        Value::Object(fields) => fields
+            .get("target_record_id")
+            .and_then(Value::as_str)
+            .map(|id| vec![id.to_string()])
+            .unwrap_or_default(),
+        Value::String(id) => vec![id.clone()],
+        _ => Vec::new(),
+    }
+}
+
 impl IdMapping {
     /// Create a new ID mapping
     pub fn new(
@@ -150,6 +204,8 @@ impl IdMapping {
             attio_id: attio_id.into(),
             salesforce_object: salesforce_object.into(),
             salesforce_id: salesforce_id.into(),
+            attio_hash: None,
+            salesforce_hash: None,
         }
     }
 }
@@ -195,4 +251,36 @@ mod tests {
         assert!(resolver.has_attio_mapping("people", "rec_456"));
         assert!(!resolver.has_attio_mapping("people", "rec_unknown"));
     }
+
+    #[test]
+    fn test_id_mapping_without_hashes() {
+        let mapping: IdMapping = serde_json::from_str(
+            r#"{"attio_object": "companies", "attio_id": "rec_1", "salesforce_object": "Account", "salesforce_id": "001xxx"}"#,
+        )
+        .unwrap();
+        assert!(mapping.attio_hash.is_none());
+        assert!(mapping.salesforce_hash.is_none());
+    }
+
+    #[test]
+    fn test_reference_values() {
+        let mut resolver = ReferenceResolver::new();
+        resolver.add_mapping(IdMapping::new("companies", "rec_1", "Account", "001a"));
+
+        let attio = serde_json::json!([
+            {"target_object": "companies", "target_record_id": "rec_unlinked"},
+            {"target_object": "companies", "target_record_id": "rec_1"}
+        ]);
+        assert_eq!(
+            resolver.reference_to_salesforce("companies", &attio),
+            Some(Value::String("001a".to_string()))
+        );
+        assert_eq!(
+            resolver.reference_to_attio("Account", "companies", &Value::String("001a".into())),
+            Some(serde_json::json!([{"target_object": "companies", "target_record_id": "rec_1"}]))
+        );
+        assert!(resolver
+            .reference_to_attio("Account", "companies", &Value::String("001b".into()))
+            .is_none());
+    }
 }
@@ -0,0 +1,153 @@
+//! `SyncLock` Durable Object backing the engine's `Lock` trait.
+//!
+//! One Durable Object instance exists per lock key, so all lease transitions for
+//! a mapping are serialized by the runtime.
+
+use crate::sync::lock::{acquire_lease, may_release, renew_lease, Lock, LockLease};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use worker::wasm_bindgen::JsValue;
+use worker::{
+    durable_object, wasm_bindgen_futures, Env, Method, Request, RequestInit, Response, Result,
+    State,
+};
+
+/// Durable Object namespace binding for sync locks
+pub const SYNC_LOCK_BINDING: &str = "SYNC_LOCK";
+
+/// Durable Object storage key holding the current lease
+const LEASE_KEY: &str = "lease";
+
+/// Lock request sent from the worker to the Durable Object
+#[derive(Debug, Serialize, Deserialize)]
+struct LockRequest {
+    key: String,
+    holder: String,
+    ttl_secs: i64,
+}
+
+/// Lease-based lock keyed by object mapping
+#[durable_object]
+pub struct SyncLock {
+    state: State,
+}
+
+impl SyncLock {
+    /// Stored lease, if any; read errors are returned rather than taken as a free lock
+    async fn current_lease(&self) -> Result<Option<LockLease>> {
+        let storage = self.state.storage();
+        // `get` fails the same way for a missing key as for a failed read
+        let stored = storage.get_multiple(vec![LEASE_KEY]).await?;
+        if !stored.has(&JsValue::from_str(LEASE_KEY)) {
+            return Ok(None);
+        }
+        storage.get(LEASE_KEY).await.map(Some)
+    }
+}
+
+#[durable_object]
+impl DurableObject for SyncLock {
+    fn new(state: State, _env: Env) -> Self {
+        Self { state }
+    }
+
+    async fn fetch(&mut self, mut req: Request) -> Result<Response> {
+        let request: LockRequest = req.json().await?;
+        let current = self.current_lease().await?;
+        let now = Utc::now();
+        let ttl = Duration::seconds(request.ttl_secs);
+        let lease = LockLease {
+            key: request.key,
+            holder: request.holder,
+            expires_at: now,
+        };
+
+        match req.path().as_str() {
+            "/acquire" => {
+                let granted = acquire_lease(current.as_ref(), &lease.key, &lease.holder, ttl, now);
+                if let Some(granted) = &granted {
+                    self.state.storage().put(LEASE_KEY, granted).await?;
+                }
+                Response::from_json(&granted)
+            }
+            "/renew" => {
+                let renewed = renew_lease(current.as_ref(), &lease, ttl, now);
+                if let Some(renewed) = &renewed {
+                    self.state.storage().put(LEASE_KEY, renewed).await?;
+                }
+                Response::from_json(&renewed)
+            }
+            "/release" => {
+                if may_release(current.as_ref(), &lease) {
+                    self.state.storage().delete(LEASE_KEY).await?;
+                }
+                Response::empty()
+            }
+            _ => Response::error("Not Found", 404),
+        }
+    }
+}
+
+/// `Lock` implementation backed by the `SyncLock` Durable Object
+pub struct DurableObjectLock {
+    env: Env,
+}
+
+impl DurableObjectLock {
+    /// Create a lock using the worker's `SYNC_LOCK` binding
+    pub fn new(env: Env) -> Self {
+        Self { env }
+    }
+
+    async fn call(&self, action: &str, request: &LockRequest) -> Result<Response> {
+        let body = serde_json::to_string(request)?;
+        let mut init = RequestInit::new();
+        init.with_method(Method::Post)
+            .with_body(Some(JsValue::from_str(&body)));
+        let req = Request::new_with_init(&format!("https://sync-lock/{}", action), &init)?;
+
+        self.env
+            .durable_object(SYNC_LOCK_BINDING)?
+            .id_from_name(&request.key)?
+            .get_stub()?
+            .fetch_with_request(req)
+            .await
+    }
+}
+
+#[async_trait(?Send)]
+impl Lock for DurableObjectLock {
+    async fn acquire(
+        &self,
+        key: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> crate::Result<Option<LockLease>> {
+        let request = LockRequest {
+            key: key.to_string(),
+            holder: holder.to_string(),
+            ttl_secs: ttl.num_seconds(),
+        };
+        Ok(self.call("acquire", &request).await?.json().await?)
+    }
+
+    async fn renew(&self, lease: &LockLease, ttl: Duration) -> crate::Result<Option<LockLease>> {
+        let request = LockRequest {
+            key: lease.key.clone(),
+            holder: lease.holder.clone(),
+            ttl_secs: ttl.num_seconds(),
+        };
+        Ok(self.call("renew", &request).await?.json().await?)
+    }
+
+    async fn release(&self, lease: &LockLease) -> crate::Result<()> {
+        let request = LockRequest {
+            key: lease.key.clone(),
+            holder: lease.holder.clone(),
+            ttl_secs: 0,
+        };
+        self.call("release", &request).await?;
+        Ok(())
+    }
+}
@@ -1,14 +1,60 @@
 //! Cloudflare Worker entry point and HTTP handlers.
 
+mod lock;
 mod middleware;
+pub mod queue;
 mod routes;
+pub mod scheduled;
 
+pub use lock::{DurableObjectLock, SyncLock};
 pub use routes::handle_request;
 
+use crate::config::Config;
+use crate::storage::D1Storage;
+use crate::sync::SyncEngine;
+use std::rc::Rc;
 use worker::{event, Context, Env, Request, Response, Result};
 
+/// D1 database binding for sync state
+pub const SYNC_DB_BINDING: &str = "SYNC_DB";
+
 /// Main worker entry point
 #[event(fetch)]
 pub async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
     handle_request(req, env).await
 }
+
+/// Load configuration from worker secrets and vars
+pub fn load_config(env: &Env) -> crate::Result<Config> {
+    Config::from_lookup(|name| {
+        env.secret(name)
+            .or_else(|_| env.var(name))
+            .ok()
+            .map(|value| value.to_string())
+    })
+}
+
+/// Storage backed by the worker's D1 database
+pub fn storage() -> D1Storage {
+    D1Storage::new(SYNC_DB_BINDING)
+}
+
+/// Sync engine for a worker entry point, on D1 storage and the `SyncLock`
+/// Durable Object, so runs started from any entry point exclude each other
+pub fn worker_engine(env: &Env, config: Config) -> SyncEngine {
+    SyncEngine::new(config)
+        .with_storage(Rc::new(storage()))
+        .with_lock(Rc::new(DurableObjectLock::new(env.clone())))
+}
+
+impl From<crate::Error> for worker::Error {
+    fn from(err: crate::Error) -> Self {
+        worker::Error::RustError(err.to_string())
+    }
+}
+
+impl From<worker::Error> for crate::Error {
+    fn from(err: worker::Error) -> Self {
+        crate::Error::Http(err.to_string())
+    }
+}
@@ -0,0 +1,72 @@
+//! Queue consumer for asynchronous webhook processing.
+
+use crate::storage::Storage;
+use crate::sync::queue::{FailedMessage, MessageOutcome, WebhookMessage};
+use crate::sync::SyncTrigger;
+use worker::{
+    event, Context, Env, Message, MessageBatch, MessageExt, QueueRetryOptionsBuilder, Result,
+};
+
+/// Queue producer binding for verified webhook events
+pub const WEBHOOK_QUEUE_BINDING: &str = "WEBHOOK_QUEUE";
+
+/// Dead letter queue receiving messages that exhausted `max_retries`
+pub const DEAD_LETTER_QUEUE: &str = "attio-sfdc-webhooks-dlq";
+
+/// Queue consumer entry point
+#[event(queue)]
+pub async fn queue(batch: MessageBatch<serde_json::Value>, env: Env, _ctx: Context) -> Result<()> {
+    let queue_name = batch.queue();
+    let messages = batch.messages()?;
+    let storage = super::storage();
+
+    if queue_name == DEAD_LETTER_QUEUE {
+        for message in &messages {
+            record_failure(&storage, &queue_name, message, "Retries exhausted").await;
+        }
+        return Ok(());
+    }
+
+    let config = super::load_config(&env)?;
+    let mut engine = super::worker_engine(&env, config).with_trigger(SyncTrigger::Webhook);
+
+    for message in &messages {
+        let result = match serde_json::from_value::<WebhookMessage>(message.body().clone()) {
+            Ok(WebhookMessage::Attio(event)) => engine.handle_attio_event(&event).await,
+            Ok(WebhookMessage::Salesforce(event)) => engine.handle_salesforce_event(&event).await,
+            Err(e) => Err(e.into()),
+        };
+
+        match MessageOutcome::from_result(&result) {
+            MessageOutcome::Ack => message.ack(),
+            MessageOutcome::Retry { delay_seconds } => {
+                let options = QueueRetryOptionsBuilder::new()
+                    .with_delay_seconds(delay_seconds)
+                    .build();
+                message.retry_with_options(&options);
+            }
+            MessageOutcome::Fail { error } => {
+                record_failure(&storage, &queue_name, message, &error).await;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Record a failed message and acknowledge it, retrying if the record cannot be written
+async fn record_failure(
+    storage: &dyn Storage,
+    queue_name: &str,
+    message: &Message<serde_json::Value>,
+    error: &str,
+) {
+    let failure = FailedMessage::new(message.id(), queue_name, message.body().clone(), error);
+    match storage.record_failure(&failure).await {
+        Ok(()) => message.ack(),
+        Err(e) => {
+            worker::console_error!("Failed to record failure for {}: {}", message.id(), e);
+            message.retry();
+        }
+    }
+}
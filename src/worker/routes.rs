@@ -1,6 +1,28 @@
 //! HTTP route handlers.
 
-use worker::{Env, Request, Response, Result, RouteContext, Router};
+use super::middleware::{bad_request, internal_error, not_found, unauthorized};
+use super::queue::WEBHOOK_QUEUE_BINDING;
+use super::{load_config, storage, worker_engine};
+use crate::attio::webhooks as attio_webhooks;
+use crate::salesforce::webhooks as salesforce_webhooks;
+use crate::storage::{Page, Storage, DEFAULT_PAGE_SIZE};
+use crate::sync::pending;
+use crate::sync::queue::WebhookMessage;
+use crate::sync::scheduled::run_manual_sync;
+use crate::sync::{CandidateFilter, ConflictFilter, ManualResolution, RunStatus, SyncMode};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use worker::{Env, Request, Response, Result, Router};
+
+/// Default number of failures returned by the failures endpoint
+const DEFAULT_FAILURE_LIMIT: usize = 50;
+
+/// Header identifying who resolved a conflict through the API
+const RESOLVED_BY_HEADER: &str = "X-Resolved-By";
+
+/// Header identifying who reviewed a link candidate through the API
+const REVIEWED_BY_HEADER: &str = "X-Reviewed-By";
 
 /// Handle incoming HTTP request
 pub async fn handle_request(req: Request, env: Env) -> Result<Response> {
@@ -10,52 +32,260 @@ pub async fn handle_request(req: Request, env: Env) -> Result<Response> {
         // Health check
         .get("/health", |_, _| Response::ok("OK"))
         // API status
-        .get("/api/v1/status", |_, _| {
+        .get_async("/api/v1/status", |_req, ctx| async move {
+            let config = match load_config(&ctx.env) {
+                Ok(config) => config,
+                Err(e) => return Ok(internal_error(&e.to_string())),
+            };
+            let max_age = Duration::seconds(config.sync.stale_reference_secs);
+            let stale = pending::list_stale(&storage(), max_age, DEFAULT_PAGE_SIZE).await?;
             Response::from_json(&serde_json::json!({
                 "status": "healthy",
-                "version": env!("CARGO_PKG_VERSION")
+                "version": env!("CARGO_PKG_VERSION"),
+                "stale_references": stale
             }))
         })
         // Attio webhook
-        .post_async("/webhooks/attio", |_req, _ctx| async move {
-            // TODO: Implement webhook handling
-            // 1. Verify signature
-            // 2. Parse payload
-            // 3. Trigger sync
-            Response::ok("Webhook received")
+        .post_async("/webhooks/attio", |mut req, ctx| async move {
+            let config = match load_config(&ctx.env) {
+                Ok(config) => config,
+                Err(e) => return Ok(internal_error(&e.to_string())),
+            };
+            let payload = req.bytes().await?;
+            if !signature_valid(
+                &req,
+                attio_webhooks::SIGNATURE_HEADER,
+                &payload,
+                config.attio.webhook_secret.as_deref(),
+                attio_webhooks::verify_signature,
+            ) {
+                return Ok(unauthorized());
+            }
+            let event = match attio_webhooks::parse_webhook(&payload) {
+                Ok(event) => event,
+                Err(e) => return Ok(bad_request(&e.to_string())),
+            };
+            if !config.sync.webhook_enabled {
+                return Response::ok("Webhook sync disabled");
+            }
+            enqueue(&ctx.env, WebhookMessage::Attio(event)).await
         })
         // Salesforce webhook
-        .post_async("/webhooks/salesforce", |_req, _ctx| async move {
-            // TODO: Implement webhook handling
-            Response::ok("Webhook received")
+        .post_async("/webhooks/salesforce", |mut req, ctx| async move {
+            let config = match load_config(&ctx.env) {
+                Ok(config) => config,
+                Err(e) => return Ok(internal_error(&e.to_string())),
+            };
+            let payload = req.bytes().await?;
+            if !signature_valid(
+                &req,
+                salesforce_webhooks::SIGNATURE_HEADER,
+                &payload,
+                config.salesforce.webhook_secret.as_deref(),
+                salesforce_webhooks::verify_signature,
+            ) {
+                return Ok(unauthorized());
+            }
+            let event = match salesforce_webhooks::parse_webhook(&payload) {
+                Ok(event) => event,
+                Err(e) => return Ok(bad_request(&e.to_string())),
+            };
+            if !config.sync.webhook_enabled {
+                return Response::ok("Webhook sync disabled");
+            }
+            enqueue(&ctx.env, WebhookMessage::Salesforce(event)).await
         })
         // Manual sync trigger
-        .post_async("/api/v1/sync", |_req, _ctx| async move {
-            // TODO: Implement sync trigger
+        .post_async("/api/v1/sync", |mut req, ctx| async move {
+            let body = req.text().await?;
+            let request: SyncRequest = if body.trim().is_empty() {
+                SyncRequest::default()
+            } else {
+                match serde_json::from_str(&body) {
+                    Ok(request) => request,
+                    Err(e) => return Ok(bad_request(&e.to_string())),
+                }
+            };
+            let config = match load_config(&ctx.env) {
+                Ok(config) => config,
+                Err(e) => return Ok(internal_error(&e.to_string())),
+            };
+            let mode = if request.dry_run {
+                SyncMode::Plan
+            } else {
+                SyncMode::Apply
+            };
+
+            let mut engine = worker_engine(&ctx.env, config).with_mode(mode);
+            let result = if request.full {
+                engine.full_sync(request.restart).await
+            } else {
+                run_manual_sync(&mut engine, &storage(), request.object.as_deref()).await
+            };
+            let result = match result {
+                Ok(result) => result,
+                Err(e) => return Ok(internal_error(&e.to_string())),
+            };
             Response::from_json(&serde_json::json!({
-                "status": "sync_started",
-                "job_id": "job_123"
+                "status": if request.dry_run { "planned" } else { "completed" },
+                "records_processed": result.records_processed,
+                "records_created": result.records_created,
+                "records_updated": result.records_updated,
+                "records_deleted": result.records_deleted,
+                "records_unchanged": result.records_unchanged,
+                "records_linked": result.records_linked,
+                "link_candidates": result.link_candidates,
+                "conflicts": result.conflicts,
+                "errors": result.errors,
+                "failures": result.failures,
+                "plan": result.plan
             }))
         })
         // Get sync history
-        .get_async("/api/v1/history", |_req, _ctx| async move {
-            // TODO: Implement history retrieval
+        .get_async("/api/v1/history", |req, _ctx| async move {
+            let params = query_params(&req)?;
+            let status = match params
+                .get("status")
+                .map(|s| s.parse::<RunStatus>())
+                .transpose()
+            {
+                Ok(status) => status,
+                Err(e) => return Ok(bad_request(&e.to_string())),
+            };
+            let page = page_param(&params);
+            let runs = storage().list_runs(status, page).await?;
             Response::from_json(&serde_json::json!({
-                "history": []
+                "history": runs,
+                "offset": page.offset,
+                "limit": page.limit
             }))
         })
+        // Get a single sync run
+        .get_async("/api/v1/history/:id", |_req, ctx| async move {
+            let Some(id) = ctx.param("id") else {
+                return Ok(not_found());
+            };
+            match storage().get_run(id).await? {
+                Some(run) => Response::from_json(&run),
+                None => Ok(not_found()),
+            }
+        })
         // Get conflicts
-        .get_async("/api/v1/conflicts", |_req, _ctx| async move {
-            // TODO: Implement conflict retrieval
+        .get_async("/api/v1/conflicts", |req, _ctx| async move {
+            let params = query_params(&req)?;
+            let filter = match conflict_filter(&params) {
+                Ok(filter) => filter,
+                Err(e) => return Ok(bad_request(&e.to_string())),
+            };
+            let page = page_param(&params);
+            let conflicts = storage().list_conflicts(&filter, page).await?;
             Response::from_json(&serde_json::json!({
-                "conflicts": []
+                "conflicts": conflicts,
+                "offset": page.offset,
+                "limit": page.limit
             }))
         })
         // Resolve conflict
-        .post_async("/api/v1/conflicts/:id", |_req, _ctx| async move {
-            // TODO: Implement conflict resolution
+        .post_async("/api/v1/conflicts/:id", |mut req, ctx| async move {
+            let Some(id) = ctx.param("id").cloned() else {
+                return Ok(not_found());
+            };
+            let resolution: ManualResolution = match req.json().await {
+                Ok(resolution) => resolution,
+                Err(e) => return Ok(bad_request(&e.to_string())),
+            };
+            let resolved_by = req
+                .headers()
+                .get(RESOLVED_BY_HEADER)?
+                .unwrap_or_else(|| "api".to_string());
+            let config = match load_config(&ctx.env) {
+                Ok(config) => config,
+                Err(e) => return Ok(internal_error(&e.to_string())),
+            };
+
+            let mut engine = worker_engine(&ctx.env, config);
+            match engine
+                .resolve_conflict(&id, &resolution, &resolved_by)
+                .await
+            {
+                Ok(conflict) => Response::from_json(&conflict),
+                Err(crate::Error::NotFound { .. }) => Ok(not_found()),
+                Err(e @ crate::Error::Validation { .. }) => Ok(bad_request(&e.to_string())),
+                Err(e) => Ok(internal_error(&e.to_string())),
+            }
+        })
+        // Get link candidates
+        .get_async("/api/v1/links/candidates", |req, _ctx| async move {
+            let params = query_params(&req)?;
+            let filter = match candidate_filter(&params) {
+                Ok(filter) => filter,
+                Err(e) => return Ok(bad_request(&e.to_string())),
+            };
+            let page = page_param(&params);
+            let candidates = storage().list_link_candidates(&filter, page).await?;
+            Response::from_json(&serde_json::json!({
+                "candidates": candidates,
+                "offset": page.offset,
+                "limit": page.limit
+            }))
+        })
+        // Approve or reject a link candidate
+        .post_async(
+            "/api/v1/links/candidates/:id/:action",
+            |req, ctx| async move {
+                let (Some(id), Some(action)) = (ctx.param("id"), ctx.param("action")) else {
+                    return Ok(not_found());
+                };
+                let approve = match action.as_str() {
+                    "approve" => true,
+                    "reject" => false,
+                    _ => return Ok(not_found()),
+                };
+                let reviewed_by = req
+                    .headers()
+                    .get(REVIEWED_BY_HEADER)?
+                    .unwrap_or_else(|| "api".to_string());
+                let config = match load_config(&ctx.env) {
+                    Ok(config) => config,
+                    Err(e) => return Ok(internal_error(&e.to_string())),
+                };
+
+                let mut engine = worker_engine(&ctx.env, config);
+                match engine
+                    .review_link_candidate(id, approve, &reviewed_by)
+                    .await
+                {
+                    Ok(candidate) => Response::from_json(&candidate),
+                    Err(crate::Error::NotFound { .. }) => Ok(not_found()),
+                    Err(e @ (crate::Error::Validation { .. } | crate::Error::Conflict { .. })) => {
+                        Ok(bad_request(&e.to_string()))
+                    }
+                    Err(e) => Ok(internal_error(&e.to_string())),
+                }
+            },
+        )
+        // Get the audit trail of a record (Attio or Salesforce)
+        .get_async("/api/v1/records/:object/:id/audit", |req, ctx| async move {
+            let (Some(object), Some(id)) = (ctx.param("object"), ctx.param("id")) else {
+                return Ok(not_found());
+            };
+            let page = page_param(&query_params(&req)?);
+            let entries = storage().list_audit_entries(object, id, page).await?;
+            Response::from_json(&serde_json::json!({
+                "audit": entries,
+                "offset": page.offset,
+                "limit": page.limit
+            }))
+        })
+        // Get webhook messages that could not be processed
+        .get_async("/api/v1/failures", |req, _ctx| async move {
+            let limit = query_params(&req)?
+                .get("limit")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(DEFAULT_FAILURE_LIMIT);
+            let failures = storage().list_failures(limit).await?;
             Response::from_json(&serde_json::json!({
-                "status": "resolved"
+                "failures": failures
             }))
         })
         // Get mappings
@@ -67,3 +297,91 @@ pub async fn handle_request(req: Request, env: Env) -> Result<Response> {
         .run(req, env)
         .await
 }
+
+/// Body of a manual sync request
+#[derive(Debug, Default, Deserialize)]
+struct SyncRequest {
+    /// Only report what would be written
+    #[serde(default)]
+    dry_run: bool,
+
+    /// Restrict the sync to one object (Attio or Salesforce name)
+    object: Option<String>,
+
+    /// Sync every record, resuming from the last full sync checkpoint
+    #[serde(default)]
+    full: bool,
+
+    /// Discard the full sync checkpoint and start over
+    #[serde(default)]
+    restart: bool,
+}
+
+/// Query string parameters of a request
+fn query_params(req: &Request) -> Result<HashMap<String, String>> {
+    Ok(req.url()?.query_pairs().into_owned().collect())
+}
+
+/// Page from `page` (1-based) and `per_page` query parameters
+fn page_param(params: &HashMap<String, String>) -> Page {
+    let number = params.get("page").and_then(|v| v.parse().ok()).unwrap_or(1);
+    let size = params
+        .get("per_page")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_PAGE_SIZE);
+    Page::new(number, size)
+}
+
+/// Conflict filter from `object`, `status`, `older_than_hours` and `newer_than_hours`
+fn conflict_filter(params: &HashMap<String, String>) -> crate::Result<ConflictFilter> {
+    let hours_ago = |key: &str| -> crate::Result<Option<_>> {
+        params
+            .get(key)
+            .map(|v| {
+                v.parse()
+                    .map(|hours| Utc::now() - Duration::hours(hours))
+                    .map_err(|_| crate::Error::validation(format!("Invalid {}: {}", key, v)))
+            })
+            .transpose()
+    };
+
+    Ok(ConflictFilter {
+        object: params.get("object").cloned(),
+        record_id: params.get("record_id").cloned(),
+        status: params.get("status").map(|s| s.parse()).transpose()?,
+        detected_before: hours_ago("older_than_hours")?,
+        detected_after: hours_ago("newer_than_hours")?,
+    })
+}
+
+/// Link candidate filter from `object`, `record_id` and `status`
+fn candidate_filter(params: &HashMap<String, String>) -> crate::Result<CandidateFilter> {
+    Ok(CandidateFilter {
+        object: params.get("object").cloned(),
+        record_id: params.get("record_id").cloned(),
+        status: params.get("status").map(|s| s.parse()).transpose()?,
+    })
+}
+
+/// Check the request signature header against the configured secret
+fn signature_valid(
+    req: &Request,
+    header: &str,
+    payload: &[u8],
+    secret: Option<&str>,
+    verify: fn(&[u8], &str, &str) -> crate::Result<()>,
+) -> bool {
+    let Some(secret) = secret else {
+        return false;
+    };
+    match req.headers().get(header) {
+        Ok(Some(signature)) => verify(payload, &signature, secret).is_ok(),
+        _ => false,
+    }
+}
+
+/// Enqueue a verified webhook event and acknowledge receipt
+async fn enqueue(env: &Env, message: WebhookMessage) -> Result<Response> {
+    env.queue(WEBHOOK_QUEUE_BINDING)?.send(&message).await?;
+    Ok(Response::empty()?.with_status(202))
+}
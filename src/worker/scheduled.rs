@@ -0,0 +1,34 @@
+//! Cron trigger handler for scheduled incremental sync.
+
+use crate::sync::scheduled::run_scheduled_sync;
+use crate::sync::SyncTrigger;
+use worker::{event, Env, ScheduleContext, ScheduledEvent};
+
+/// Scheduled sync entry point
+#[event(scheduled)]
+pub async fn scheduled(_event: ScheduledEvent, env: Env, _ctx: ScheduleContext) {
+    let config = match super::load_config(&env) {
+        Ok(config) => config,
+        Err(e) => {
+            worker::console_error!("Scheduled sync failed to load config: {}", e);
+            return;
+        }
+    };
+
+    let mut engine = super::worker_engine(&env, config).with_trigger(SyncTrigger::Scheduled);
+    match run_scheduled_sync(&mut engine, &super::storage()).await {
+        Ok(runs) => {
+            for run in runs {
+                worker::console_log!(
+                    "Scheduled sync {} ({}): {:?}, {} processed, {} errors",
+                    run.id,
+                    run.objects.join(", "),
+                    run.status,
+                    run.records_processed,
+                    run.errors
+                );
+            }
+        }
+        Err(e) => worker::console_error!("Scheduled sync failed: {}", e),
+    }
+}